@@ -8,9 +8,13 @@ use solana_sdk::{
 
 use raydium_amm_v3::accounts as raydium_accounts;
 use raydium_amm_v3::instruction as raydium_instruction;
+use raydium_amm_v3::instructions::FundRewardMode;
 use raydium_amm_v3::states::{
-    AMM_CONFIG_SEED, OBSERVATION_SEED, OPERATION_SEED, POOL_SEED, POOL_VAULT_SEED, POSITION_SEED,
-    TICK_ARRAY_SEED,
+    AdminChange, AmmConfigUpdate, FeeRecipient, MintRegistryUpdate, OperationAccountUpdate,
+    RewardScheduleKind, SwapAllowlistUpdate, AMM_CONFIG_PENDING_OWNER_SEED, AMM_CONFIG_SEED,
+    FEE_DISTRIBUTION_SEED, MINT_REGISTRY_SEED, OBSERVATION_SEED, OPERATION_SEED,
+    POOL_REWARD_EXTENSION_SEED, POOL_SEED, POOL_VAULT_SEED, POSITION_LOCK_SEED, POSITION_SEED,
+    SCHEDULED_CHANGE_SEED, SWAP_ALLOWLIST_SEED, TICK_ARRAY_SEED,
 };
 use std::rc::Rc;
 
@@ -51,12 +55,10 @@ pub fn create_amm_config_instr(
     Ok(instructions)
 }
 
-pub fn update_amm_config_instr(
+pub fn update_amm_config_v2_instr(
     config: &ClientConfig,
     amm_config: Pubkey,
-    remaining_accounts: Vec<AccountMeta>,
-    param: u8,
-    value: u32,
+    update: AmmConfigUpdate,
 ) -> Result<Vec<Instruction>> {
     let payer = read_keypair_file(&config.payer_path)?;
     let admin = read_keypair_file(&config.admin_path)?;
@@ -66,12 +68,115 @@ pub fn update_amm_config_instr(
     let program = client.program(config.raydium_v3_program)?;
     let instructions = program
         .request()
-        .accounts(raydium_accounts::UpdateAmmConfig {
+        .accounts(raydium_accounts::UpdateAmmConfigV2 {
             owner: admin.pubkey(),
             amm_config,
         })
-        .accounts(remaining_accounts)
-        .args(raydium_instruction::UpdateAmmConfig { param, value })
+        .args(raydium_instruction::UpdateAmmConfigV2 { update })
+        .instructions()?;
+    Ok(instructions)
+}
+
+pub fn propose_amm_config_owner_instr(
+    config: &ClientConfig,
+    amm_config: Pubkey,
+    new_owner: Option<Pubkey>,
+    new_fund_owner: Option<Pubkey>,
+) -> Result<Vec<Instruction>> {
+    let admin = read_keypair_file(&config.admin_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(admin));
+    let program = client.program(config.raydium_v3_program)?;
+    let (pending_owner, __bump) = Pubkey::find_program_address(
+        &[
+            AMM_CONFIG_PENDING_OWNER_SEED.as_bytes(),
+            amm_config.to_bytes().as_ref(),
+        ],
+        &program.id(),
+    );
+    let instructions = program
+        .request()
+        .accounts(raydium_accounts::ProposeAmmConfigOwner {
+            authority: program.payer(),
+            amm_config,
+            pending_owner,
+            system_program: system_program::id(),
+        })
+        .args(raydium_instruction::ProposeAmmConfigOwner {
+            new_owner,
+            new_fund_owner,
+        })
+        .instructions()?;
+    Ok(instructions)
+}
+
+pub fn accept_amm_config_owner_instr(
+    config: &ClientConfig,
+    amm_config: Pubkey,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.raydium_v3_program)?;
+    let (pending_owner, __bump) = Pubkey::find_program_address(
+        &[
+            AMM_CONFIG_PENDING_OWNER_SEED.as_bytes(),
+            amm_config.to_bytes().as_ref(),
+        ],
+        &program.id(),
+    );
+    let instructions = program
+        .request()
+        .accounts(raydium_accounts::AcceptAmmConfigOwner {
+            new_owner: program.payer(),
+            amm_config,
+            pending_owner,
+        })
+        .args(raydium_instruction::AcceptAmmConfigOwner {})
+        .instructions()?;
+    Ok(instructions)
+}
+
+pub fn create_mint_registry_instr(config: &ClientConfig) -> Result<Vec<Instruction>> {
+    let admin = read_keypair_file(&config.admin_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(admin));
+    let program = client.program(config.raydium_v3_program)?;
+    let (mint_registry, __bump) =
+        Pubkey::find_program_address(&[MINT_REGISTRY_SEED.as_bytes()], &program.id());
+    let instructions = program
+        .request()
+        .accounts(raydium_accounts::CreateMintRegistry {
+            owner: program.payer(),
+            mint_registry,
+            system_program: system_program::id(),
+        })
+        .args(raydium_instruction::CreateMintRegistry {})
+        .instructions()?;
+    Ok(instructions)
+}
+
+pub fn update_mint_registry_instr(
+    config: &ClientConfig,
+    update: MintRegistryUpdate,
+) -> Result<Vec<Instruction>> {
+    let admin = read_keypair_file(&config.admin_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(admin));
+    let program = client.program(config.raydium_v3_program)?;
+    let (mint_registry, __bump) =
+        Pubkey::find_program_address(&[MINT_REGISTRY_SEED.as_bytes()], &program.id());
+    let instructions = program
+        .request()
+        .accounts(raydium_accounts::UpdateMintRegistry {
+            owner: program.payer(),
+            mint_registry,
+        })
+        .args(raydium_instruction::UpdateMintRegistry { update })
         .instructions()?;
     Ok(instructions)
 }
@@ -98,8 +203,7 @@ pub fn create_operation_account_instr(config: &ClientConfig) -> Result<Vec<Instr
 
 pub fn update_operation_account_instr(
     config: &ClientConfig,
-    param: u8,
-    keys: Vec<Pubkey>,
+    update: OperationAccountUpdate,
 ) -> Result<Vec<Instruction>> {
     let payer = read_keypair_file(&config.admin_path)?;
     let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
@@ -115,7 +219,7 @@ pub fn update_operation_account_instr(
             operation_state: operation_account_key,
             system_program: system_program::id(),
         })
-        .args(raydium_instruction::UpdateOperationAccount { param, keys })
+        .args(raydium_instruction::UpdateOperationAccount { update })
         .instructions()?;
     Ok(instructions)
 }
@@ -168,6 +272,8 @@ pub fn create_pool_instr(
         ],
         &program.id(),
     );
+    let (mint_registry, __bump) =
+        Pubkey::find_program_address(&[MINT_REGISTRY_SEED.as_bytes()], &program.id());
     let instructions = program
         .request()
         .accounts(raydium_accounts::CreatePool {
@@ -185,6 +291,8 @@ pub fn create_pool_instr(
             system_program: system_program::id(),
             rent: sysvar::rent::id(),
         })
+        // the token-2022 mints are checked against the mint registry
+        .accounts(vec![AccountMeta::new_readonly(mint_registry, false)])
         .args(raydium_instruction::CreatePool {
             sqrt_price_x64,
             open_time,
@@ -193,9 +301,11 @@ pub fn create_pool_instr(
     Ok(instructions)
 }
 
+/// `remaining_accounts` must include the reward extension of the pool once it has one
 pub fn open_position_instr(
     config: &ClientConfig,
     pool_account_key: Pubkey,
+    amm_config: Pubkey,
     token_vault_0: Pubkey,
     token_vault_1: Pubkey,
     token_mint_0: Pubkey,
@@ -285,6 +395,8 @@ pub fn open_position_instr(
             vault_1_mint: token_mint_1,
         })
         .accounts(remaining_accounts)
+        // the status of the amm config is inherited by the pool when the config is passed
+        .accounts(vec![AccountMeta::new_readonly(amm_config, false)])
         .args(raydium_instruction::OpenPositionV2 {
             liquidity,
             amount_0_max,
@@ -300,9 +412,11 @@ pub fn open_position_instr(
     Ok(instructions)
 }
 
+/// `remaining_accounts` must include the reward extension of the pool once it has one
 pub fn open_position_with_token22_nft_instr(
     config: &ClientConfig,
     pool_account_key: Pubkey,
+    amm_config: Pubkey,
     token_vault_0: Pubkey,
     token_vault_1: Pubkey,
     token_mint_0: Pubkey,
@@ -386,6 +500,8 @@ pub fn open_position_with_token22_nft_instr(
             vault_1_mint: token_mint_1,
         })
         .accounts(remaining_accounts)
+        // the status of the amm config is inherited by the pool when the config is passed
+        .accounts(vec![AccountMeta::new_readonly(amm_config, false)])
         .args(raydium_instruction::OpenPositionWithToken22Nft {
             liquidity,
             amount_0_max,
@@ -401,9 +517,11 @@ pub fn open_position_with_token22_nft_instr(
     Ok(instructions)
 }
 
+/// `remaining_accounts` must include the reward extension of the pool once it has one
 pub fn increase_liquidity_instr(
     config: &ClientConfig,
     pool_account_key: Pubkey,
+    amm_config: Pubkey,
     token_vault_0: Pubkey,
     token_vault_1: Pubkey,
     token_mint_0: Pubkey,
@@ -476,6 +594,8 @@ pub fn increase_liquidity_instr(
             vault_1_mint: token_mint_1,
         })
         .accounts(remaining_accounts)
+        // the status of the amm config is inherited by the pool when the config is passed
+        .accounts(vec![AccountMeta::new_readonly(amm_config, false)])
         .args(raydium_instruction::IncreaseLiquidityV2 {
             liquidity,
             amount_0_max,
@@ -486,9 +606,11 @@ pub fn increase_liquidity_instr(
     Ok(instructions)
 }
 
+/// `remaining_accounts` must include the reward extension of the pool once it has one
 pub fn decrease_liquidity_instr(
     config: &ClientConfig,
     pool_account_key: Pubkey,
+    amm_config: Pubkey,
     token_vault_0: Pubkey,
     token_vault_1: Pubkey,
     token_mint_0: Pubkey,
@@ -561,6 +683,8 @@ pub fn decrease_liquidity_instr(
             vault_1_mint: token_mint_1,
         })
         .accounts(remaining_accounts)
+        // the status of the amm config is inherited by the pool when the config is passed
+        .accounts(vec![AccountMeta::new_readonly(amm_config, false)])
         .args(raydium_instruction::DecreaseLiquidityV2 {
             liquidity,
             amount_0_min,
@@ -600,6 +724,7 @@ pub fn close_personal_position_instr(
     Ok(instructions)
 }
 
+/// `remaining_accounts` must include the reward extension of the pool once it has one
 pub fn swap_instr(
     config: &ClientConfig,
     amm_config: Pubkey,
@@ -646,6 +771,7 @@ pub fn swap_instr(
     Ok(instructions)
 }
 
+/// `remaining_accounts` must include the reward extension of the pool once it has one
 pub fn swap_v2_instr(
     config: &ClientConfig,
     amm_config: Pubkey,
@@ -696,6 +822,116 @@ pub fn swap_v2_instr(
     Ok(instructions)
 }
 
+/// The accounts of each hop in `remaining_accounts` must be followed by the reward extension
+/// of its pool once the pool has one
+pub fn swap_router_base_in_instr(
+    config: &ClientConfig,
+    input_token_account: Pubkey,
+    input_token_mint: Pubkey,
+    remaining_accounts: Vec<AccountMeta>,
+    amount_in: u64,
+    amount_out_minimum: u64,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.raydium_v3_program)?;
+    let instructions = program
+        .request()
+        .accounts(raydium_accounts::SwapRouterBaseIn {
+            payer: program.payer(),
+            input_token_account,
+            input_token_mint,
+            token_program: spl_token::id(),
+            token_program_2022: spl_token_2022::id(),
+            memo_program: spl_memo::id(),
+        })
+        .accounts(remaining_accounts)
+        .args(raydium_instruction::SwapRouterBaseIn {
+            amount_in,
+            amount_out_minimum,
+        })
+        .instructions()?;
+    Ok(instructions)
+}
+
+/// The accounts of each hop in `remaining_accounts` must be followed by the reward extension
+/// of its pool once the pool has one
+pub fn swap_router_base_out_instr(
+    config: &ClientConfig,
+    input_token_account: Pubkey,
+    input_token_mint: Pubkey,
+    remaining_accounts: Vec<AccountMeta>,
+    amount_out: u64,
+    amount_in_maximum: u64,
+    hop_account_counts: Vec<u8>,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.raydium_v3_program)?;
+    let instructions = program
+        .request()
+        .accounts(raydium_accounts::SwapRouterBaseIn {
+            payer: program.payer(),
+            input_token_account,
+            input_token_mint,
+            token_program: spl_token::id(),
+            token_program_2022: spl_token_2022::id(),
+            memo_program: spl_memo::id(),
+        })
+        .accounts(remaining_accounts)
+        .args(raydium_instruction::SwapRouterBaseOut {
+            amount_out,
+            amount_in_maximum,
+            hop_account_counts,
+            deadline: None,
+        })
+        .instructions()?;
+    Ok(instructions)
+}
+
+/// The accounts of each hop in `remaining_accounts` must be followed by the reward extension
+/// of its pool once the pool has one
+pub fn swap_router_base_in_v2_instr(
+    config: &ClientConfig,
+    input_token_account: Pubkey,
+    input_token_mint: Pubkey,
+    remaining_accounts: Vec<AccountMeta>,
+    amount_in: u64,
+    amount_out_minimum: u64,
+    hop_account_counts: Vec<u8>,
+    sqrt_price_limits_x64: Vec<u128>,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.raydium_v3_program)?;
+    let instructions = program
+        .request()
+        .accounts(raydium_accounts::SwapRouterBaseIn {
+            payer: program.payer(),
+            input_token_account,
+            input_token_mint,
+            token_program: spl_token::id(),
+            token_program_2022: spl_token_2022::id(),
+            memo_program: spl_memo::id(),
+        })
+        .accounts(remaining_accounts)
+        .args(raydium_instruction::SwapRouterBaseInV2 {
+            amount_in,
+            amount_out_minimum,
+            hop_account_counts,
+            sqrt_price_limits_x64,
+            deadline: None,
+        })
+        .instructions()?;
+    Ok(instructions)
+}
+
 pub fn initialize_reward_instr(
     config: &ClientConfig,
     pool_account_key: Pubkey,
@@ -705,6 +941,7 @@ pub fn initialize_reward_instr(
     reward_token_vault: Pubkey,
     user_reward_token: Pubkey,
     reward_token_program: Pubkey,
+    reward_extension: Option<Pubkey>,
     open_time: u64,
     end_time: u64,
     emissions_per_second_x64: u128,
@@ -714,6 +951,14 @@ pub fn initialize_reward_instr(
     // Client.
     let client = Client::new(url, Rc::new(admin));
     let program = client.program(config.raydium_v3_program)?;
+    // the token-2022 reward mints are checked against the mint registry
+    let (mint_registry, __bump) =
+        Pubkey::find_program_address(&[MINT_REGISTRY_SEED.as_bytes()], &program.id());
+    let mut remaining_accounts = vec![AccountMeta::new_readonly(mint_registry, false)];
+    // the rewards beyond the pool rewards are initialized in the reward extension
+    if let Some(reward_extension) = reward_extension {
+        remaining_accounts.push(AccountMeta::new(reward_extension, false));
+    }
 
     let instructions = program
         .request()
@@ -729,6 +974,7 @@ pub fn initialize_reward_instr(
             system_program: system_program::id(),
             rent: sysvar::rent::id(),
         })
+        .accounts(remaining_accounts)
         .args(raydium_instruction::InitializeReward {
             param: raydium_amm_v3::instructions::InitializeRewardParam {
                 open_time,
@@ -747,6 +993,7 @@ pub fn set_reward_params_instr(
     reward_token_vault: Pubkey,
     user_reward_token: Pubkey,
     operation_account_key: Pubkey,
+    reward_extension: Option<Pubkey>,
     reward_index: u8,
     open_time: u64,
     end_time: u64,
@@ -758,11 +1005,14 @@ pub fn set_reward_params_instr(
     let client = Client::new(url, Rc::new(admin));
     let program = client.program(config.raydium_v3_program)?;
 
-    let remaining_accounts = vec![
+    let mut remaining_accounts = vec![
         AccountMeta::new(reward_token_vault, false),
         AccountMeta::new(user_reward_token, false),
         AccountMeta::new_readonly(spl_token::id(), false),
     ];
+    if let Some(reward_extension) = reward_extension {
+        remaining_accounts.push(AccountMeta::new(reward_extension, false));
+    }
 
     let instructions = program
         .request()
@@ -785,12 +1035,95 @@ pub fn set_reward_params_instr(
     Ok(instructions)
 }
 
-pub fn transfer_reward_owner(
+pub fn fund_reward_instr(
     config: &ClientConfig,
     pool_account_key: Pubkey,
-    new_owner: Pubkey,
-    encode: bool,
-    authority: Option<Pubkey>,
+    funder_token_account: Pubkey,
+    reward_token_vault: Pubkey,
+    reward_vault_mint: Pubkey,
+    reward_extension: Option<Pubkey>,
+    reward_index: u8,
+    amount: u64,
+    mode: FundRewardMode,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.raydium_v3_program)?;
+
+    let mut remaining_accounts = Vec::new();
+    if let Some(reward_extension) = reward_extension {
+        remaining_accounts.push(AccountMeta::new(reward_extension, false));
+    }
+
+    let instructions = program
+        .request()
+        .accounts(raydium_accounts::FundReward {
+            funder: program.payer(),
+            funder_token_account,
+            pool_state: pool_account_key,
+            reward_token_vault,
+            reward_vault_mint,
+            token_program: spl_token::id(),
+            token_program_2022: spl_token_2022::id(),
+        })
+        .accounts(remaining_accounts)
+        .args(raydium_instruction::FundReward {
+            reward_index,
+            amount,
+            mode,
+        })
+        .instructions()?;
+    Ok(instructions)
+}
+
+pub fn retire_reward_instr(
+    config: &ClientConfig,
+    pool_account_key: Pubkey,
+    reward_token_vault: Pubkey,
+    reward_vault_mint: Pubkey,
+    owner_token_account: Pubkey,
+    reward_extension: Option<Pubkey>,
+    reward_index: u8,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.raydium_v3_program)?;
+
+    let mut remaining_accounts = Vec::new();
+    if let Some(reward_extension) = reward_extension {
+        remaining_accounts.push(AccountMeta::new(reward_extension, false));
+    }
+
+    let instructions = program
+        .request()
+        .accounts(raydium_accounts::RetireReward {
+            owner: program.payer(),
+            pool_state: pool_account_key,
+            reward_token_vault,
+            reward_vault_mint,
+            owner_token_account,
+            token_program: spl_token::id(),
+            token_program_2022: spl_token_2022::id(),
+        })
+        .accounts(remaining_accounts)
+        .args(raydium_instruction::RetireReward { reward_index })
+        .instructions()?;
+    Ok(instructions)
+}
+
+pub fn set_reward_schedule_instr(
+    config: &ClientConfig,
+    pool_account_key: Pubkey,
+    operation_account_key: Pubkey,
+    reward_extension: Option<Pubkey>,
+    reward_index: u8,
+    kind: RewardScheduleKind,
+    step_period: u32,
+    end_emissions_per_second_x64: u128,
 ) -> Result<Vec<Instruction>> {
     let admin = read_keypair_file(&config.admin_path)?;
     let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
@@ -798,17 +1131,662 @@ pub fn transfer_reward_owner(
     let client = Client::new(url, Rc::new(admin));
     let program = client.program(config.raydium_v3_program)?;
 
+    let mut remaining_accounts = Vec::new();
+    if let Some(reward_extension) = reward_extension {
+        remaining_accounts.push(AccountMeta::new(reward_extension, false));
+    }
+
     let instructions = program
         .request()
-        .accounts(raydium_accounts::TransferRewardOwner {
-            authority: if encode {
-                authority.unwrap()
-            } else {
-                program.payer()
-            },
+        .accounts(raydium_accounts::SetRewardSchedule {
+            authority: program.payer(),
             pool_state: pool_account_key,
+            operation_state: operation_account_key,
         })
-        .args(raydium_instruction::TransferRewardOwner { new_owner })
+        .accounts(remaining_accounts)
+        .args(raydium_instruction::SetRewardSchedule {
+            reward_index,
+            kind,
+            step_period,
+            end_emissions_per_second_x64,
+        })
+        .instructions()?;
+    Ok(instructions)
+}
+
+fn position_lock_accounts(
+    program_id: &Pubkey,
+    pool_account_key: Pubkey,
+    nft_mint_key: Pubkey,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+    tick_array_lower_start_index: i32,
+    tick_array_upper_start_index: i32,
+) -> (Pubkey, Pubkey, Pubkey, Pubkey, Pubkey) {
+    let (personal_position_key, __bump) = Pubkey::find_program_address(
+        &[POSITION_SEED.as_bytes(), nft_mint_key.to_bytes().as_ref()],
+        program_id,
+    );
+    let (protocol_position_key, __bump) = Pubkey::find_program_address(
+        &[
+            POSITION_SEED.as_bytes(),
+            pool_account_key.to_bytes().as_ref(),
+            &tick_lower_index.to_be_bytes(),
+            &tick_upper_index.to_be_bytes(),
+        ],
+        program_id,
+    );
+    let (tick_array_lower, __bump) = Pubkey::find_program_address(
+        &[
+            TICK_ARRAY_SEED.as_bytes(),
+            pool_account_key.to_bytes().as_ref(),
+            &tick_array_lower_start_index.to_be_bytes(),
+        ],
+        program_id,
+    );
+    let (tick_array_upper, __bump) = Pubkey::find_program_address(
+        &[
+            TICK_ARRAY_SEED.as_bytes(),
+            pool_account_key.to_bytes().as_ref(),
+            &tick_array_upper_start_index.to_be_bytes(),
+        ],
+        program_id,
+    );
+    let (position_lock_key, __bump) = Pubkey::find_program_address(
+        &[
+            POSITION_LOCK_SEED.as_bytes(),
+            nft_mint_key.to_bytes().as_ref(),
+        ],
+        program_id,
+    );
+    (
+        personal_position_key,
+        protocol_position_key,
+        tick_array_lower,
+        tick_array_upper,
+        position_lock_key,
+    )
+}
+
+pub fn lock_position_instr(
+    config: &ClientConfig,
+    pool_account_key: Pubkey,
+    nft_mint_key: Pubkey,
+    nft_token_key: Pubkey,
+    reward_extension: Option<Pubkey>,
+    lock_until: u64,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+    tick_array_lower_start_index: i32,
+    tick_array_upper_start_index: i32,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.raydium_v3_program)?;
+    let (
+        personal_position_key,
+        protocol_position_key,
+        tick_array_lower,
+        tick_array_upper,
+        position_lock_key,
+    ) = position_lock_accounts(
+        &program.id(),
+        pool_account_key,
+        nft_mint_key,
+        tick_lower_index,
+        tick_upper_index,
+        tick_array_lower_start_index,
+        tick_array_upper_start_index,
+    );
+
+    let mut remaining_accounts = Vec::new();
+    if let Some(reward_extension) = reward_extension {
+        remaining_accounts.push(AccountMeta::new(reward_extension, false));
+    }
+
+    let instructions = program
+        .request()
+        .accounts(raydium_accounts::LockPosition {
+            nft_owner: program.payer(),
+            nft_account: nft_token_key,
+            personal_position: personal_position_key,
+            pool_state: pool_account_key,
+            protocol_position: protocol_position_key,
+            tick_array_lower,
+            tick_array_upper,
+            position_lock: position_lock_key,
+            system_program: system_program::id(),
+        })
+        .accounts(remaining_accounts)
+        .args(raydium_instruction::LockPosition { lock_until })
+        .instructions()?;
+    Ok(instructions)
+}
+
+pub fn unlock_position_instr(
+    config: &ClientConfig,
+    pool_account_key: Pubkey,
+    nft_mint_key: Pubkey,
+    rent_payer: Pubkey,
+    reward_extension: Option<Pubkey>,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+    tick_array_lower_start_index: i32,
+    tick_array_upper_start_index: i32,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.raydium_v3_program)?;
+    let (
+        personal_position_key,
+        protocol_position_key,
+        tick_array_lower,
+        tick_array_upper,
+        position_lock_key,
+    ) = position_lock_accounts(
+        &program.id(),
+        pool_account_key,
+        nft_mint_key,
+        tick_lower_index,
+        tick_upper_index,
+        tick_array_lower_start_index,
+        tick_array_upper_start_index,
+    );
+
+    let mut remaining_accounts = Vec::new();
+    if let Some(reward_extension) = reward_extension {
+        remaining_accounts.push(AccountMeta::new(reward_extension, false));
+    }
+
+    let instructions = program
+        .request()
+        .accounts(raydium_accounts::UnlockPosition {
+            payer: program.payer(),
+            rent_payer,
+            personal_position: personal_position_key,
+            pool_state: pool_account_key,
+            protocol_position: protocol_position_key,
+            tick_array_lower,
+            tick_array_upper,
+            position_lock: position_lock_key,
+        })
+        .accounts(remaining_accounts)
+        .args(raydium_instruction::UnlockPosition {})
+        .instructions()?;
+    Ok(instructions)
+}
+
+pub fn set_position_lock_config_instr(
+    config: &ClientConfig,
+    pool_account_key: Pubkey,
+    lock_reward_multiplier: u16,
+    min_lock_duration: u32,
+) -> Result<Vec<Instruction>> {
+    let admin = read_keypair_file(&config.admin_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(admin));
+    let program = client.program(config.raydium_v3_program)?;
+    let instructions = program
+        .request()
+        .accounts(raydium_accounts::SetPositionLockConfig {
+            owner: program.payer(),
+            pool_state: pool_account_key,
+        })
+        .args(raydium_instruction::SetPositionLockConfig {
+            lock_reward_multiplier,
+            min_lock_duration,
+        })
+        .instructions()?;
+    Ok(instructions)
+}
+
+pub fn transfer_reward_owner(
+    config: &ClientConfig,
+    pool_account_key: Pubkey,
+    new_owner: Pubkey,
+    encode: bool,
+    authority: Option<Pubkey>,
+) -> Result<Vec<Instruction>> {
+    let admin = read_keypair_file(&config.admin_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(admin));
+    let program = client.program(config.raydium_v3_program)?;
+
+    let instructions = program
+        .request()
+        .accounts(raydium_accounts::TransferRewardOwner {
+            authority: if encode {
+                authority.unwrap()
+            } else {
+                program.payer()
+            },
+            pool_state: pool_account_key,
+        })
+        .args(raydium_instruction::TransferRewardOwner { new_owner })
+        .instructions()?;
+    Ok(instructions)
+}
+
+pub fn accept_reward_owner_instr(
+    config: &ClientConfig,
+    pool_account_key: Pubkey,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.raydium_v3_program)?;
+    let instructions = program
+        .request()
+        .accounts(raydium_accounts::AcceptRewardOwner {
+            new_owner: program.payer(),
+            pool_state: pool_account_key,
+        })
+        .args(raydium_instruction::AcceptRewardOwner {})
+        .instructions()?;
+    Ok(instructions)
+}
+
+pub fn update_observation_update_duration_instr(
+    config: &ClientConfig,
+    pool_account_key: Pubkey,
+    observation_key: Pubkey,
+    observation_update_duration: u32,
+) -> Result<Vec<Instruction>> {
+    let admin = read_keypair_file(&config.admin_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(admin));
+    let program = client.program(config.raydium_v3_program)?;
+
+    let instructions = program
+        .request()
+        .accounts(raydium_accounts::UpdateObservationUpdateDuration {
+            authority: program.payer(),
+            pool_state: pool_account_key,
+            observation_state: observation_key,
+        })
+        .args(raydium_instruction::UpdateObservationUpdateDuration {
+            observation_update_duration,
+        })
+        .instructions()?;
+    Ok(instructions)
+}
+
+pub fn create_swap_allowlist_instr(
+    config: &ClientConfig,
+    pool_account_key: Pubkey,
+    launch_duration: u64,
+    keys: Vec<Pubkey>,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.raydium_v3_program)?;
+    let (swap_allowlist_key, __bump) = Pubkey::find_program_address(
+        &[
+            SWAP_ALLOWLIST_SEED.as_bytes(),
+            pool_account_key.to_bytes().as_ref(),
+        ],
+        &program.id(),
+    );
+    let instructions = program
+        .request()
+        .accounts(raydium_accounts::CreateSwapAllowlist {
+            owner: program.payer(),
+            pool_state: pool_account_key,
+            swap_allowlist: swap_allowlist_key,
+            system_program: system_program::id(),
+        })
+        .args(raydium_instruction::CreateSwapAllowlist {
+            launch_duration,
+            keys,
+        })
+        .instructions()?;
+    Ok(instructions)
+}
+
+pub fn create_reward_extension_instr(
+    config: &ClientConfig,
+    pool_account_key: Pubkey,
+    operation_account_key: Pubkey,
+) -> Result<Vec<Instruction>> {
+    let admin = read_keypair_file(&config.admin_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(admin));
+    let program = client.program(config.raydium_v3_program)?;
+    let (reward_extension_key, __bump) = Pubkey::find_program_address(
+        &[
+            POOL_REWARD_EXTENSION_SEED.as_bytes(),
+            pool_account_key.to_bytes().as_ref(),
+        ],
+        &program.id(),
+    );
+    let instructions = program
+        .request()
+        .accounts(raydium_accounts::CreateRewardExtension {
+            owner: program.payer(),
+            pool_state: pool_account_key,
+            reward_extension: reward_extension_key,
+            system_program: system_program::id(),
+        })
+        // a reward admin operator is checked against the operation account
+        .accounts(vec![AccountMeta::new_readonly(
+            operation_account_key,
+            false,
+        )])
+        .args(raydium_instruction::CreateRewardExtension {})
+        .instructions()?;
+    Ok(instructions)
+}
+
+pub fn update_swap_allowlist_instr(
+    config: &ClientConfig,
+    pool_account_key: Pubkey,
+    update: SwapAllowlistUpdate,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.raydium_v3_program)?;
+    let (swap_allowlist_key, __bump) = Pubkey::find_program_address(
+        &[
+            SWAP_ALLOWLIST_SEED.as_bytes(),
+            pool_account_key.to_bytes().as_ref(),
+        ],
+        &program.id(),
+    );
+    let instructions = program
+        .request()
+        .accounts(raydium_accounts::UpdateSwapAllowlist {
+            owner: program.payer(),
+            pool_state: pool_account_key,
+            swap_allowlist: swap_allowlist_key,
+        })
+        .args(raydium_instruction::UpdateSwapAllowlist { update })
+        .instructions()?;
+    Ok(instructions)
+}
+
+pub fn update_pool_fee_rates_instr(
+    config: &ClientConfig,
+    amm_config: Pubkey,
+    pool_account_key: Pubkey,
+    trade_fee_rate: Option<u32>,
+    protocol_fee_rate: Option<u32>,
+    fund_fee_rate: Option<u32>,
+) -> Result<Vec<Instruction>> {
+    let admin = read_keypair_file(&config.admin_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(admin));
+    let program = client.program(config.raydium_v3_program)?;
+    let instructions = program
+        .request()
+        .accounts(raydium_accounts::UpdatePoolFeeRates {
+            authority: program.payer(),
+            amm_config,
+            pool_state: pool_account_key,
+        })
+        .args(raydium_instruction::UpdatePoolFeeRates {
+            trade_fee_rate,
+            protocol_fee_rate,
+            fund_fee_rate,
+        })
+        .instructions()?;
+    Ok(instructions)
+}
+
+pub fn update_pool_status_instr(
+    config: &ClientConfig,
+    pool_account_key: Pubkey,
+    status: u8,
+) -> Result<Vec<Instruction>> {
+    let admin = read_keypair_file(&config.admin_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(admin));
+    let program = client.program(config.raydium_v3_program)?;
+    let (operation_account_key, __bump) =
+        Pubkey::find_program_address(&[OPERATION_SEED.as_bytes()], &program.id());
+    let instructions = program
+        .request()
+        .accounts(raydium_accounts::UpdatePoolStatus {
+            authority: program.payer(),
+            pool_state: pool_account_key,
+        })
+        // operators with the pool pauser role are checked against the operation account
+        .accounts(vec![AccountMeta::new_readonly(
+            operation_account_key,
+            false,
+        )])
+        .args(raydium_instruction::UpdatePoolStatus { status })
+        .instructions()?;
+    Ok(instructions)
+}
+
+pub fn pause_amm_config_pools_instr(
+    config: &ClientConfig,
+    amm_config: Pubkey,
+    pools: Vec<Pubkey>,
+    disabled_bits: u8,
+) -> Result<Vec<Instruction>> {
+    let admin = read_keypair_file(&config.admin_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(admin));
+    let program = client.program(config.raydium_v3_program)?;
+    let (operation_account_key, __bump) =
+        Pubkey::find_program_address(&[OPERATION_SEED.as_bytes()], &program.id());
+    // the pause guardian is checked against the operation account
+    let mut remaining_accounts = vec![AccountMeta::new_readonly(operation_account_key, false)];
+    remaining_accounts.extend(pools.into_iter().map(|pool| AccountMeta::new(pool, false)));
+    let instructions = program
+        .request()
+        .accounts(raydium_accounts::PauseAmmConfigPools {
+            authority: program.payer(),
+            amm_config,
+        })
+        .accounts(remaining_accounts)
+        .args(raydium_instruction::PauseAmmConfigPools { disabled_bits })
+        .instructions()?;
+    Ok(instructions)
+}
+
+pub fn schedule_admin_change_instr(
+    config: &ClientConfig,
+    target: Pubkey,
+    nonce: u64,
+    change: AdminChange,
+) -> Result<Vec<Instruction>> {
+    let admin = read_keypair_file(&config.admin_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(admin));
+    let program = client.program(config.raydium_v3_program)?;
+    let (scheduled_change, __bump) = Pubkey::find_program_address(
+        &[
+            SCHEDULED_CHANGE_SEED.as_bytes(),
+            target.to_bytes().as_ref(),
+            &nonce.to_be_bytes(),
+        ],
+        &program.id(),
+    );
+    // the target is passed again as the account type the change applies to
+    let (amm_config, pool_state) = match change {
+        AdminChange::EnablePoolStatus(_) => (None, Some(target)),
+        _ => (Some(target), None),
+    };
+    let instructions = program
+        .request()
+        .accounts(raydium_accounts::ScheduleAdminChange {
+            authority: program.payer(),
+            target,
+            scheduled_change,
+            system_program: system_program::id(),
+            amm_config,
+            pool_state,
+        })
+        .args(raydium_instruction::ScheduleAdminChange { nonce, change })
+        .instructions()?;
+    Ok(instructions)
+}
+
+pub fn execute_admin_change_instr(
+    config: &ClientConfig,
+    scheduled_change: Pubkey,
+    authority: Pubkey,
+    target: Pubkey,
+    change: AdminChange,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.raydium_v3_program)?;
+    let (amm_config, pool_state) = match change {
+        AdminChange::EnablePoolStatus(_) => (None, Some(target)),
+        _ => (Some(target), None),
+    };
+    let instructions = program
+        .request()
+        .accounts(raydium_accounts::ExecuteAdminChange {
+            authority,
+            scheduled_change,
+            amm_config,
+            pool_state,
+        })
+        .args(raydium_instruction::ExecuteAdminChange {})
+        .instructions()?;
+    Ok(instructions)
+}
+
+pub fn cancel_admin_change_instr(
+    config: &ClientConfig,
+    scheduled_change: Pubkey,
+) -> Result<Vec<Instruction>> {
+    let admin = read_keypair_file(&config.admin_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(admin));
+    let program = client.program(config.raydium_v3_program)?;
+    let instructions = program
+        .request()
+        .accounts(raydium_accounts::CancelAdminChange {
+            authority: program.payer(),
+            scheduled_change,
+        })
+        .args(raydium_instruction::CancelAdminChange {})
+        .instructions()?;
+    Ok(instructions)
+}
+
+pub fn set_fee_distribution_instr(
+    config: &ClientConfig,
+    amm_config: Pubkey,
+    recipients: Vec<FeeRecipient>,
+) -> Result<Vec<Instruction>> {
+    let admin = read_keypair_file(&config.admin_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(admin));
+    let program = client.program(config.raydium_v3_program)?;
+    let (fee_distribution, __bump) = Pubkey::find_program_address(
+        &[
+            FEE_DISTRIBUTION_SEED.as_bytes(),
+            amm_config.to_bytes().as_ref(),
+        ],
+        &program.id(),
+    );
+    let instructions = program
+        .request()
+        .accounts(raydium_accounts::SetFeeDistribution {
+            owner: program.payer(),
+            amm_config,
+            fee_distribution,
+            system_program: system_program::id(),
+        })
+        .args(raydium_instruction::SetFeeDistribution { recipients })
+        .instructions()?;
+    Ok(instructions)
+}
+
+pub fn set_fund_fee_distribution_instr(
+    config: &ClientConfig,
+    amm_config: Pubkey,
+    recipients: Vec<FeeRecipient>,
+) -> Result<Vec<Instruction>> {
+    let admin = read_keypair_file(&config.admin_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(admin));
+    let program = client.program(config.raydium_v3_program)?;
+    let (fee_distribution, __bump) = Pubkey::find_program_address(
+        &[
+            FEE_DISTRIBUTION_SEED.as_bytes(),
+            amm_config.to_bytes().as_ref(),
+        ],
+        &program.id(),
+    );
+    let instructions = program
+        .request()
+        .accounts(raydium_accounts::SetFundFeeDistribution {
+            owner: program.payer(),
+            amm_config,
+            fee_distribution,
+            system_program: system_program::id(),
+        })
+        .args(raydium_instruction::SetFundFeeDistribution { recipients })
+        .instructions()?;
+    Ok(instructions)
+}
+
+pub fn distribute_fees_instr(
+    config: &ClientConfig,
+    pool_account_key: Pubkey,
+    amm_config: Pubkey,
+    token_vault_0: Pubkey,
+    token_vault_1: Pubkey,
+    token_mint_0: Pubkey,
+    token_mint_1: Pubkey,
+    remaining_accounts: Vec<AccountMeta>,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.raydium_v3_program)?;
+    let (fee_distribution, __bump) = Pubkey::find_program_address(
+        &[
+            FEE_DISTRIBUTION_SEED.as_bytes(),
+            amm_config.to_bytes().as_ref(),
+        ],
+        &program.id(),
+    );
+    let instructions = program
+        .request()
+        .accounts(raydium_accounts::DistributeFees {
+            payer: program.payer(),
+            pool_state: pool_account_key,
+            fee_distribution,
+            token_vault_0,
+            token_vault_1,
+            vault_0_mint: token_mint_0,
+            vault_1_mint: token_mint_1,
+            token_program: spl_token::id(),
+            token_program_2022: spl_token_2022::id(),
+        })
+        .accounts(remaining_accounts)
+        .args(raydium_instruction::DistributeFees {})
         .instructions()?;
     Ok(instructions)
 }
@@ -142,6 +142,9 @@ pub fn handle_program_log(
             ConfigChangeEvent::DISCRIMINATOR => {
                 println!("{:#?}", decode_event::<ConfigChangeEvent>(&mut slice)?);
             }
+            AmmConfigUpdateEvent::DISCRIMINATOR => {
+                println!("{:#?}", decode_event::<AmmConfigUpdateEvent>(&mut slice)?);
+            }
             CollectPersonalFeeEvent::DISCRIMINATOR => {
                 println!(
                     "{:#?}",
@@ -181,9 +184,84 @@ pub fn handle_program_log(
             SwapEvent::DISCRIMINATOR => {
                 println!("{:#?}", decode_event::<SwapEvent>(&mut slice)?);
             }
+            RouteSwapEvent::DISCRIMINATOR => {
+                println!("{:#?}", decode_event::<RouteSwapEvent>(&mut slice)?);
+            }
             PoolCreatedEvent::DISCRIMINATOR => {
                 println!("{:#?}", decode_event::<PoolCreatedEvent>(&mut slice)?);
             }
+            MintRegistryChangeEvent::DISCRIMINATOR => {
+                println!(
+                    "{:#?}",
+                    decode_event::<MintRegistryChangeEvent>(&mut slice)?
+                );
+            }
+            OperatorRoleChangeEvent::DISCRIMINATOR => {
+                println!(
+                    "{:#?}",
+                    decode_event::<OperatorRoleChangeEvent>(&mut slice)?
+                );
+            }
+            AmmConfigStatusChangeEvent::DISCRIMINATOR => {
+                println!(
+                    "{:#?}",
+                    decode_event::<AmmConfigStatusChangeEvent>(&mut slice)?
+                );
+            }
+            FeeDistributionChangeEvent::DISCRIMINATOR => {
+                println!(
+                    "{:#?}",
+                    decode_event::<FeeDistributionChangeEvent>(&mut slice)?
+                );
+            }
+            FeeDistributedEvent::DISCRIMINATOR => {
+                println!("{:#?}", decode_event::<FeeDistributedEvent>(&mut slice)?);
+            }
+            PoolStatusChangeEvent::DISCRIMINATOR => {
+                println!("{:#?}", decode_event::<PoolStatusChangeEvent>(&mut slice)?);
+            }
+            PoolFeeChangeEvent::DISCRIMINATOR => {
+                println!("{:#?}", decode_event::<PoolFeeChangeEvent>(&mut slice)?);
+            }
+            AdminChangeScheduledEvent::DISCRIMINATOR => {
+                println!(
+                    "{:#?}",
+                    decode_event::<AdminChangeScheduledEvent>(&mut slice)?
+                );
+            }
+            AdminChangeExecutedEvent::DISCRIMINATOR => {
+                println!(
+                    "{:#?}",
+                    decode_event::<AdminChangeExecutedEvent>(&mut slice)?
+                );
+            }
+            AdminChangeCancelledEvent::DISCRIMINATOR => {
+                println!(
+                    "{:#?}",
+                    decode_event::<AdminChangeCancelledEvent>(&mut slice)?
+                );
+            }
+            CreateRewardExtensionEvent::DISCRIMINATOR => {
+                println!(
+                    "{:#?}",
+                    decode_event::<CreateRewardExtensionEvent>(&mut slice)?
+                );
+            }
+            FundRewardEvent::DISCRIMINATOR => {
+                println!("{:#?}", decode_event::<FundRewardEvent>(&mut slice)?);
+            }
+            RetireRewardEvent::DISCRIMINATOR => {
+                println!("{:#?}", decode_event::<RetireRewardEvent>(&mut slice)?);
+            }
+            SetRewardScheduleEvent::DISCRIMINATOR => {
+                println!("{:#?}", decode_event::<SetRewardScheduleEvent>(&mut slice)?);
+            }
+            LockPositionEvent::DISCRIMINATOR => {
+                println!("{:#?}", decode_event::<LockPositionEvent>(&mut slice)?);
+            }
+            UnlockPositionEvent::DISCRIMINATOR => {
+                println!("{:#?}", decode_event::<UnlockPositionEvent>(&mut slice)?);
+            }
             _ => {
                 println!("unknow event: {}", l);
             }
@@ -388,6 +466,50 @@ pub fn handle_program_instruction(
             }
             println!("{:#?}", UpdateAmmConfig::from(ix));
         }
+        instruction::UpdateAmmConfigV2::DISCRIMINATOR => {
+            let ix = decode_instruction::<instruction::UpdateAmmConfigV2>(&mut ix_data).unwrap();
+            #[derive(Debug)]
+            pub struct UpdateAmmConfigV2 {
+                pub update: AmmConfigUpdate,
+            }
+            impl From<instruction::UpdateAmmConfigV2> for UpdateAmmConfigV2 {
+                fn from(instr: instruction::UpdateAmmConfigV2) -> UpdateAmmConfigV2 {
+                    UpdateAmmConfigV2 {
+                        update: instr.update,
+                    }
+                }
+            }
+            println!("{:#?}", UpdateAmmConfigV2::from(ix));
+        }
+        instruction::ProposeAmmConfigOwner::DISCRIMINATOR => {
+            let ix =
+                decode_instruction::<instruction::ProposeAmmConfigOwner>(&mut ix_data).unwrap();
+            #[derive(Debug)]
+            pub struct ProposeAmmConfigOwner {
+                pub new_owner: Option<Pubkey>,
+                pub new_fund_owner: Option<Pubkey>,
+            }
+            impl From<instruction::ProposeAmmConfigOwner> for ProposeAmmConfigOwner {
+                fn from(instr: instruction::ProposeAmmConfigOwner) -> ProposeAmmConfigOwner {
+                    ProposeAmmConfigOwner {
+                        new_owner: instr.new_owner,
+                        new_fund_owner: instr.new_fund_owner,
+                    }
+                }
+            }
+            println!("{:#?}", ProposeAmmConfigOwner::from(ix));
+        }
+        instruction::AcceptAmmConfigOwner::DISCRIMINATOR => {
+            let ix = decode_instruction::<instruction::AcceptAmmConfigOwner>(&mut ix_data).unwrap();
+            #[derive(Debug)]
+            pub struct AcceptAmmConfigOwner;
+            impl From<instruction::AcceptAmmConfigOwner> for AcceptAmmConfigOwner {
+                fn from(_instr: instruction::AcceptAmmConfigOwner) -> AcceptAmmConfigOwner {
+                    AcceptAmmConfigOwner
+                }
+            }
+            println!("{:#?}", AcceptAmmConfigOwner::from(ix));
+        }
         instruction::CreatePool::DISCRIMINATOR => {
             let ix = decode_instruction::<instruction::CreatePool>(&mut ix_data).unwrap();
             #[derive(Debug)]
@@ -420,6 +542,105 @@ pub fn handle_program_instruction(
             }
             println!("{:#?}", UpdatePoolStatus::from(ix));
         }
+        instruction::PauseAmmConfigPools::DISCRIMINATOR => {
+            let ix = decode_instruction::<instruction::PauseAmmConfigPools>(&mut ix_data).unwrap();
+            #[derive(Debug)]
+            pub struct PauseAmmConfigPools {
+                pub disabled_bits: u8,
+            }
+            impl From<instruction::PauseAmmConfigPools> for PauseAmmConfigPools {
+                fn from(instr: instruction::PauseAmmConfigPools) -> PauseAmmConfigPools {
+                    PauseAmmConfigPools {
+                        disabled_bits: instr.disabled_bits,
+                    }
+                }
+            }
+            println!("{:#?}", PauseAmmConfigPools::from(ix));
+        }
+        instruction::UpdatePoolFeeRates::DISCRIMINATOR => {
+            let ix = decode_instruction::<instruction::UpdatePoolFeeRates>(&mut ix_data).unwrap();
+            #[derive(Debug)]
+            pub struct UpdatePoolFeeRates {
+                pub trade_fee_rate: Option<u32>,
+                pub protocol_fee_rate: Option<u32>,
+                pub fund_fee_rate: Option<u32>,
+            }
+            impl From<instruction::UpdatePoolFeeRates> for UpdatePoolFeeRates {
+                fn from(instr: instruction::UpdatePoolFeeRates) -> UpdatePoolFeeRates {
+                    UpdatePoolFeeRates {
+                        trade_fee_rate: instr.trade_fee_rate,
+                        protocol_fee_rate: instr.protocol_fee_rate,
+                        fund_fee_rate: instr.fund_fee_rate,
+                    }
+                }
+            }
+            println!("{:#?}", UpdatePoolFeeRates::from(ix));
+        }
+        instruction::ScheduleAdminChange::DISCRIMINATOR => {
+            let ix = decode_instruction::<instruction::ScheduleAdminChange>(&mut ix_data).unwrap();
+            #[derive(Debug)]
+            pub struct ScheduleAdminChange {
+                pub nonce: u64,
+                pub change: AdminChange,
+            }
+            impl From<instruction::ScheduleAdminChange> for ScheduleAdminChange {
+                fn from(instr: instruction::ScheduleAdminChange) -> ScheduleAdminChange {
+                    ScheduleAdminChange {
+                        nonce: instr.nonce,
+                        change: instr.change,
+                    }
+                }
+            }
+            println!("{:#?}", ScheduleAdminChange::from(ix));
+        }
+        instruction::ExecuteAdminChange::DISCRIMINATOR => {
+            let ix = decode_instruction::<instruction::ExecuteAdminChange>(&mut ix_data).unwrap();
+            #[derive(Debug)]
+            pub struct ExecuteAdminChange;
+            impl From<instruction::ExecuteAdminChange> for ExecuteAdminChange {
+                fn from(_instr: instruction::ExecuteAdminChange) -> ExecuteAdminChange {
+                    ExecuteAdminChange
+                }
+            }
+            println!("{:#?}", ExecuteAdminChange::from(ix));
+        }
+        instruction::CancelAdminChange::DISCRIMINATOR => {
+            let ix = decode_instruction::<instruction::CancelAdminChange>(&mut ix_data).unwrap();
+            #[derive(Debug)]
+            pub struct CancelAdminChange;
+            impl From<instruction::CancelAdminChange> for CancelAdminChange {
+                fn from(_instr: instruction::CancelAdminChange) -> CancelAdminChange {
+                    CancelAdminChange
+                }
+            }
+            println!("{:#?}", CancelAdminChange::from(ix));
+        }
+        instruction::CreateMintRegistry::DISCRIMINATOR => {
+            let ix = decode_instruction::<instruction::CreateMintRegistry>(&mut ix_data).unwrap();
+            #[derive(Debug)]
+            pub struct CreateMintRegistry;
+            impl From<instruction::CreateMintRegistry> for CreateMintRegistry {
+                fn from(_instr: instruction::CreateMintRegistry) -> CreateMintRegistry {
+                    CreateMintRegistry
+                }
+            }
+            println!("{:#?}", CreateMintRegistry::from(ix));
+        }
+        instruction::UpdateMintRegistry::DISCRIMINATOR => {
+            let ix = decode_instruction::<instruction::UpdateMintRegistry>(&mut ix_data).unwrap();
+            #[derive(Debug)]
+            pub struct UpdateMintRegistry {
+                pub update: MintRegistryUpdate,
+            }
+            impl From<instruction::UpdateMintRegistry> for UpdateMintRegistry {
+                fn from(instr: instruction::UpdateMintRegistry) -> UpdateMintRegistry {
+                    UpdateMintRegistry {
+                        update: instr.update,
+                    }
+                }
+            }
+            println!("{:#?}", UpdateMintRegistry::from(ix));
+        }
         instruction::CreateOperationAccount::DISCRIMINATOR => {
             let ix =
                 decode_instruction::<instruction::CreateOperationAccount>(&mut ix_data).unwrap();
@@ -437,19 +658,49 @@ pub fn handle_program_instruction(
                 decode_instruction::<instruction::UpdateOperationAccount>(&mut ix_data).unwrap();
             #[derive(Debug)]
             pub struct UpdateOperationAccount {
-                pub param: u8,
-                pub keys: Vec<Pubkey>,
+                pub update: OperationAccountUpdate,
             }
             impl From<instruction::UpdateOperationAccount> for UpdateOperationAccount {
                 fn from(instr: instruction::UpdateOperationAccount) -> UpdateOperationAccount {
                     UpdateOperationAccount {
-                        param: instr.param,
-                        keys: instr.keys,
+                        update: instr.update,
                     }
                 }
             }
             println!("{:#?}", UpdateOperationAccount::from(ix));
         }
+        instruction::CreateSwapAllowlist::DISCRIMINATOR => {
+            let ix = decode_instruction::<instruction::CreateSwapAllowlist>(&mut ix_data).unwrap();
+            #[derive(Debug)]
+            pub struct CreateSwapAllowlist {
+                pub launch_duration: u64,
+                pub keys: Vec<Pubkey>,
+            }
+            impl From<instruction::CreateSwapAllowlist> for CreateSwapAllowlist {
+                fn from(instr: instruction::CreateSwapAllowlist) -> CreateSwapAllowlist {
+                    CreateSwapAllowlist {
+                        launch_duration: instr.launch_duration,
+                        keys: instr.keys,
+                    }
+                }
+            }
+            println!("{:#?}", CreateSwapAllowlist::from(ix));
+        }
+        instruction::UpdateSwapAllowlist::DISCRIMINATOR => {
+            let ix = decode_instruction::<instruction::UpdateSwapAllowlist>(&mut ix_data).unwrap();
+            #[derive(Debug)]
+            pub struct UpdateSwapAllowlist {
+                pub update: SwapAllowlistUpdate,
+            }
+            impl From<instruction::UpdateSwapAllowlist> for UpdateSwapAllowlist {
+                fn from(instr: instruction::UpdateSwapAllowlist) -> UpdateSwapAllowlist {
+                    UpdateSwapAllowlist {
+                        update: instr.update,
+                    }
+                }
+            }
+            println!("{:#?}", UpdateSwapAllowlist::from(ix));
+        }
         instruction::TransferRewardOwner::DISCRIMINATOR => {
             let ix = decode_instruction::<instruction::TransferRewardOwner>(&mut ix_data).unwrap();
             #[derive(Debug)]
@@ -465,6 +716,17 @@ pub fn handle_program_instruction(
             }
             println!("{:#?}", TransferRewardOwner::from(ix));
         }
+        instruction::AcceptRewardOwner::DISCRIMINATOR => {
+            let ix = decode_instruction::<instruction::AcceptRewardOwner>(&mut ix_data).unwrap();
+            #[derive(Debug)]
+            pub struct AcceptRewardOwner;
+            impl From<instruction::AcceptRewardOwner> for AcceptRewardOwner {
+                fn from(_instr: instruction::AcceptRewardOwner) -> AcceptRewardOwner {
+                    AcceptRewardOwner
+                }
+            }
+            println!("{:#?}", AcceptRewardOwner::from(ix));
+        }
         instruction::InitializeReward::DISCRIMINATOR => {
             let ix = decode_instruction::<instruction::InitializeReward>(&mut ix_data).unwrap();
             #[derive(Debug)]
@@ -505,6 +767,18 @@ pub fn handle_program_instruction(
             }
             println!("{:#?}", UpdateRewardInfos::from(ix));
         }
+        instruction::CreateRewardExtension::DISCRIMINATOR => {
+            let ix =
+                decode_instruction::<instruction::CreateRewardExtension>(&mut ix_data).unwrap();
+            #[derive(Debug)]
+            pub struct CreateRewardExtension;
+            impl From<instruction::CreateRewardExtension> for CreateRewardExtension {
+                fn from(_instr: instruction::CreateRewardExtension) -> CreateRewardExtension {
+                    CreateRewardExtension
+                }
+            }
+            println!("{:#?}", CreateRewardExtension::from(ix));
+        }
         instruction::SetRewardParams::DISCRIMINATOR => {
             let ix = decode_instruction::<instruction::SetRewardParams>(&mut ix_data).unwrap();
             #[derive(Debug)]
@@ -526,6 +800,105 @@ pub fn handle_program_instruction(
             }
             println!("{:#?}", SetRewardParams::from(ix));
         }
+        instruction::FundReward::DISCRIMINATOR => {
+            let ix = decode_instruction::<instruction::FundReward>(&mut ix_data).unwrap();
+            #[derive(Debug)]
+            pub struct FundReward {
+                pub reward_index: u8,
+                pub amount: u64,
+                pub mode: FundRewardMode,
+            }
+            impl From<instruction::FundReward> for FundReward {
+                fn from(instr: instruction::FundReward) -> FundReward {
+                    FundReward {
+                        reward_index: instr.reward_index,
+                        amount: instr.amount,
+                        mode: instr.mode,
+                    }
+                }
+            }
+            println!("{:#?}", FundReward::from(ix));
+        }
+        instruction::RetireReward::DISCRIMINATOR => {
+            let ix = decode_instruction::<instruction::RetireReward>(&mut ix_data).unwrap();
+            #[derive(Debug)]
+            pub struct RetireReward {
+                pub reward_index: u8,
+            }
+            impl From<instruction::RetireReward> for RetireReward {
+                fn from(instr: instruction::RetireReward) -> RetireReward {
+                    RetireReward {
+                        reward_index: instr.reward_index,
+                    }
+                }
+            }
+            println!("{:#?}", RetireReward::from(ix));
+        }
+        instruction::SetRewardSchedule::DISCRIMINATOR => {
+            let ix = decode_instruction::<instruction::SetRewardSchedule>(&mut ix_data).unwrap();
+            #[derive(Debug)]
+            pub struct SetRewardSchedule {
+                pub reward_index: u8,
+                pub kind: RewardScheduleKind,
+                pub step_period: u32,
+                pub end_emissions_per_second_x64: u128,
+            }
+            impl From<instruction::SetRewardSchedule> for SetRewardSchedule {
+                fn from(instr: instruction::SetRewardSchedule) -> SetRewardSchedule {
+                    SetRewardSchedule {
+                        reward_index: instr.reward_index,
+                        kind: instr.kind,
+                        step_period: instr.step_period,
+                        end_emissions_per_second_x64: instr.end_emissions_per_second_x64,
+                    }
+                }
+            }
+            println!("{:#?}", SetRewardSchedule::from(ix));
+        }
+        instruction::LockPosition::DISCRIMINATOR => {
+            let ix = decode_instruction::<instruction::LockPosition>(&mut ix_data).unwrap();
+            #[derive(Debug)]
+            pub struct LockPosition {
+                pub lock_until: u64,
+            }
+            impl From<instruction::LockPosition> for LockPosition {
+                fn from(instr: instruction::LockPosition) -> LockPosition {
+                    LockPosition {
+                        lock_until: instr.lock_until,
+                    }
+                }
+            }
+            println!("{:#?}", LockPosition::from(ix));
+        }
+        instruction::UnlockPosition::DISCRIMINATOR => {
+            let ix = decode_instruction::<instruction::UnlockPosition>(&mut ix_data).unwrap();
+            #[derive(Debug)]
+            pub struct UnlockPosition;
+            impl From<instruction::UnlockPosition> for UnlockPosition {
+                fn from(_instr: instruction::UnlockPosition) -> UnlockPosition {
+                    UnlockPosition
+                }
+            }
+            println!("{:#?}", UnlockPosition::from(ix));
+        }
+        instruction::SetPositionLockConfig::DISCRIMINATOR => {
+            let ix =
+                decode_instruction::<instruction::SetPositionLockConfig>(&mut ix_data).unwrap();
+            #[derive(Debug)]
+            pub struct SetPositionLockConfig {
+                pub lock_reward_multiplier: u16,
+                pub min_lock_duration: u32,
+            }
+            impl From<instruction::SetPositionLockConfig> for SetPositionLockConfig {
+                fn from(instr: instruction::SetPositionLockConfig) -> SetPositionLockConfig {
+                    SetPositionLockConfig {
+                        lock_reward_multiplier: instr.lock_reward_multiplier,
+                        min_lock_duration: instr.min_lock_duration,
+                    }
+                }
+            }
+            println!("{:#?}", SetPositionLockConfig::from(ix));
+        }
         instruction::CollectProtocolFee::DISCRIMINATOR => {
             let ix = decode_instruction::<instruction::CollectProtocolFee>(&mut ix_data).unwrap();
             #[derive(Debug)]
@@ -560,6 +933,48 @@ pub fn handle_program_instruction(
             }
             println!("{:#?}", CollectFundFee::from(ix));
         }
+        instruction::SetFeeDistribution::DISCRIMINATOR => {
+            let ix = decode_instruction::<instruction::SetFeeDistribution>(&mut ix_data).unwrap();
+            #[derive(Debug)]
+            pub struct SetFeeDistribution {
+                pub recipients: Vec<FeeRecipient>,
+            }
+            impl From<instruction::SetFeeDistribution> for SetFeeDistribution {
+                fn from(instr: instruction::SetFeeDistribution) -> SetFeeDistribution {
+                    SetFeeDistribution {
+                        recipients: instr.recipients,
+                    }
+                }
+            }
+            println!("{:#?}", SetFeeDistribution::from(ix));
+        }
+        instruction::SetFundFeeDistribution::DISCRIMINATOR => {
+            let ix =
+                decode_instruction::<instruction::SetFundFeeDistribution>(&mut ix_data).unwrap();
+            #[derive(Debug)]
+            pub struct SetFundFeeDistribution {
+                pub recipients: Vec<FeeRecipient>,
+            }
+            impl From<instruction::SetFundFeeDistribution> for SetFundFeeDistribution {
+                fn from(instr: instruction::SetFundFeeDistribution) -> SetFundFeeDistribution {
+                    SetFundFeeDistribution {
+                        recipients: instr.recipients,
+                    }
+                }
+            }
+            println!("{:#?}", SetFundFeeDistribution::from(ix));
+        }
+        instruction::DistributeFees::DISCRIMINATOR => {
+            let ix = decode_instruction::<instruction::DistributeFees>(&mut ix_data).unwrap();
+            #[derive(Debug)]
+            pub struct DistributeFees;
+            impl From<instruction::DistributeFees> for DistributeFees {
+                fn from(_instr: instruction::DistributeFees) -> DistributeFees {
+                    DistributeFees
+                }
+            }
+            println!("{:#?}", DistributeFees::from(ix));
+        }
         instruction::OpenPosition::DISCRIMINATOR => {
             let ix = decode_instruction::<instruction::OpenPosition>(&mut ix_data).unwrap();
             #[derive(Debug)]
@@ -618,6 +1033,39 @@ pub fn handle_program_instruction(
             }
             println!("{:#?}", OpenPositionV2::from(ix));
         }
+        instruction::OpenPositionV3::DISCRIMINATOR => {
+            let ix = decode_instruction::<instruction::OpenPositionV3>(&mut ix_data).unwrap();
+            #[derive(Debug)]
+            pub struct OpenPositionV3 {
+                pub tick_lower_index: i32,
+                pub tick_upper_index: i32,
+                pub tick_array_lower_start_index: i32,
+                pub tick_array_upper_start_index: i32,
+                pub liquidity: u128,
+                pub amount_0_max: u64,
+                pub amount_1_max: u64,
+                pub base_flag: Option<bool>,
+                pub with_metadata: bool,
+                pub deadline: Option<i64>,
+            }
+            impl From<instruction::OpenPositionV3> for OpenPositionV3 {
+                fn from(instr: instruction::OpenPositionV3) -> OpenPositionV3 {
+                    OpenPositionV3 {
+                        tick_lower_index: instr.tick_lower_index,
+                        tick_upper_index: instr.tick_upper_index,
+                        tick_array_lower_start_index: instr.tick_array_lower_start_index,
+                        tick_array_upper_start_index: instr.tick_array_upper_start_index,
+                        liquidity: instr.liquidity,
+                        amount_0_max: instr.amount_0_max,
+                        amount_1_max: instr.amount_1_max,
+                        base_flag: instr.base_flag,
+                        with_metadata: instr.with_metadata,
+                        deadline: instr.deadline,
+                    }
+                }
+            }
+            println!("{:#?}", OpenPositionV3::from(ix));
+        }
         instruction::ClosePosition::DISCRIMINATOR => {
             let ix = decode_instruction::<instruction::ClosePosition>(&mut ix_data).unwrap();
             #[derive(Debug)]
@@ -669,6 +1117,29 @@ pub fn handle_program_instruction(
             }
             println!("{:#?}", IncreaseLiquidityV2::from(ix));
         }
+        instruction::IncreaseLiquidityV3::DISCRIMINATOR => {
+            let ix = decode_instruction::<instruction::IncreaseLiquidityV3>(&mut ix_data).unwrap();
+            #[derive(Debug)]
+            pub struct IncreaseLiquidityV3 {
+                pub liquidity: u128,
+                pub amount_0_max: u64,
+                pub amount_1_max: u64,
+                pub base_flag: Option<bool>,
+                pub deadline: Option<i64>,
+            }
+            impl From<instruction::IncreaseLiquidityV3> for IncreaseLiquidityV3 {
+                fn from(instr: instruction::IncreaseLiquidityV3) -> IncreaseLiquidityV3 {
+                    IncreaseLiquidityV3 {
+                        liquidity: instr.liquidity,
+                        amount_0_max: instr.amount_0_max,
+                        amount_1_max: instr.amount_1_max,
+                        base_flag: instr.base_flag,
+                        deadline: instr.deadline,
+                    }
+                }
+            }
+            println!("{:#?}", IncreaseLiquidityV3::from(ix));
+        }
         instruction::DecreaseLiquidity::DISCRIMINATOR => {
             let ix = decode_instruction::<instruction::DecreaseLiquidity>(&mut ix_data).unwrap();
             #[derive(Debug)]
@@ -707,6 +1178,27 @@ pub fn handle_program_instruction(
             }
             println!("{:#?}", DecreaseLiquidityV2::from(ix));
         }
+        instruction::DecreaseLiquidityV3::DISCRIMINATOR => {
+            let ix = decode_instruction::<instruction::DecreaseLiquidityV3>(&mut ix_data).unwrap();
+            #[derive(Debug)]
+            pub struct DecreaseLiquidityV3 {
+                pub liquidity: u128,
+                pub amount_0_min: u64,
+                pub amount_1_min: u64,
+                pub deadline: Option<i64>,
+            }
+            impl From<instruction::DecreaseLiquidityV3> for DecreaseLiquidityV3 {
+                fn from(instr: instruction::DecreaseLiquidityV3) -> DecreaseLiquidityV3 {
+                    DecreaseLiquidityV3 {
+                        liquidity: instr.liquidity,
+                        amount_0_min: instr.amount_0_min,
+                        amount_1_min: instr.amount_1_min,
+                        deadline: instr.deadline,
+                    }
+                }
+            }
+            println!("{:#?}", DecreaseLiquidityV3::from(ix));
+        }
         instruction::Swap::DISCRIMINATOR => {
             let ix = decode_instruction::<instruction::Swap>(&mut ix_data).unwrap();
             #[derive(Debug)]
@@ -749,6 +1241,29 @@ pub fn handle_program_instruction(
             }
             println!("{:#?}", SwapV2::from(ix));
         }
+        instruction::SwapV3::DISCRIMINATOR => {
+            let ix = decode_instruction::<instruction::SwapV3>(&mut ix_data).unwrap();
+            #[derive(Debug)]
+            pub struct SwapV3 {
+                pub amount: u64,
+                pub other_amount_threshold: u64,
+                pub sqrt_price_limit_x64: u128,
+                pub is_base_input: bool,
+                pub deadline: Option<i64>,
+            }
+            impl From<instruction::SwapV3> for SwapV3 {
+                fn from(instr: instruction::SwapV3) -> SwapV3 {
+                    SwapV3 {
+                        amount: instr.amount,
+                        other_amount_threshold: instr.other_amount_threshold,
+                        sqrt_price_limit_x64: instr.sqrt_price_limit_x64,
+                        is_base_input: instr.is_base_input,
+                        deadline: instr.deadline,
+                    }
+                }
+            }
+            println!("{:#?}", SwapV3::from(ix));
+        }
         instruction::SwapRouterBaseIn::DISCRIMINATOR => {
             let ix = decode_instruction::<instruction::SwapRouterBaseIn>(&mut ix_data).unwrap();
             #[derive(Debug)]
@@ -766,6 +1281,69 @@ pub fn handle_program_instruction(
             }
             println!("{:#?}", SwapRouterBaseIn::from(ix));
         }
+        instruction::SwapRouterBaseInV3::DISCRIMINATOR => {
+            let ix = decode_instruction::<instruction::SwapRouterBaseInV3>(&mut ix_data).unwrap();
+            #[derive(Debug)]
+            pub struct SwapRouterBaseInV3 {
+                pub amount_in: u64,
+                pub amount_out_minimum: u64,
+                pub deadline: Option<i64>,
+            }
+            impl From<instruction::SwapRouterBaseInV3> for SwapRouterBaseInV3 {
+                fn from(instr: instruction::SwapRouterBaseInV3) -> SwapRouterBaseInV3 {
+                    SwapRouterBaseInV3 {
+                        amount_in: instr.amount_in,
+                        amount_out_minimum: instr.amount_out_minimum,
+                        deadline: instr.deadline,
+                    }
+                }
+            }
+            println!("{:#?}", SwapRouterBaseInV3::from(ix));
+        }
+        instruction::SwapRouterBaseOut::DISCRIMINATOR => {
+            let ix = decode_instruction::<instruction::SwapRouterBaseOut>(&mut ix_data).unwrap();
+            #[derive(Debug)]
+            pub struct SwapRouterBaseOut {
+                pub amount_out: u64,
+                pub amount_in_maximum: u64,
+                pub hop_account_counts: Vec<u8>,
+                pub deadline: Option<i64>,
+            }
+            impl From<instruction::SwapRouterBaseOut> for SwapRouterBaseOut {
+                fn from(instr: instruction::SwapRouterBaseOut) -> SwapRouterBaseOut {
+                    SwapRouterBaseOut {
+                        amount_out: instr.amount_out,
+                        amount_in_maximum: instr.amount_in_maximum,
+                        hop_account_counts: instr.hop_account_counts,
+                        deadline: instr.deadline,
+                    }
+                }
+            }
+            println!("{:#?}", SwapRouterBaseOut::from(ix));
+        }
+        instruction::SwapRouterBaseInV2::DISCRIMINATOR => {
+            let ix = decode_instruction::<instruction::SwapRouterBaseInV2>(&mut ix_data).unwrap();
+            #[derive(Debug)]
+            pub struct SwapRouterBaseInV2 {
+                pub amount_in: u64,
+                pub amount_out_minimum: u64,
+                pub hop_account_counts: Vec<u8>,
+                pub sqrt_price_limits_x64: Vec<u128>,
+                pub deadline: Option<i64>,
+            }
+            impl From<instruction::SwapRouterBaseInV2> for SwapRouterBaseInV2 {
+                fn from(instr: instruction::SwapRouterBaseInV2) -> SwapRouterBaseInV2 {
+                    SwapRouterBaseInV2 {
+                        amount_in: instr.amount_in,
+                        amount_out_minimum: instr.amount_out_minimum,
+                        hop_account_counts: instr.hop_account_counts,
+                        sqrt_price_limits_x64: instr.sqrt_price_limits_x64,
+                        deadline: instr.deadline,
+                    }
+                }
+            }
+            println!("{:#?}", SwapRouterBaseInV2::from(ix));
+        }
         _ => {
             println!("unknow instruction: {}", instr_data);
         }
@@ -19,6 +19,7 @@ use solana_client::{
 use solana_sdk::{
     commitment_config::CommitmentConfig,
     compute_budget::ComputeBudgetInstruction,
+    instruction::Instruction,
     message::Message,
     program_pack::Pack,
     pubkey::Pubkey,
@@ -40,11 +41,16 @@ use instructions::token_instructions::*;
 use instructions::utils::*;
 use raydium_amm_v3::{
     libraries::{fixed_point_64, liquidity_math, tick_math},
-    states::{PoolState, TickArrayBitmapExtension, TickArrayState, POOL_TICK_ARRAY_BITMAP_SEED},
+    states::{
+        AdminChange, AmmConfigUpdate, FeeDistributionState, FeeRecipient, MintRegistryState,
+        MintRegistryUpdate, OperationAccountUpdate, OperatorRoleBitIndex, PoolState,
+        PoolStatusBitIndex, ScheduledChangeState, SwapAllowlistUpdate, TickArrayBitmapExtension,
+        TickArrayState, POOL_REWARD_EXTENSION_SEED, POOL_TICK_ARRAY_BITMAP_SEED,
+    },
 };
 use spl_associated_token_account::get_associated_token_address;
 use spl_token_2022::{
-    extension::StateWithExtensions,
+    extension::{ExtensionType, StateWithExtensions},
     state::Mint,
     state::{Account, AccountState},
 };
@@ -176,6 +182,97 @@ fn load_cfg(client_config: &String) -> Result<ClientConfig> {
         amm_config_index,
     })
 }
+/// The reward extension of the pool, every instruction accruing rewards requires it once the pool has one
+fn pool_reward_extension(config: &ClientConfig, pool: &PoolState) -> Option<Pubkey> {
+    if pool.reward_extension == 0 {
+        return None;
+    }
+    Some(
+        Pubkey::find_program_address(
+            &[
+                POOL_REWARD_EXTENSION_SEED.as_bytes(),
+                config.pool_id_account.unwrap().to_bytes().as_ref(),
+            ],
+            &config.raydium_v3_program,
+        )
+        .0,
+    )
+}
+/// Locks the position of the nft until the timestamp, or unlocks it when there is no timestamp
+fn position_lock_instr(
+    config: &ClientConfig,
+    rpc_client: &RpcClient,
+    owner: &Pubkey,
+    nft_mint: Pubkey,
+    lock_until: Option<u64>,
+) -> Result<Vec<Instruction>> {
+    let personal_position_key = Pubkey::find_program_address(
+        &[
+            raydium_amm_v3::states::POSITION_SEED.as_bytes(),
+            nft_mint.to_bytes().as_ref(),
+        ],
+        &config.raydium_v3_program,
+    )
+    .0;
+    let position = deserialize_anchor_account::<raydium_amm_v3::states::PersonalPositionState>(
+        &rpc_client.get_account(&personal_position_key)?,
+    )?;
+    let pool =
+        deserialize_anchor_account::<PoolState>(&rpc_client.get_account(&position.pool_id)?)?;
+    let tick_array_lower_start_index =
+        raydium_amm_v3::states::TickArrayState::get_array_start_index(
+            position.tick_lower_index,
+            pool.tick_spacing.into(),
+        );
+    let tick_array_upper_start_index =
+        raydium_amm_v3::states::TickArrayState::get_array_start_index(
+            position.tick_upper_index,
+            pool.tick_spacing.into(),
+        );
+    let mut pool_config = config.clone();
+    pool_config.pool_id_account = Some(position.pool_id);
+    let reward_extension = pool_reward_extension(&pool_config, &pool);
+    match lock_until {
+        Some(lock_until) => {
+            let nft_token_info =
+                get_all_nft_and_position_by_owner(rpc_client, owner, &config.raydium_v3_program)
+                    .into_iter()
+                    .find(|nft_info| nft_info.mint == nft_mint)
+                    .ok_or_else(|| format_err!("position nft not owned"))?;
+            lock_position_instr(
+                config,
+                position.pool_id,
+                nft_mint,
+                nft_token_info.key,
+                reward_extension,
+                lock_until,
+                position.tick_lower_index,
+                position.tick_upper_index,
+                tick_array_lower_start_index,
+                tick_array_upper_start_index,
+            )
+        }
+        // anyone can unlock an expired lock, its rent goes back to the payer of the lock
+        None => {
+            let position_lock =
+                deserialize_anchor_account::<raydium_amm_v3::states::PositionLockState>(
+                    &rpc_client
+                        .get_account(&raydium_amm_v3::states::PositionLockState::key(nft_mint))?,
+                )?;
+            unlock_position_instr(
+                config,
+                position.pool_id,
+                nft_mint,
+                position_lock.rent_payer,
+                reward_extension,
+                position.tick_lower_index,
+                position.tick_upper_index,
+                tick_array_lower_start_index,
+                tick_array_upper_start_index,
+            )
+        }
+    }
+}
 fn read_keypair_file(s: &str) -> Result<Keypair> {
     solana_sdk::signature::read_keypair_file(s)
         .map_err(|_| format_err!("failed to read keypair from {}", s))
@@ -188,6 +285,20 @@ fn path_is_exist(path: &str) -> bool {
     Path::new(path).exists()
 }
 
+fn pause_status_bits(swap: bool, deposit: bool) -> Result<u8> {
+    let mut disabled_bits = 0;
+    if swap {
+        disabled_bits |= 1 << PoolStatusBitIndex::Swap as u8;
+    }
+    if deposit {
+        disabled_bits |= 1 << PoolStatusBitIndex::OpenPositionOrIncreaseLiquidity as u8;
+    }
+    if disabled_bits == 0 {
+        return Err(format_err!("nothing to pause, pass --swap or --deposit"));
+    }
+    Ok(disabled_bits)
+}
+
 fn load_cur_and_next_five_tick_array(
     rpc_client: &RpcClient,
     pool_config: &ClientConfig,
@@ -344,6 +455,161 @@ pub struct Opts {
     #[clap(subcommand)]
     pub command: CommandsName,
 }
+#[derive(Debug, Parser)]
+pub enum ConfigUpdateCommand {
+    TradeFeeRate { value: u32 },
+    ProtocolFeeRate { value: u32 },
+    FundFeeRate { value: u32 },
+    Owner { new_owner: Pubkey },
+    FundOwner { new_fund_owner: Pubkey },
+    DynamicFeeControl { value: u32 },
+    MinTradeFeeRate { value: u32 },
+    MaxTradeFeeRate { value: u32 },
+    VolatilityFilterPeriod { value: u32 },
+    VolatilityDecayPeriod { value: u32 },
+    Status { value: u8 },
+}
+
+impl From<ConfigUpdateCommand> for AmmConfigUpdate {
+    fn from(command: ConfigUpdateCommand) -> Self {
+        match command {
+            ConfigUpdateCommand::TradeFeeRate { value } => AmmConfigUpdate::TradeFeeRate(value),
+            ConfigUpdateCommand::ProtocolFeeRate { value } => {
+                AmmConfigUpdate::ProtocolFeeRate(value)
+            }
+            ConfigUpdateCommand::FundFeeRate { value } => AmmConfigUpdate::FundFeeRate(value),
+            ConfigUpdateCommand::Owner { new_owner } => AmmConfigUpdate::Owner(new_owner),
+            ConfigUpdateCommand::FundOwner { new_fund_owner } => {
+                AmmConfigUpdate::FundOwner(new_fund_owner)
+            }
+            ConfigUpdateCommand::DynamicFeeControl { value } => {
+                AmmConfigUpdate::DynamicFeeControl(value)
+            }
+            ConfigUpdateCommand::MinTradeFeeRate { value } => {
+                AmmConfigUpdate::MinTradeFeeRate(value)
+            }
+            ConfigUpdateCommand::MaxTradeFeeRate { value } => {
+                AmmConfigUpdate::MaxTradeFeeRate(value)
+            }
+            ConfigUpdateCommand::VolatilityFilterPeriod { value } => {
+                AmmConfigUpdate::VolatilityFilterPeriod(value)
+            }
+            ConfigUpdateCommand::VolatilityDecayPeriod { value } => {
+                AmmConfigUpdate::VolatilityDecayPeriod(value)
+            }
+            ConfigUpdateCommand::Status { value } => AmmConfigUpdate::Status(value),
+        }
+    }
+}
+
+#[derive(Debug, Parser)]
+pub enum MintRegistryCommand {
+    AllowExtension {
+        extension: u16,
+    },
+    DisallowExtension {
+        extension: u16,
+    },
+    AddMint {
+        mint: Pubkey,
+        #[arg(long)]
+        max_transfer_fee_bps: Option<u16>,
+    },
+    RemoveMint {
+        mint: Pubkey,
+    },
+}
+
+impl From<MintRegistryCommand> for MintRegistryUpdate {
+    fn from(command: MintRegistryCommand) -> Self {
+        match command {
+            MintRegistryCommand::AllowExtension { extension } => {
+                MintRegistryUpdate::AllowExtension(extension)
+            }
+            MintRegistryCommand::DisallowExtension { extension } => {
+                MintRegistryUpdate::DisallowExtension(extension)
+            }
+            MintRegistryCommand::AddMint {
+                mint,
+                max_transfer_fee_bps,
+            } => MintRegistryUpdate::AddMint {
+                mint,
+                max_transfer_fee_bps,
+            },
+            MintRegistryCommand::RemoveMint { mint } => MintRegistryUpdate::RemoveMint(mint),
+        }
+    }
+}
+
+#[derive(Debug, Parser)]
+pub enum OperationCommand {
+    SetOperatorRoles {
+        operator: Pubkey,
+        #[arg(long)]
+        reward_admin: bool,
+        #[arg(long)]
+        whitelist_manager: bool,
+        #[arg(long)]
+        pool_pauser: bool,
+        #[arg(long)]
+        fee_collector: bool,
+    },
+    AddWhitelistMints {
+        mints: Vec<Pubkey>,
+    },
+    RemoveWhitelistMints {
+        mints: Vec<Pubkey>,
+    },
+}
+
+impl From<OperationCommand> for OperationAccountUpdate {
+    fn from(command: OperationCommand) -> Self {
+        match command {
+            OperationCommand::SetOperatorRoles {
+                operator,
+                reward_admin,
+                whitelist_manager,
+                pool_pauser,
+                fee_collector,
+            } => {
+                let roles = [
+                    (reward_admin, OperatorRoleBitIndex::RewardAdmin),
+                    (whitelist_manager, OperatorRoleBitIndex::WhitelistManager),
+                    (pool_pauser, OperatorRoleBitIndex::PoolPauser),
+                    (fee_collector, OperatorRoleBitIndex::FeeCollector),
+                ]
+                .into_iter()
+                .filter(|(enabled, _)| *enabled)
+                .fold(0u8, |roles, (_, role)| roles | (1 << role as u8));
+                OperationAccountUpdate::SetOperatorRoles { operator, roles }
+            }
+            OperationCommand::AddWhitelistMints { mints } => {
+                OperationAccountUpdate::AddWhitelistMints(mints)
+            }
+            OperationCommand::RemoveWhitelistMints { mints } => {
+                OperationAccountUpdate::RemoveWhitelistMints(mints)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Parser)]
+pub enum SwapAllowlistCommand {
+    AddSwappers { keys: Vec<Pubkey> },
+    RemoveSwappers { keys: Vec<Pubkey> },
+}
+
+impl From<SwapAllowlistCommand> for SwapAllowlistUpdate {
+    fn from(command: SwapAllowlistCommand) -> Self {
+        match command {
+            SwapAllowlistCommand::AddSwappers { keys } => SwapAllowlistUpdate::AddSwappers(keys),
+            SwapAllowlistCommand::RemoveSwappers { keys } => {
+                SwapAllowlistUpdate::RemoveSwappers(keys)
+            }
+        }
+    }
+}
+
 #[derive(Debug, Parser)]
 pub enum CommandsName {
     NewMint {
@@ -391,15 +657,74 @@ pub enum CommandsName {
     },
     UpdateConfig {
         config_index: u16,
-        param: u8,
-        value: u32,
-        remaining: Option<Pubkey>,
+        #[clap(subcommand)]
+        update: ConfigUpdateCommand,
+    },
+    AcceptConfigOwner {
+        config_index: u16,
     },
     CreateOperation,
+    CreateMintRegistry,
+    UpdateMintRegistry {
+        #[clap(subcommand)]
+        update: MintRegistryCommand,
+    },
     UpdateOperation {
-        param: u8,
+        #[clap(subcommand)]
+        update: OperationCommand,
+    },
+    UpdateObservationDuration {
+        observation_update_duration: u32,
+    },
+    UpdatePoolFeeRates {
+        #[arg(long)]
+        trade_fee_rate: Option<u32>,
+        #[arg(long)]
+        protocol_fee_rate: Option<u32>,
+        #[arg(long)]
+        fund_fee_rate: Option<u32>,
+    },
+    PausePool {
+        #[arg(long)]
+        swap: bool,
+        #[arg(long)]
+        deposit: bool,
+    },
+    PauseConfigPools {
+        config_index: u16,
+        #[arg(long)]
+        swap: bool,
+        #[arg(long)]
+        deposit: bool,
+    },
+    SetFeeDistribution {
+        config_index: u16,
+        #[arg(long, num_args = 0..)]
+        recipients: Vec<Pubkey>,
+        #[arg(long, num_args = 0..)]
+        shares_bps: Vec<u16>,
+        /// Set the recipients of the fund fees instead of the protocol fees
+        #[arg(long)]
+        fund: bool,
+    },
+    DistributeFees,
+    SchedulePoolStatus {
+        enable_bits: u8,
+    },
+    ExecuteChange {
+        scheduled_change: Pubkey,
+    },
+    CancelChange {
+        scheduled_change: Pubkey,
+    },
+    CreateSwapAllowlist {
+        launch_duration: u64,
         keys: Vec<Pubkey>,
     },
+    UpdateSwapAllowlist {
+        #[clap(subcommand)]
+        update: SwapAllowlistCommand,
+    },
     CreatePool {
         config_index: u16,
         price: f64,
@@ -408,6 +733,7 @@ pub enum CommandsName {
         #[arg(short, long, default_value_t = 0)]
         open_time: u64,
     },
+    CreateRewardExtension,
     InitReward {
         open_time: u64,
         end_time: u64,
@@ -421,6 +747,36 @@ pub enum CommandsName {
         emissions: f64,
         reward_mint: Pubkey,
     },
+    FundReward {
+        index: u8,
+        amount: u64,
+        reward_mint: Pubkey,
+        #[arg(short, long)]
+        increase_emissions: bool,
+    },
+    RetireReward {
+        index: u8,
+        reward_mint: Pubkey,
+    },
+    SetRewardSchedule {
+        index: u8,
+        end_emissions: f64,
+        #[arg(long)]
+        step_period: Option<u32>,
+        #[arg(long)]
+        constant: bool,
+    },
+    LockPosition {
+        nft_mint: Pubkey,
+        lock_until: u64,
+    },
+    UnlockPosition {
+        nft_mint: Pubkey,
+    },
+    SetPositionLockConfig {
+        lock_reward_multiplier: u16,
+        min_lock_duration: u32,
+    },
     TransferRewardOwner {
         pool_id: Pubkey,
         new_owner: Pubkey,
@@ -428,6 +784,9 @@ pub enum CommandsName {
         encode: bool,
         authority: Option<Pubkey>,
     },
+    AcceptRewardOwner {
+        pool_id: Pubkey,
+    },
     OpenPosition {
         tick_lower_price: f64,
         tick_upper_price: f64,
@@ -489,7 +848,12 @@ pub enum CommandsName {
         token: Pubkey,
     },
     POperation,
-    PObservation,
+    PMintRegistry,
+    PObservation {
+        /// Print the volatility and the harmonic mean liquidity over the last `stats` seconds
+        #[arg(long)]
+        stats: Option<u32>,
+    },
     PConfig {
         config_index: u16,
     },
@@ -730,7 +1094,456 @@ fn main() -> Result<()> {
             let signers = vec![&payer];
             let recent_hash = rpc_client.get_latest_blockhash()?;
             let txn = Transaction::new_signed_with_payer(
-                &wrap_sol_instr,
+                &wrap_sol_instr,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
+        CommandsName::UnWrapSol { wrap_sol_account } => {
+            let unwrap_sol_instr =
+                close_token_account(&pool_config, &wrap_sol_account, &payer.pubkey(), &payer)?;
+            // send
+            let signers = vec![&payer];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &unwrap_sol_instr,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
+        CommandsName::CreateConfig {
+            config_index,
+            tick_spacing,
+            trade_fee_rate,
+            protocol_fee_rate,
+            fund_fee_rate,
+        } => {
+            let create_instr = create_amm_config_instr(
+                &pool_config.clone(),
+                config_index,
+                tick_spacing,
+                trade_fee_rate,
+                protocol_fee_rate,
+                fund_fee_rate,
+            )?;
+            // send
+            let signers = vec![&payer, &admin];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &create_instr,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
+        CommandsName::UpdateConfig {
+            config_index,
+            update,
+        } => {
+            let (amm_config_key, __bump) = Pubkey::find_program_address(
+                &[
+                    raydium_amm_v3::states::AMM_CONFIG_SEED.as_bytes(),
+                    &config_index.to_be_bytes(),
+                ],
+                &pool_config.raydium_v3_program,
+            );
+            // owner changes are only proposed, the new owner has to accept them
+            let update_amm_config_instr = match update {
+                ConfigUpdateCommand::Owner { new_owner } => propose_amm_config_owner_instr(
+                    &pool_config.clone(),
+                    amm_config_key,
+                    Some(new_owner),
+                    None,
+                )?,
+                ConfigUpdateCommand::FundOwner { new_fund_owner } => {
+                    propose_amm_config_owner_instr(
+                        &pool_config.clone(),
+                        amm_config_key,
+                        None,
+                        Some(new_fund_owner),
+                    )?
+                }
+                update => {
+                    let update = AmmConfigUpdate::from(update);
+                    let nonce = std::time::SystemTime::now()
+                        .duration_since(std::time::UNIX_EPOCH)?
+                        .as_secs();
+                    if let AmmConfigUpdate::Status(status) = update {
+                        let amm_config: raydium_amm_v3::states::AmmConfig =
+                            program.account(amm_config_key)?;
+                        let mut instructions = Vec::new();
+                        // disabling status bits applies instantly
+                        let disable_bits = status & !amm_config.status;
+                        if disable_bits != 0 {
+                            instructions.extend(update_amm_config_v2_instr(
+                                &pool_config.clone(),
+                                amm_config_key,
+                                AmmConfigUpdate::Status(amm_config.status | disable_bits),
+                            )?);
+                        }
+                        // enabling status bits only applies after the admin change delay
+                        let enable_bits = amm_config.status & !status;
+                        if enable_bits != 0 {
+                            instructions.extend(schedule_admin_change_instr(
+                                &pool_config.clone(),
+                                amm_config_key,
+                                nonce,
+                                AdminChange::EnableAmmConfigStatus(enable_bits),
+                            )?);
+                        }
+                        instructions
+                    } else if update.is_fee_rate_change() {
+                        // fee rate changes only apply after the admin change delay
+                        schedule_admin_change_instr(
+                            &pool_config.clone(),
+                            amm_config_key,
+                            nonce,
+                            AdminChange::AmmConfig(update),
+                        )?
+                    } else {
+                        update_amm_config_v2_instr(&pool_config.clone(), amm_config_key, update)?
+                    }
+                }
+            };
+            // send
+            let signers = vec![&payer, &admin];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &update_amm_config_instr,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
+        CommandsName::AcceptConfigOwner { config_index } => {
+            let (amm_config_key, __bump) = Pubkey::find_program_address(
+                &[
+                    raydium_amm_v3::states::AMM_CONFIG_SEED.as_bytes(),
+                    &config_index.to_be_bytes(),
+                ],
+                &pool_config.raydium_v3_program,
+            );
+            let accept_instr = accept_amm_config_owner_instr(&pool_config.clone(), amm_config_key)?;
+            // send
+            let signers = vec![&payer];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &accept_instr,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
+        CommandsName::UpdateObservationDuration {
+            observation_update_duration,
+        } => {
+            let pool_id = pool_config.pool_id_account.unwrap();
+            let pool: raydium_amm_v3::states::PoolState = program.account(pool_id)?;
+            let update_instr = update_observation_update_duration_instr(
+                &pool_config.clone(),
+                pool_id,
+                pool.observation_key,
+                observation_update_duration,
+            )?;
+            // send
+            let signers = vec![&payer, &admin];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &update_instr,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
+        CommandsName::UpdatePoolFeeRates {
+            trade_fee_rate,
+            protocol_fee_rate,
+            fund_fee_rate,
+        } => {
+            let pool_id = pool_config.pool_id_account.unwrap();
+            let pool: raydium_amm_v3::states::PoolState = program.account(pool_id)?;
+            let update_instr = update_pool_fee_rates_instr(
+                &pool_config.clone(),
+                pool.amm_config,
+                pool_id,
+                trade_fee_rate,
+                protocol_fee_rate,
+                fund_fee_rate,
+            )?;
+            // send
+            let signers = vec![&payer, &admin];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &update_instr,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
+        CommandsName::PausePool { swap, deposit } => {
+            let pool_id = pool_config.pool_id_account.unwrap();
+            let pool: raydium_amm_v3::states::PoolState = program.account(pool_id)?;
+            let status = pool.status | pause_status_bits(swap, deposit)?;
+            let update_instr = update_pool_status_instr(&pool_config.clone(), pool_id, status)?;
+            // send
+            let signers = vec![&payer, &admin];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &update_instr,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
+        CommandsName::PauseConfigPools {
+            config_index,
+            swap,
+            deposit,
+        } => {
+            let disabled_bits = pause_status_bits(swap, deposit)?;
+            let (amm_config_key, __bump) = Pubkey::find_program_address(
+                &[
+                    raydium_amm_v3::states::AMM_CONFIG_SEED.as_bytes(),
+                    &config_index.to_be_bytes(),
+                ],
+                &program.id(),
+            );
+            let pools_by_config = rpc_client.get_program_accounts_with_config(
+                &pool_config.raydium_v3_program,
+                RpcProgramAccountsConfig {
+                    filters: Some(vec![
+                        RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+                            8 + 1,
+                            &amm_config_key.to_bytes(),
+                        )),
+                        RpcFilterType::DataSize(PoolState::LEN as u64),
+                    ]),
+                    account_config: RpcAccountInfoConfig {
+                        encoding: Some(UiAccountEncoding::Base64Zstd),
+                        ..RpcAccountInfoConfig::default()
+                    },
+                    with_context: Some(false),
+                },
+            )?;
+            let pools: Vec<Pubkey> = pools_by_config.into_iter().map(|pool| pool.0).collect();
+            println!("pools of amm_config {}: {}", amm_config_key, pools.len());
+            // the config is paused by the first transaction even without pools
+            let pool_chunks: Vec<&[Pubkey]> = if pools.is_empty() {
+                vec![&pools[..]]
+            } else {
+                pools.chunks(20).collect()
+            };
+            for pools in pool_chunks {
+                let pause_instr = pause_amm_config_pools_instr(
+                    &pool_config.clone(),
+                    amm_config_key,
+                    pools.to_vec(),
+                    disabled_bits,
+                )?;
+                // send
+                let signers = vec![&payer, &admin];
+                let recent_hash = rpc_client.get_latest_blockhash()?;
+                let txn = Transaction::new_signed_with_payer(
+                    &pause_instr,
+                    Some(&payer.pubkey()),
+                    &signers,
+                    recent_hash,
+                );
+                let signature = send_txn(&rpc_client, &txn, true)?;
+                println!("{}", signature);
+            }
+        }
+        CommandsName::SetFeeDistribution {
+            config_index,
+            recipients,
+            shares_bps,
+            fund,
+        } => {
+            if recipients.len() != shares_bps.len() {
+                panic!("each recipient must have a share");
+            }
+            let (amm_config_key, __bump) = Pubkey::find_program_address(
+                &[
+                    raydium_amm_v3::states::AMM_CONFIG_SEED.as_bytes(),
+                    &config_index.to_be_bytes(),
+                ],
+                &program.id(),
+            );
+            let recipients = recipients
+                .into_iter()
+                .zip(shares_bps)
+                .map(|(recipient, share_bps)| FeeRecipient {
+                    recipient,
+                    share_bps,
+                })
+                .collect();
+            let set_instr = if fund {
+                set_fund_fee_distribution_instr(&pool_config.clone(), amm_config_key, recipients)?
+            } else {
+                set_fee_distribution_instr(&pool_config.clone(), amm_config_key, recipients)?
+            };
+            // send
+            let signers = vec![&payer, &admin];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &set_instr,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
+        CommandsName::DistributeFees => {
+            let pool_id = pool_config.pool_id_account.unwrap();
+            let pool: PoolState = program.account(pool_id)?;
+            let (fee_distribution_key, __bump) = Pubkey::find_program_address(
+                &[
+                    raydium_amm_v3::states::FEE_DISTRIBUTION_SEED.as_bytes(),
+                    pool.amm_config.to_bytes().as_ref(),
+                ],
+                &program.id(),
+            );
+            let fee_distribution: FeeDistributionState = program.account(fee_distribution_key)?;
+            let mint_0_owner = rpc_client.get_account(&pool.token_mint_0)?.owner;
+            let mint_1_owner = rpc_client.get_account(&pool.token_mint_1)?.owner;
+            // the token_0 and token_1 accounts of each protocol fee recipient, then of each fund fee recipient
+            let mut remaining_accounts = Vec::new();
+            for fee_recipient in fee_distribution
+                .protocol_recipients
+                .iter()
+                .chain(fee_distribution.fund_recipients.iter())
+            {
+                remaining_accounts.push(AccountMeta::new(
+                    spl_associated_token_account::get_associated_token_address_with_program_id(
+                        &fee_recipient.recipient,
+                        &pool.token_mint_0,
+                        &mint_0_owner,
+                    ),
+                    false,
+                ));
+                remaining_accounts.push(AccountMeta::new(
+                    spl_associated_token_account::get_associated_token_address_with_program_id(
+                        &fee_recipient.recipient,
+                        &pool.token_mint_1,
+                        &mint_1_owner,
+                    ),
+                    false,
+                ));
+            }
+            let distribute_instr = distribute_fees_instr(
+                &pool_config.clone(),
+                pool_id,
+                pool.amm_config,
+                pool.token_vault_0,
+                pool.token_vault_1,
+                pool.token_mint_0,
+                pool.token_mint_1,
+                remaining_accounts,
+            )?;
+            // send
+            let signers = vec![&payer];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &distribute_instr,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
+        CommandsName::SchedulePoolStatus { enable_bits } => {
+            let pool_id = pool_config.pool_id_account.unwrap();
+            let nonce = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)?
+                .as_secs();
+            let schedule_instr = schedule_admin_change_instr(
+                &pool_config.clone(),
+                pool_id,
+                nonce,
+                AdminChange::EnablePoolStatus(enable_bits),
+            )?;
+            // send
+            let signers = vec![&payer, &admin];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &schedule_instr,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
+        CommandsName::ExecuteChange { scheduled_change } => {
+            let scheduled_change_state: ScheduledChangeState = program.account(scheduled_change)?;
+            let execute_instr = execute_admin_change_instr(
+                &pool_config.clone(),
+                scheduled_change,
+                scheduled_change_state.authority,
+                scheduled_change_state.target,
+                scheduled_change_state.change,
+            )?;
+            // send
+            let signers = vec![&payer];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &execute_instr,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
+        CommandsName::CancelChange { scheduled_change } => {
+            let cancel_instr = cancel_admin_change_instr(&pool_config.clone(), scheduled_change)?;
+            // send
+            let signers = vec![&payer, &admin];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &cancel_instr,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
+        CommandsName::CreateSwapAllowlist {
+            launch_duration,
+            keys,
+        } => {
+            let create_instr = create_swap_allowlist_instr(
+                &pool_config.clone(),
+                pool_config.pool_id_account.unwrap(),
+                launch_duration,
+                keys,
+            )?;
+            // send
+            let signers = vec![&payer];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &create_instr,
                 Some(&payer.pubkey()),
                 &signers,
                 recent_hash,
@@ -738,14 +1551,17 @@ fn main() -> Result<()> {
             let signature = send_txn(&rpc_client, &txn, true)?;
             println!("{}", signature);
         }
-        CommandsName::UnWrapSol { wrap_sol_account } => {
-            let unwrap_sol_instr =
-                close_token_account(&pool_config, &wrap_sol_account, &payer.pubkey(), &payer)?;
+        CommandsName::UpdateSwapAllowlist { update } => {
+            let update_instr = update_swap_allowlist_instr(
+                &pool_config.clone(),
+                pool_config.pool_id_account.unwrap(),
+                update.into(),
+            )?;
             // send
             let signers = vec![&payer];
             let recent_hash = rpc_client.get_latest_blockhash()?;
             let txn = Transaction::new_signed_with_payer(
-                &unwrap_sol_instr,
+                &update_instr,
                 Some(&payer.pubkey()),
                 &signers,
                 recent_hash,
@@ -753,21 +1569,8 @@ fn main() -> Result<()> {
             let signature = send_txn(&rpc_client, &txn, true)?;
             println!("{}", signature);
         }
-        CommandsName::CreateConfig {
-            config_index,
-            tick_spacing,
-            trade_fee_rate,
-            protocol_fee_rate,
-            fund_fee_rate,
-        } => {
-            let create_instr = create_amm_config_instr(
-                &pool_config.clone(),
-                config_index,
-                tick_spacing,
-                trade_fee_rate,
-                protocol_fee_rate,
-                fund_fee_rate,
-            )?;
+        CommandsName::CreateOperation => {
+            let create_instr = create_operation_account_instr(&pool_config.clone())?;
             // send
             let signers = vec![&payer, &admin];
             let recent_hash = rpc_client.get_latest_blockhash()?;
@@ -780,48 +1583,13 @@ fn main() -> Result<()> {
             let signature = send_txn(&rpc_client, &txn, true)?;
             println!("{}", signature);
         }
-        CommandsName::UpdateConfig {
-            config_index,
-            param,
-            value,
-            remaining,
-        } => {
-            let mut remaing_accounts = Vec::new();
-            let mut update_value = 0;
-            let match_param = Some(param);
-            match match_param {
-                Some(0) => update_value = value,
-                Some(1) => update_value = value,
-                Some(2) => update_value = value,
-                Some(3) => {
-                    let remaining_key = remaining.unwrap();
-                    remaing_accounts.push(AccountMeta::new_readonly(remaining_key, false));
-                }
-                Some(4) => {
-                    let remaining_key = remaining.unwrap();
-                    remaing_accounts.push(AccountMeta::new_readonly(remaining_key, false));
-                }
-                _ => panic!("error input"),
-            }
-            let (amm_config_key, __bump) = Pubkey::find_program_address(
-                &[
-                    raydium_amm_v3::states::AMM_CONFIG_SEED.as_bytes(),
-                    &config_index.to_be_bytes(),
-                ],
-                &pool_config.raydium_v3_program,
-            );
-            let update_amm_config_instr = update_amm_config_instr(
-                &pool_config.clone(),
-                amm_config_key,
-                remaing_accounts,
-                param,
-                update_value,
-            )?;
+        CommandsName::CreateMintRegistry => {
+            let create_instr = create_mint_registry_instr(&pool_config.clone())?;
             // send
             let signers = vec![&payer, &admin];
             let recent_hash = rpc_client.get_latest_blockhash()?;
             let txn = Transaction::new_signed_with_payer(
-                &update_amm_config_instr,
+                &create_instr,
                 Some(&payer.pubkey()),
                 &signers,
                 recent_hash,
@@ -829,13 +1597,13 @@ fn main() -> Result<()> {
             let signature = send_txn(&rpc_client, &txn, true)?;
             println!("{}", signature);
         }
-        CommandsName::CreateOperation => {
-            let create_instr = create_operation_account_instr(&pool_config.clone())?;
+        CommandsName::UpdateMintRegistry { update } => {
+            let update_instr = update_mint_registry_instr(&pool_config.clone(), update.into())?;
             // send
             let signers = vec![&payer, &admin];
             let recent_hash = rpc_client.get_latest_blockhash()?;
             let txn = Transaction::new_signed_with_payer(
-                &create_instr,
+                &update_instr,
                 Some(&payer.pubkey()),
                 &signers,
                 recent_hash,
@@ -843,8 +1611,8 @@ fn main() -> Result<()> {
             let signature = send_txn(&rpc_client, &txn, true)?;
             println!("{}", signature);
         }
-        CommandsName::UpdateOperation { param, keys } => {
-            let create_instr = update_operation_account_instr(&pool_config.clone(), param, keys)?;
+        CommandsName::UpdateOperation { update } => {
+            let create_instr = update_operation_account_instr(&pool_config.clone(), update.into())?;
             // send
             let signers = vec![&payer, &admin];
             let recent_hash = rpc_client.get_latest_blockhash()?;
@@ -919,6 +1687,30 @@ fn main() -> Result<()> {
             let signature = send_txn(&rpc_client, &txn, true)?;
             println!("{}", signature);
         }
+        CommandsName::CreateRewardExtension => {
+            let program = anchor_client.program(pool_config.raydium_v3_program)?;
+            let operator_account_key = Pubkey::find_program_address(
+                &[raydium_amm_v3::states::OPERATION_SEED.as_bytes()],
+                &program.id(),
+            )
+            .0;
+            let create_instr = create_reward_extension_instr(
+                &pool_config.clone(),
+                pool_config.pool_id_account.unwrap(),
+                operator_account_key,
+            )?;
+            // send
+            let signers = vec![&payer, &admin];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &create_instr,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
         CommandsName::InitReward {
             open_time,
             end_time,
@@ -956,6 +1748,7 @@ fn main() -> Result<()> {
                 reward_token_vault,
                 user_reward_token,
                 mint_account.owner,
+                pool_reward_extension(&pool_config, &pool_account),
                 open_time,
                 end_time,
                 emissions_per_second_x64,
@@ -1008,6 +1801,7 @@ fn main() -> Result<()> {
                 reward_token_vault,
                 user_reward_token,
                 operator_account_key,
+                pool_reward_extension(&pool_config, &pool_account),
                 index,
                 open_time,
                 end_time,
@@ -1025,6 +1819,200 @@ fn main() -> Result<()> {
             let signature = send_txn(&rpc_client, &txn, true)?;
             println!("{}", signature);
         }
+        CommandsName::FundReward {
+            index,
+            amount,
+            reward_mint,
+            increase_emissions,
+        } => {
+            let program = anchor_client.program(pool_config.raydium_v3_program)?;
+            let pool_account: raydium_amm_v3::states::PoolState =
+                program.account(pool_config.pool_id_account.unwrap())?;
+            let reward_token_vault = Pubkey::find_program_address(
+                &[
+                    raydium_amm_v3::states::POOL_REWARD_VAULT_SEED.as_bytes(),
+                    pool_config.pool_id_account.unwrap().to_bytes().as_ref(),
+                    reward_mint.to_bytes().as_ref(),
+                ],
+                &program.id(),
+            )
+            .0;
+            let funder_token_account = get_associated_token_address(&payer.pubkey(), &reward_mint);
+            let mode = if increase_emissions {
+                raydium_amm_v3::instructions::FundRewardMode::IncreaseEmissions
+            } else {
+                raydium_amm_v3::instructions::FundRewardMode::ExtendEndTime
+            };
+            let fund_instr = fund_reward_instr(
+                &pool_config.clone(),
+                pool_config.pool_id_account.unwrap(),
+                funder_token_account,
+                reward_token_vault,
+                reward_mint,
+                pool_reward_extension(&pool_config, &pool_account),
+                index,
+                amount,
+                mode,
+            )?;
+            // send
+            let signers = vec![&payer];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &fund_instr,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
+        CommandsName::RetireReward { index, reward_mint } => {
+            let program = anchor_client.program(pool_config.raydium_v3_program)?;
+            let pool_account: raydium_amm_v3::states::PoolState =
+                program.account(pool_config.pool_id_account.unwrap())?;
+            let reward_token_vault = Pubkey::find_program_address(
+                &[
+                    raydium_amm_v3::states::POOL_REWARD_VAULT_SEED.as_bytes(),
+                    pool_config.pool_id_account.unwrap().to_bytes().as_ref(),
+                    reward_mint.to_bytes().as_ref(),
+                ],
+                &program.id(),
+            )
+            .0;
+            // the remaining balance of the vault is swept to the pool owner
+            let reward_mint_owner = rpc_client.get_account(&reward_mint)?.owner;
+            let owner_token_account =
+                spl_associated_token_account::get_associated_token_address_with_program_id(
+                    &pool_account.owner,
+                    &reward_mint,
+                    &reward_mint_owner,
+                );
+            let retire_instr = retire_reward_instr(
+                &pool_config.clone(),
+                pool_config.pool_id_account.unwrap(),
+                reward_token_vault,
+                reward_mint,
+                owner_token_account,
+                pool_reward_extension(&pool_config, &pool_account),
+                index,
+            )?;
+            // send
+            let signers = vec![&payer];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &retire_instr,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
+        CommandsName::SetRewardSchedule {
+            index,
+            end_emissions,
+            step_period,
+            constant,
+        } => {
+            let end_emissions_per_second_x64 = (end_emissions * fixed_point_64::Q64 as f64) as u128;
+            let kind = if constant {
+                raydium_amm_v3::states::RewardScheduleKind::Constant
+            } else if step_period.is_some() {
+                raydium_amm_v3::states::RewardScheduleKind::Step
+            } else {
+                raydium_amm_v3::states::RewardScheduleKind::LinearDecay
+            };
+
+            let program = anchor_client.program(pool_config.raydium_v3_program)?;
+            let pool_account: raydium_amm_v3::states::PoolState =
+                program.account(pool_config.pool_id_account.unwrap())?;
+            let operator_account_key = Pubkey::find_program_address(
+                &[raydium_amm_v3::states::OPERATION_SEED.as_bytes()],
+                &program.id(),
+            )
+            .0;
+            let schedule_instr = set_reward_schedule_instr(
+                &pool_config.clone(),
+                pool_config.pool_id_account.unwrap(),
+                operator_account_key,
+                pool_reward_extension(&pool_config, &pool_account),
+                index,
+                kind,
+                step_period.unwrap_or_default(),
+                end_emissions_per_second_x64,
+            )?;
+            // send
+            let signers = vec![&payer, &admin];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &schedule_instr,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
+        CommandsName::LockPosition {
+            nft_mint,
+            lock_until,
+        } => {
+            let position_lock_instr = position_lock_instr(
+                &pool_config,
+                &rpc_client,
+                &payer.pubkey(),
+                nft_mint,
+                Some(lock_until),
+            )?;
+            // send
+            let signers = vec![&payer];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &position_lock_instr,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
+        CommandsName::UnlockPosition { nft_mint } => {
+            let position_unlock_instr =
+                position_lock_instr(&pool_config, &rpc_client, &payer.pubkey(), nft_mint, None)?;
+            // send
+            let signers = vec![&payer];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &position_unlock_instr,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
+        CommandsName::SetPositionLockConfig {
+            lock_reward_multiplier,
+            min_lock_duration,
+        } => {
+            let lock_config_instr = set_position_lock_config_instr(
+                &pool_config,
+                pool_config.pool_id_account.unwrap(),
+                lock_reward_multiplier,
+                min_lock_duration,
+            )?;
+            // send
+            let signers = vec![&payer, &admin];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &lock_config_instr,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
         CommandsName::TransferRewardOwner {
             pool_id,
             new_owner,
@@ -1057,6 +2045,21 @@ fn main() -> Result<()> {
                 println!("{}", signature);
             }
         }
+        CommandsName::AcceptRewardOwner { pool_id } => {
+            let accept_reward_owner_instr =
+                accept_reward_owner_instr(&pool_config.clone(), pool_id)?;
+            // send
+            let signers = vec![&payer];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &accept_reward_owner_instr,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
         CommandsName::OpenPosition {
             tick_lower_price,
             tick_upper_price,
@@ -1193,6 +2196,9 @@ fn main() -> Result<()> {
                     pool_config.tickarray_bitmap_extension.unwrap(),
                     false,
                 ));
+                if let Some(reward_extension) = pool_reward_extension(&pool_config, &pool) {
+                    remaining_accounts.push(AccountMeta::new(reward_extension, false));
+                }
 
                 let mut instructions = Vec::new();
                 let request_inits_instr =
@@ -1201,6 +2207,7 @@ fn main() -> Result<()> {
                 let open_position_instr = open_position_with_token22_nft_instr(
                     &pool_config.clone(),
                     pool_config.pool_id_account.unwrap(),
+                    pool.amm_config,
                     pool.token_vault_0,
                     pool.token_vault_1,
                     pool.token_mint_0,
@@ -1384,10 +2391,14 @@ fn main() -> Result<()> {
                     pool_config.tickarray_bitmap_extension.unwrap(),
                     false,
                 ));
+                if let Some(reward_extension) = pool_reward_extension(&pool_config, &pool) {
+                    remaining_accounts.push(AccountMeta::new(reward_extension, false));
+                }
 
                 let increase_instr = increase_liquidity_instr(
                     &pool_config.clone(),
                     pool_config.pool_id_account.unwrap(),
+                    pool.amm_config,
                     pool.token_vault_0,
                     pool.token_vault_1,
                     pool.token_mint_0,
@@ -1500,6 +2511,22 @@ fn main() -> Result<()> {
                         reward_vault_with_user_vault.push(item.token_mint);
                     }
                 }
+                // the rewards of the reward extension follow the pool rewards
+                let reward_extension = pool_reward_extension(&pool_config, &pool);
+                if let Some(reward_extension) = reward_extension {
+                    let reward_extension_account: raydium_amm_v3::states::PoolRewardExtension =
+                        program.account(reward_extension)?;
+                    for item in reward_extension_account.reward_infos.into_iter() {
+                        if item.token_mint != Pubkey::default() {
+                            reward_vault_with_user_vault.push(item.token_vault);
+                            reward_vault_with_user_vault.push(get_associated_token_address(
+                                &payer.pubkey(),
+                                &item.token_mint,
+                            ));
+                            reward_vault_with_user_vault.push(item.token_mint);
+                        }
+                    }
+                }
                 let liquidity = if let Some(liquidity) = liquidity {
                     liquidity
                 } else {
@@ -1535,6 +2562,9 @@ fn main() -> Result<()> {
                     pool_config.tickarray_bitmap_extension.unwrap(),
                     false,
                 ));
+                if let Some(reward_extension) = reward_extension {
+                    remaining_accounts.push(AccountMeta::new(reward_extension, false));
+                }
 
                 let mut accounts = reward_vault_with_user_vault
                     .into_iter()
@@ -1545,6 +2575,7 @@ fn main() -> Result<()> {
                 let mut decrease_instr = decrease_liquidity_instr(
                     &pool_config.clone(),
                     pool_config.pool_id_account.unwrap(),
+                    pool.amm_config,
                     pool.token_vault_0,
                     pool.token_vault_1,
                     pool.token_mint_0,
@@ -1643,7 +2674,7 @@ fn main() -> Result<()> {
             let zero_for_one = user_input_state.base.mint == pool_state.token_mint_0
                 && user_output_state.base.mint == pool_state.token_mint_1;
             // load tick_arrays
-            let mut tick_arrays = load_cur_and_next_five_tick_array(
+            let tick_arrays = load_cur_and_next_five_tick_array(
                 &rpc_client,
                 &pool_config,
                 &pool_state,
@@ -1670,7 +2701,7 @@ fn main() -> Result<()> {
                     &amm_config_state,
                     &pool_state,
                     &tickarray_bitmap_extension,
-                    &mut tick_arrays,
+                    &tick_arrays,
                 )
                 .unwrap();
             println!(
@@ -1701,6 +2732,26 @@ fn main() -> Result<()> {
                 pool_config.tickarray_bitmap_extension.unwrap(),
                 false,
             ));
+            if let Some(reward_extension) = pool_reward_extension(&pool_config, &pool_state) {
+                remaining_accounts.push(AccountMeta::new(reward_extension, false));
+            }
+            // the swap allowlist is required until the launch phase of the pool ends
+            let block_timestamp = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)?
+                .as_secs();
+            if block_timestamp < pool_state.launch_end_time {
+                remaining_accounts.push(AccountMeta::new_readonly(
+                    Pubkey::find_program_address(
+                        &[
+                            raydium_amm_v3::states::SWAP_ALLOWLIST_SEED.as_bytes(),
+                            pool_config.pool_id_account.unwrap().to_bytes().as_ref(),
+                        ],
+                        &pool_config.raydium_v3_program,
+                    )
+                    .0,
+                    false,
+                ));
+            }
             let mut accounts = tick_array_indexs
                 .into_iter()
                 .map(|index| {
@@ -1822,7 +2873,7 @@ fn main() -> Result<()> {
             };
             let amount_specified = amount.checked_sub(transfer_fee).unwrap();
             // load tick_arrays
-            let mut tick_arrays = load_cur_and_next_five_tick_array(
+            let tick_arrays = load_cur_and_next_five_tick_array(
                 &rpc_client,
                 &pool_config,
                 &pool_state,
@@ -1849,7 +2900,7 @@ fn main() -> Result<()> {
                     &amm_config_state,
                     &pool_state,
                     &tickarray_bitmap_extension,
-                    &mut tick_arrays,
+                    &tick_arrays,
                 )
                 .unwrap();
             println!(
@@ -1878,6 +2929,26 @@ fn main() -> Result<()> {
                 pool_config.tickarray_bitmap_extension.unwrap(),
                 false,
             ));
+            if let Some(reward_extension) = pool_reward_extension(&pool_config, &pool_state) {
+                remaining_accounts.push(AccountMeta::new(reward_extension, false));
+            }
+            // the swap allowlist is required until the launch phase of the pool ends
+            let block_timestamp = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)?
+                .as_secs();
+            if block_timestamp < pool_state.launch_end_time {
+                remaining_accounts.push(AccountMeta::new_readonly(
+                    Pubkey::find_program_address(
+                        &[
+                            raydium_amm_v3::states::SWAP_ALLOWLIST_SEED.as_bytes(),
+                            pool_config.pool_id_account.unwrap().to_bytes().as_ref(),
+                        ],
+                        &pool_config.raydium_v3_program,
+                    )
+                    .0,
+                    false,
+                ));
+            }
             let mut accounts = tick_array_indexs
                 .into_iter()
                 .map(|index| {
@@ -2047,13 +3118,69 @@ fn main() -> Result<()> {
                 program.account(operation_account_key)?;
             println!("{:#?}", operation_account);
         }
-        CommandsName::PObservation => {
+        CommandsName::PMintRegistry => {
+            let (mint_registry_key, __bump) = Pubkey::find_program_address(
+                &[raydium_amm_v3::states::MINT_REGISTRY_SEED.as_bytes()],
+                &program.id(),
+            );
+            println!("{}", mint_registry_key);
+            let mint_registry: MintRegistryState = program.account(mint_registry_key)?;
+            let allowed_extension_flag = mint_registry.allowed_extension_flag;
+            let allowed_extensions: Vec<ExtensionType> = (0u16..64)
+                .filter(|bit| allowed_extension_flag & (1 << bit) != 0)
+                .filter_map(|bit| ExtensionType::try_from(bit).ok())
+                .collect();
+            println!("allowed_extensions:{:?}", allowed_extensions);
+            for registered_mint in mint_registry.mints {
+                if registered_mint.mint == Pubkey::default() {
+                    continue;
+                }
+                println!("{:?}", registered_mint);
+            }
+        }
+        CommandsName::PObservation { stats } => {
             let pool: raydium_amm_v3::states::PoolState =
                 program.account(pool_config.pool_id_account.unwrap())?;
             println!("{}", pool.observation_key);
-            let observation_account: raydium_amm_v3::states::ObservationState =
-                program.account(pool.observation_key)?;
-            println!("{:#?}", observation_account);
+            let (observation_account, observation_extension) =
+                raydium_amm_v3::states::ObservationState::from_account_data(
+                    &rpc_client.get_account_data(&pool.observation_key)?,
+                )?;
+            println!(
+                "observation_update_duration:{}, cardinality:{}, cardinality_next:{}",
+                observation_account.update_duration(),
+                observation_account.cardinality(),
+                observation_account.cardinality_next()
+            );
+            if let Some(window) = stats {
+                let block_timestamp = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)?
+                    .as_secs() as u32;
+                let metrics = raydium_amm_v3::states::get_oracle_metrics(
+                    &observation_account,
+                    &observation_extension,
+                    block_timestamp,
+                    window,
+                    pool.tick_current,
+                    pool.seconds_per_liquidity_cumulative_x64(u64::from(block_timestamp)),
+                )?;
+                let volatility = match metrics.volatility_x64 {
+                    Some(volatility_x64) => format!(
+                        "{} ticks",
+                        volatility_x64 as f64 / fixed_point_64::Q64 as f64
+                    ),
+                    None => "not enough observations, at least 3 are required".to_string(),
+                };
+                println!(
+                    "window:{}, observation_count:{}, volatility:{}, harmonic_mean_liquidity:{}",
+                    metrics.window,
+                    metrics.observation_count,
+                    volatility,
+                    metrics.harmonic_mean_liquidity
+                );
+            } else {
+                println!("{:#?}", observation_account);
+            }
         }
         CommandsName::PConfig { config_index } => {
             let (amm_config_key, __bump) = Pubkey::find_program_address(
@@ -2256,6 +3383,15 @@ fn main() -> Result<()> {
             println!("pool_id:{}", pool_id);
             let pool_account: raydium_amm_v3::states::PoolState = program.account(pool_id)?;
             println!("{:#?}", pool_account);
+            let amm_config_account: raydium_amm_v3::states::AmmConfig =
+                program.account(pool_account.amm_config)?;
+            let effective_amm_config = pool_account.effective_amm_config(&amm_config_account);
+            println!(
+                "trade_fee_rate:{}, protocol_fee_rate:{}, fund_fee_rate:{}",
+                effective_amm_config.get_trade_fee_rate(pool_account.volatility_accumulator),
+                effective_amm_config.protocol_fee_rate,
+                effective_amm_config.fund_fee_rate
+            );
         }
         CommandsName::PBitmapExtension { bitmap_extension } => {
             let bitmap_extension = if let Some(bitmap_extension) = bitmap_extension {
@@ -105,4 +105,130 @@ pub enum ErrorCode {
     MaxTokenOverflow,
     #[msg("calculate overflow")]
     CalculateOverflow,
+
+    /// oracle errors
+    #[msg("Observation state is not initialized")]
+    ObservationNotInitialized,
+    #[msg("The requested observation is older than the oldest stored observation")]
+    ObservationTooOld,
+    #[msg("Invalid observe params")]
+    InvalidObserveParams,
+    #[msg("Invalid observation cardinality")]
+    InvalidObservationCardinality,
+    #[msg("Invalid observation update duration")]
+    InvalidObservationUpdateDuration,
+
+    /// router errors
+    #[msg("Invalid route accounts")]
+    InvalidRouteAccounts,
+
+    /// launch errors
+    #[msg("The swapper is not on the swap allowlist of the pool")]
+    NotInSwapAllowlist,
+    #[msg("The swap allowlist is full")]
+    SwapAllowlistFull,
+    #[msg("Invalid launch duration")]
+    InvalidLaunchDuration,
+    #[msg("The pool is already open")]
+    PoolAlreadyOpen,
+
+    /// config errors
+    #[msg("Invalid trade fee rate")]
+    InvalidTradeFeeRate,
+    #[msg("Invalid protocol fee rate")]
+    InvalidProtocolFeeRate,
+    #[msg("Invalid fund fee rate")]
+    InvalidFundFeeRate,
+    #[msg("Invalid dynamic fee bounds")]
+    InvalidDynamicFeeBounds,
+
+    /// ownership errors
+    #[msg("Owner changes must be proposed and accepted")]
+    OwnerChangeNotAccepted,
+    #[msg("The signer is not the pending owner")]
+    NotPendingOwner,
+
+    /// timelock errors
+    #[msg("The change must be scheduled")]
+    AdminChangeNotScheduled,
+    #[msg("The scheduled change is not effective yet")]
+    AdminChangeNotEffective,
+    #[msg("Invalid target of the scheduled change")]
+    InvalidAdminChangeTarget,
+
+    /// mint registry errors
+    #[msg("Invalid token-2022 extension type")]
+    InvalidExtensionType,
+    #[msg("Invalid max transfer fee")]
+    InvalidMaxTransferFee,
+    #[msg("The mint registry is full")]
+    MintRegistryFull,
+
+    /// operator role errors
+    #[msg("Invalid operator roles")]
+    InvalidOperatorRoles,
+    #[msg("The operation owner list is full")]
+    OperationOwnerListFull,
+
+    /// pause guardian errors
+    #[msg("No pool status bits to disable")]
+    InvalidPoolStatusBits,
+    #[msg("The pool does not belong to the amm config")]
+    PoolNotInAmmConfig,
+
+    /// fee distribution errors
+    #[msg("Invalid fee distribution recipients or shares")]
+    InvalidFeeDistribution,
+    #[msg("Invalid fee recipient token account")]
+    InvalidFeeRecipientTokenAccount,
+
+    /// reward extension errors
+    #[msg("The reward extension account of the pool is missing")]
+    MissingRewardExtensionAccount,
+
+    /// fund reward errors
+    #[msg("The reward emission has ended")]
+    RewardEmissionEnded,
+    #[msg("The fund amount is too small to change the reward emission")]
+    FundRewardAmountTooSmall,
+
+    /// retire reward errors
+    #[msg("The reward has not ended or its positions have not claimed every emitted reward")]
+    RewardNotSettled,
+
+    /// reward schedule errors
+    #[msg("Invalid reward emission schedule")]
+    InvalidRewardSchedule,
+    #[msg("The emission of a scheduled reward can't be changed while it follows its schedule")]
+    ScheduledRewardRunning,
+    #[msg("The emission schedule can only be set before the reward opens")]
+    RewardScheduleLocked,
+
+    /// position lock errors
+    #[msg("The liquidity of a locked position can't be changed")]
+    PositionLocked,
+    #[msg("The lock of the position has not expired")]
+    PositionLockNotExpired,
+    #[msg("The pool does not allow position locks")]
+    PositionLockDisabled,
+    #[msg("The lock is shorter than the minimum lock duration of the pool")]
+    InvalidLockDuration,
+    #[msg("Invalid lock reward multiplier")]
+    InvalidLockRewardMultiplier,
+
+    /// scheduled status errors
+    #[msg("Scheduled status changes must name the status bits to enable")]
+    InvalidScheduledStatusChange,
+
+    /// create pool errors
+    #[msg("The open time is too far in the future")]
+    InvalidOpenTime,
+
+    /// mint registry errors
+    #[msg("The mint registry account is missing")]
+    MissingMintRegistryAccount,
+
+    /// amm config status errors
+    #[msg("The amm config account is missing")]
+    MissingAmmConfigAccount,
 }
@@ -0,0 +1,33 @@
+use super::apply_amm_config_update;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct AcceptAmmConfigOwner<'info> {
+    /// The proposed owner or fund owner
+    pub new_owner: Signer<'info>,
+
+    #[account(mut)]
+    pub amm_config: Account<'info, AmmConfig>,
+
+    #[account(
+        mut,
+        seeds = [
+            AMM_CONFIG_PENDING_OWNER_SEED.as_bytes(),
+            amm_config.key().as_ref(),
+        ],
+        bump = pending_owner.bump,
+    )]
+    pub pending_owner: Account<'info, AmmConfigPendingOwner>,
+}
+
+pub fn accept_amm_config_owner(ctx: Context<AcceptAmmConfigOwner>) -> Result<()> {
+    let updates = ctx
+        .accounts
+        .pending_owner
+        .accept(ctx.accounts.new_owner.key())?;
+    for update in updates {
+        apply_amm_config_update(&mut ctx.accounts.amm_config, update)?;
+    }
+    Ok(())
+}
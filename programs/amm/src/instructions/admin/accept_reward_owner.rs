@@ -0,0 +1,28 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct AcceptRewardOwner<'info> {
+    /// The owner proposed by transfer_reward_owner
+    pub new_owner: Signer<'info>,
+
+    #[account(mut)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+}
+
+pub fn accept_reward_owner(ctx: Context<AcceptRewardOwner>) -> Result<()> {
+    let new_owner = ctx.accounts.new_owner.key();
+    let mut pool_state = ctx.accounts.pool_state.load_mut()?;
+    require_keys_eq!(
+        pool_state.pending_owner,
+        new_owner,
+        ErrorCode::NotPendingOwner
+    );
+    for reward_info in &mut pool_state.reward_infos {
+        reward_info.authority = new_owner;
+    }
+    pool_state.owner = new_owner;
+    pool_state.pending_owner = Pubkey::default();
+    Ok(())
+}
@@ -0,0 +1,26 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CancelAdminChange<'info> {
+    /// Only admin can cancel a change
+    #[account(
+        mut,
+        address = crate::admin::id() @ ErrorCode::NotApproved
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(mut, close = authority)]
+    pub scheduled_change: Account<'info, ScheduledChangeState>,
+}
+
+pub fn cancel_admin_change(ctx: Context<CancelAdminChange>) -> Result<()> {
+    let scheduled_change = &ctx.accounts.scheduled_change;
+    emit!(AdminChangeCancelledEvent {
+        scheduled_change: scheduled_change.key(),
+        target: scheduled_change.target,
+        change: scheduled_change.change,
+    });
+    Ok(())
+}
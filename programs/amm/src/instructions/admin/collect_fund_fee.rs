@@ -1,5 +1,5 @@
+use super::check_fee_collector;
 use crate::decrease_liquidity::check_unclaimed_fees_and_vault;
-use crate::error::ErrorCode;
 use crate::states::*;
 use crate::util::*;
 use anchor_lang::prelude::*;
@@ -7,8 +7,9 @@ use anchor_spl::token::Token;
 use anchor_spl::token_interface::{Mint, Token2022, TokenAccount};
 #[derive(Accounts)]
 pub struct CollectFundFee<'info> {
-    /// Only admin or fund_owner can collect fee now
-    #[account(constraint = (owner.key() == amm_config.fund_owner || owner.key() == crate::admin::id()) @ ErrorCode::NotApproved)]
+    /// Only admin or fund_owner can collect fee now,
+    /// or an operator with the fee collector role to the accounts of the fund_owner,
+    /// it passes the operation account in the remaining accounts
     pub owner: Signer<'info>,
 
     /// Pool state stores accumulated protocol fee amount
@@ -60,11 +61,27 @@ pub struct CollectFundFee<'info> {
     pub token_program_2022: Program<'info, Token2022>,
 }
 
-pub fn collect_fund_fee(
-    ctx: Context<CollectFundFee>,
+pub fn collect_fund_fee<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, CollectFundFee<'info>>,
     amount_0_requested: u64,
     amount_1_requested: u64,
 ) -> Result<()> {
+    let owner = ctx.accounts.owner.key();
+    check_fee_collector(
+        owner,
+        ctx.accounts.amm_config.fund_owner,
+        [
+            ctx.accounts.recipient_token_account_0.owner,
+            ctx.accounts.recipient_token_account_1.owner,
+        ],
+        || {
+            operator_has_role(
+                ctx.remaining_accounts,
+                owner,
+                OperatorRoleBitIndex::FeeCollector,
+            )
+        },
+    )?;
     let amount_0: u64;
     let amount_1: u64;
     {
@@ -8,8 +8,9 @@ use anchor_spl::token_interface::{Mint, Token2022, TokenAccount};
 
 #[derive(Accounts)]
 pub struct CollectProtocolFee<'info> {
-    /// Only admin or config owner can collect fee now
-    #[account(constraint = (owner.key() == amm_config.owner || owner.key() == crate::admin::id()) @ ErrorCode::NotApproved)]
+    /// Only admin or config owner can collect fee now,
+    /// or an operator with the fee collector role to the accounts of the config owner,
+    /// it passes the operation account in the remaining accounts
     pub owner: Signer<'info>,
 
     /// Pool state stores accumulated protocol fee amount
@@ -63,11 +64,27 @@ pub struct CollectProtocolFee<'info> {
     pub token_program_2022: Program<'info, Token2022>,
 }
 
-pub fn collect_protocol_fee(
-    ctx: Context<CollectProtocolFee>,
+pub fn collect_protocol_fee<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, CollectProtocolFee<'info>>,
     amount_0_requested: u64,
     amount_1_requested: u64,
 ) -> Result<()> {
+    let owner = ctx.accounts.owner.key();
+    check_fee_collector(
+        owner,
+        ctx.accounts.amm_config.owner,
+        [
+            ctx.accounts.recipient_token_account_0.owner,
+            ctx.accounts.recipient_token_account_1.owner,
+        ],
+        || {
+            operator_has_role(
+                ctx.remaining_accounts,
+                owner,
+                OperatorRoleBitIndex::FeeCollector,
+            )
+        },
+    )?;
     let amount_0: u64;
     let amount_1: u64;
     {
@@ -121,3 +138,55 @@ pub fn collect_protocol_fee(
 
     Ok(())
 }
+
+/// The admin and the fee owner collect the fees to any account,
+/// an operator with the fee collector role only to the accounts of the fee owner
+pub(crate) fn check_fee_collector(
+    collector: Pubkey,
+    fee_owner: Pubkey,
+    recipient_owners: [Pubkey; 2],
+    is_fee_collector_operator: impl FnOnce() -> Result<bool>,
+) -> Result<()> {
+    if collector == fee_owner || collector == crate::admin::id() {
+        return Ok(());
+    }
+    require!(
+        is_fee_collector_operator()?
+            && recipient_owners
+                .iter()
+                .all(|recipient_owner| *recipient_owner == fee_owner),
+        ErrorCode::NotApproved
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn check_fee_collector_test() {
+        let fee_owner = Pubkey::new_unique();
+        let collector = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+
+        // the fee owner and the admin collect to any account
+        check_fee_collector(fee_owner, fee_owner, [other, other], || Ok(false)).unwrap();
+        check_fee_collector(crate::admin::id(), fee_owner, [other, other], || Ok(false)).unwrap();
+
+        // a signer which is neither the fee owner nor a fee collector is rejected
+        assert_eq!(
+            check_fee_collector(collector, fee_owner, [fee_owner, fee_owner], || Ok(false))
+                .unwrap_err(),
+            ErrorCode::NotApproved.into()
+        );
+
+        // a fee collector only collects to the accounts of the fee owner
+        check_fee_collector(collector, fee_owner, [fee_owner, fee_owner], || Ok(true)).unwrap();
+        assert_eq!(
+            check_fee_collector(collector, fee_owner, [fee_owner, collector], || Ok(true))
+                .unwrap_err(),
+            ErrorCode::NotApproved.into()
+        );
+    }
+}
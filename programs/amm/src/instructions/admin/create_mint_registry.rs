@@ -0,0 +1,33 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CreateMintRegistry<'info> {
+    /// Only admin can create the mint registry
+    #[account(
+        mut,
+        address = crate::admin::id() @ ErrorCode::NotApproved
+    )]
+    pub owner: Signer<'info>,
+
+    /// Initialize the mint registry with the default allowed extensions
+    #[account(
+        init,
+        seeds = [
+            MINT_REGISTRY_SEED.as_bytes(),
+        ],
+        bump,
+        payer = owner,
+        space = MintRegistryState::LEN
+    )]
+    pub mint_registry: AccountLoader<'info, MintRegistryState>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_mint_registry(ctx: Context<CreateMintRegistry>) -> Result<()> {
+    let mut mint_registry = ctx.accounts.mint_registry.load_init()?;
+    mint_registry.initialize(ctx.bumps.mint_registry);
+    Ok(())
+}
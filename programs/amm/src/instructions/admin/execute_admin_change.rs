@@ -0,0 +1,82 @@
+use super::apply_amm_config_update;
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ExecuteAdminChange<'info> {
+    /// CHECK: The admin who scheduled the change, receives the rent back
+    #[account(mut, address = scheduled_change.authority)]
+    pub authority: UncheckedAccount<'info>,
+
+    #[account(mut, close = authority)]
+    pub scheduled_change: Account<'info, ScheduledChangeState>,
+
+    /// The target amm config, passed for the amm config changes
+    #[account(
+        mut,
+        address = scheduled_change.target @ ErrorCode::InvalidAdminChangeTarget
+    )]
+    pub amm_config: Option<Account<'info, AmmConfig>>,
+
+    /// The target pool, passed for the pool status changes
+    #[account(
+        mut,
+        address = scheduled_change.target @ ErrorCode::InvalidAdminChangeTarget
+    )]
+    pub pool_state: Option<AccountLoader<'info, PoolState>>,
+}
+
+/// Anyone can apply the change once it is effective
+pub fn execute_admin_change(ctx: Context<ExecuteAdminChange>) -> Result<()> {
+    let scheduled_change = &ctx.accounts.scheduled_change;
+    let block_timestamp = u64::try_from(Clock::get()?.unix_timestamp).unwrap();
+    require_gte!(
+        block_timestamp,
+        scheduled_change.effective_at,
+        ErrorCode::AdminChangeNotEffective
+    );
+
+    match scheduled_change.change {
+        AdminChange::AmmConfig(update) => {
+            let amm_config = ctx
+                .accounts
+                .amm_config
+                .as_mut()
+                .ok_or(ErrorCode::InvalidAdminChangeTarget)?;
+            apply_amm_config_update(amm_config, update)?;
+        }
+        AdminChange::EnablePoolStatus(enable_bits) => {
+            let pool_state_loader = ctx
+                .accounts
+                .pool_state
+                .as_ref()
+                .ok_or(ErrorCode::InvalidAdminChangeTarget)?;
+            let mut pool_state = pool_state_loader.load_mut()?;
+            let status_before = pool_state.status;
+            pool_state.set_status(AdminChange::enable_status_bits(status_before, enable_bits));
+            emit!(PoolStatusChangeEvent::new(
+                pool_state_loader.key(),
+                status_before,
+                pool_state.status
+            ));
+        }
+        AdminChange::EnableAmmConfigStatus(enable_bits) => {
+            let amm_config = ctx
+                .accounts
+                .amm_config
+                .as_mut()
+                .ok_or(ErrorCode::InvalidAdminChangeTarget)?;
+            let status = AdminChange::enable_status_bits(amm_config.status, enable_bits);
+            apply_amm_config_update(amm_config, AmmConfigUpdate::Status(status))?;
+        }
+    }
+
+    emit!(AdminChangeExecutedEvent {
+        scheduled_change: scheduled_change.key(),
+        target: scheduled_change.target,
+        change: scheduled_change.change,
+    });
+
+    Ok(())
+}
@@ -4,12 +4,27 @@ pub use create_amm_config::*;
 pub mod update_amm_config;
 pub use update_amm_config::*;
 
+pub mod update_amm_config_v2;
+pub use update_amm_config_v2::*;
+
+pub mod propose_amm_config_owner;
+pub use propose_amm_config_owner::*;
+
+pub mod accept_amm_config_owner;
+pub use accept_amm_config_owner::*;
+
 pub mod collect_protocol_fee;
 pub use collect_protocol_fee::*;
 
 pub mod collect_fund_fee;
 pub use collect_fund_fee::*;
 
+pub mod set_fee_distribution;
+pub use set_fee_distribution::*;
+
+pub mod set_fund_fee_distribution;
+pub use set_fund_fee_distribution::*;
+
 pub mod create_operation_account;
 pub use create_operation_account::*;
 
@@ -19,8 +34,35 @@ pub use update_operation_account::*;
 pub mod transfer_reward_owner;
 pub use transfer_reward_owner::*;
 
+pub mod accept_reward_owner;
+pub use accept_reward_owner::*;
+
 pub mod update_pool_status;
 pub use update_pool_status::*;
 
+pub mod pause_amm_config_pools;
+pub use pause_amm_config_pools::*;
+
 pub mod create_support_mint_associated;
 pub use create_support_mint_associated::*;
+
+pub mod create_mint_registry;
+pub use create_mint_registry::*;
+
+pub mod update_mint_registry;
+pub use update_mint_registry::*;
+
+pub mod update_observation_update_duration;
+pub use update_observation_update_duration::*;
+
+pub mod update_pool_fee_rates;
+pub use update_pool_fee_rates::*;
+
+pub mod schedule_admin_change;
+pub use schedule_admin_change::*;
+
+pub mod execute_admin_change;
+pub use execute_admin_change::*;
+
+pub mod cancel_admin_change;
+pub use cancel_admin_change::*;
@@ -0,0 +1,48 @@
+use super::apply_amm_config_update;
+use super::update_pool_status::{check_pause_guardian, disable_pool_status_bits};
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct PauseAmmConfigPools<'info> {
+    /// The admin, or the pause guardian which is an operator with the pool pauser role
+    /// and passes the operation account in the remaining accounts
+    pub authority: Signer<'info>,
+
+    /// The amm config of the pools to pause, its status is inherited by every pool of the config
+    /// when the config is passed, the pools are writable remaining accounts
+    #[account(mut)]
+    pub amm_config: Account<'info, AmmConfig>,
+}
+
+/// The bits are disabled on the amm config and on every pool passed, so that the pools are paused
+/// even for the instructions which don't pass the config
+pub fn pause_amm_config_pools<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, PauseAmmConfigPools<'info>>,
+    disabled_bits: u8,
+) -> Result<()> {
+    check_pause_guardian(&ctx.accounts.authority, ctx.remaining_accounts)?;
+    require_neq!(disabled_bits, 0, ErrorCode::InvalidPoolStatusBits);
+
+    let status = ctx.accounts.amm_config.status | disabled_bits;
+    apply_amm_config_update(
+        &mut ctx.accounts.amm_config,
+        AmmConfigUpdate::Status(status),
+    )?;
+
+    let operation_state_key = OperationState::key();
+    for account_info in ctx.remaining_accounts.iter() {
+        if account_info.key() == operation_state_key {
+            continue;
+        }
+        let pool_state_loader = AccountLoader::<PoolState>::try_from(account_info)?;
+        require_keys_eq!(
+            pool_state_loader.load()?.amm_config,
+            ctx.accounts.amm_config.key(),
+            ErrorCode::PoolNotInAmmConfig
+        );
+        disable_pool_status_bits(&pool_state_loader, disabled_bits)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ProposeAmmConfigOwner<'info> {
+    /// Only admin can propose the owners
+    #[account(
+        mut,
+        address = crate::admin::id() @ ErrorCode::NotApproved
+    )]
+    pub authority: Signer<'info>,
+
+    pub amm_config: Account<'info, AmmConfig>,
+
+    /// Stores the proposed owners until they accept
+    #[account(
+        init_if_needed,
+        seeds = [
+            AMM_CONFIG_PENDING_OWNER_SEED.as_bytes(),
+            amm_config.key().as_ref(),
+        ],
+        bump,
+        payer = authority,
+        space = AmmConfigPendingOwner::LEN
+    )]
+    pub pending_owner: Account<'info, AmmConfigPendingOwner>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn propose_amm_config_owner(
+    ctx: Context<ProposeAmmConfigOwner>,
+    new_owner: Option<Pubkey>,
+    new_fund_owner: Option<Pubkey>,
+) -> Result<()> {
+    let pending_owner = &mut ctx.accounts.pending_owner;
+    pending_owner.bump = ctx.bumps.pending_owner;
+    pending_owner.amm_config = ctx.accounts.amm_config.key();
+    pending_owner.propose(new_owner, new_fund_owner);
+    #[cfg(feature = "enable-log")]
+    msg!(
+        "amm_config:{}, pending_owner:{}, pending_fund_owner:{}",
+        pending_owner.amm_config,
+        pending_owner.pending_owner,
+        pending_owner.pending_fund_owner
+    );
+    Ok(())
+}
@@ -0,0 +1,101 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(nonce: u64)]
+pub struct ScheduleAdminChange<'info> {
+    /// Only admin can schedule a change
+    #[account(
+        mut,
+        address = crate::admin::id() @ ErrorCode::NotApproved
+    )]
+    pub authority: Signer<'info>,
+
+    /// CHECK: The amm config or pool the change applies to, checked against the change
+    #[account(owner = crate::id() @ ErrorCode::InvalidAdminChangeTarget)]
+    pub target: UncheckedAccount<'info>,
+
+    /// Holds the change until it is executed or cancelled
+    #[account(
+        init,
+        seeds = [
+            SCHEDULED_CHANGE_SEED.as_bytes(),
+            target.key().as_ref(),
+            &nonce.to_be_bytes(),
+        ],
+        bump,
+        payer = authority,
+        space = ScheduledChangeState::LEN
+    )]
+    pub scheduled_change: Account<'info, ScheduledChangeState>,
+
+    pub system_program: Program<'info, System>,
+
+    /// The target amm config, passed for the amm config changes
+    #[account(address = target.key() @ ErrorCode::InvalidAdminChangeTarget)]
+    pub amm_config: Option<Account<'info, AmmConfig>>,
+
+    /// The target pool, passed for the pool status changes
+    #[account(address = target.key() @ ErrorCode::InvalidAdminChangeTarget)]
+    pub pool_state: Option<AccountLoader<'info, PoolState>>,
+}
+
+pub fn schedule_admin_change(
+    ctx: Context<ScheduleAdminChange>,
+    nonce: u64,
+    change: AdminChange,
+) -> Result<()> {
+    match change {
+        AdminChange::AmmConfig(update) => {
+            require!(!update.is_owner_change(), ErrorCode::OwnerChangeNotAccepted);
+            require!(
+                !matches!(update, AmmConfigUpdate::Status(_)),
+                ErrorCode::InvalidScheduledStatusChange
+            );
+            // reject the invalid rates now instead of after the delay
+            let mut amm_config = ctx
+                .accounts
+                .amm_config
+                .as_deref()
+                .cloned()
+                .ok_or(ErrorCode::InvalidAdminChangeTarget)?;
+            amm_config.apply_update(update)?;
+        }
+        AdminChange::EnableAmmConfigStatus(enable_bits) => {
+            require_neq!(enable_bits, 0, ErrorCode::InvalidScheduledStatusChange);
+            require!(
+                ctx.accounts.amm_config.is_some(),
+                ErrorCode::InvalidAdminChangeTarget
+            );
+        }
+        AdminChange::EnablePoolStatus(enable_bits) => {
+            require_neq!(enable_bits, 0, ErrorCode::InvalidScheduledStatusChange);
+            require!(
+                ctx.accounts.pool_state.is_some(),
+                ErrorCode::InvalidAdminChangeTarget
+            );
+        }
+    }
+
+    let effective_at = u64::try_from(Clock::get()?.unix_timestamp)
+        .unwrap()
+        .checked_add(ADMIN_CHANGE_DELAY)
+        .unwrap();
+    let scheduled_change = &mut ctx.accounts.scheduled_change;
+    scheduled_change.bump = ctx.bumps.scheduled_change;
+    scheduled_change.authority = ctx.accounts.authority.key();
+    scheduled_change.target = ctx.accounts.target.key();
+    scheduled_change.nonce = nonce;
+    scheduled_change.effective_at = effective_at;
+    scheduled_change.change = change;
+
+    emit!(AdminChangeScheduledEvent {
+        scheduled_change: scheduled_change.key(),
+        target: scheduled_change.target,
+        change,
+        effective_at,
+    });
+
+    Ok(())
+}
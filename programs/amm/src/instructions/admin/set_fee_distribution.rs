@@ -0,0 +1,47 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetFeeDistribution<'info> {
+    /// Only admin can set the recipients of the protocol fees
+    #[account(
+        mut,
+        address = crate::admin::id() @ ErrorCode::NotApproved
+    )]
+    pub owner: Signer<'info>,
+
+    pub amm_config: Account<'info, AmmConfig>,
+
+    /// Stores the fee recipients of the pools of the amm config
+    #[account(
+        init_if_needed,
+        seeds = [
+            FEE_DISTRIBUTION_SEED.as_bytes(),
+            amm_config.key().as_ref(),
+        ],
+        bump,
+        payer = owner,
+        space = FeeDistributionState::LEN
+    )]
+    pub fee_distribution: Account<'info, FeeDistributionState>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_fee_distribution(
+    ctx: Context<SetFeeDistribution>,
+    recipients: Vec<FeeRecipient>,
+) -> Result<()> {
+    let fee_distribution = &mut ctx.accounts.fee_distribution;
+    fee_distribution.bump = ctx.bumps.fee_distribution;
+    fee_distribution.amm_config = ctx.accounts.amm_config.key();
+    fee_distribution.set_protocol_recipients(recipients)?;
+
+    emit!(FeeDistributionChangeEvent {
+        amm_config: fee_distribution.amm_config,
+        protocol_recipients: fee_distribution.protocol_recipients.clone(),
+        fund_recipients: fee_distribution.fund_recipients.clone(),
+    });
+    Ok(())
+}
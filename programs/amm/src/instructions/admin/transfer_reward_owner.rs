@@ -17,10 +17,8 @@ pub fn transfer_reward_owner<'a, 'b, 'c, 'info>(
     ctx: Context<'a, 'b, 'c, 'info, TransferRewardOwner<'info>>,
     new_owner: Pubkey,
 ) -> Result<()> {
+    // the new owner takes over once it signs accept_reward_owner
     let mut pool_state = ctx.accounts.pool_state.load_mut()?;
-    for reward_info in &mut pool_state.reward_infos {
-        reward_info.authority = new_owner;
-    }
-    pool_state.owner = new_owner;
+    pool_state.pending_owner = new_owner;
     Ok(())
 }
@@ -4,7 +4,7 @@ use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
 pub struct UpdateAmmConfig<'info> {
-    /// The amm config owner or admin
+    /// Only admin can update the amm config
     #[account(address = crate::admin::id() @ ErrorCode::NotApproved)]
     pub owner: Signer<'info>,
 
@@ -13,24 +13,42 @@ pub struct UpdateAmmConfig<'info> {
     pub amm_config: Account<'info, AmmConfig>,
 }
 
-pub fn update_amm_config(ctx: Context<UpdateAmmConfig>, param: u8, value: u32) -> Result<()> {
-    let amm_config = &mut ctx.accounts.amm_config;
-    let match_param = Some(param);
-    match match_param {
-        Some(0) => update_trade_fee_rate(amm_config, value),
-        Some(1) => update_protocol_fee_rate(amm_config, value),
-        Some(2) => update_fund_fee_rate(amm_config, value),
-        Some(3) => {
-            let new_owner = *ctx.remaining_accounts.iter().next().unwrap().key;
-            set_new_owner(amm_config, new_owner);
-        }
-        Some(4) => {
-            let new_fund_owner = *ctx.remaining_accounts.iter().next().unwrap().key;
-            set_new_fund_owner(amm_config, new_fund_owner);
-        }
-        _ => return err!(ErrorCode::InvalidUpdateConfigFlag),
+pub fn update_amm_config(_ctx: Context<UpdateAmmConfig>, param: u8, _value: u32) -> Result<()> {
+    match param {
+        // the fee rates and the volatility periods are changed by schedule_admin_change and execute_admin_change
+        0 | 1 | 2 | 5 | 6 | 7 | 8 | 9 => err!(ErrorCode::AdminChangeNotScheduled),
+        // the owners are changed by propose_amm_config_owner and accept_amm_config_owner
+        3 | 4 => err!(ErrorCode::OwnerChangeNotAccepted),
+        _ => err!(ErrorCode::InvalidUpdateConfigFlag),
     }
+}
+
+/// Apply the update to the config and emit the config before and after the change
+pub(crate) fn apply_amm_config_update(
+    amm_config: &mut Account<AmmConfig>,
+    update: AmmConfigUpdate,
+) -> Result<()> {
+    let before = amm_config.values();
+    amm_config.apply_update(update)?;
+    let after = amm_config.values();
+    #[cfg(feature = "enable-log")]
+    msg!("amm_config:{}, update:{:?}", amm_config.key(), update);
 
+    emit!(AmmConfigUpdateEvent {
+        amm_config: amm_config.key(),
+        index: amm_config.index,
+        update,
+        before,
+        after,
+    });
+    if let AmmConfigUpdate::Status(status) = update {
+        emit!(AmmConfigStatusChangeEvent {
+            amm_config: amm_config.key(),
+            disabled_bits: status & !before.status,
+            enabled_bits: before.status & !status,
+            status,
+        });
+    }
     emit!(ConfigChangeEvent {
         index: amm_config.index,
         owner: amm_config.owner,
@@ -43,40 +61,3 @@ pub fn update_amm_config(ctx: Context<UpdateAmmConfig>, param: u8, value: u32) -
 
     Ok(())
 }
-
-fn update_protocol_fee_rate(amm_config: &mut Account<AmmConfig>, protocol_fee_rate: u32) {
-    assert!(protocol_fee_rate <= FEE_RATE_DENOMINATOR_VALUE);
-    assert!(protocol_fee_rate + amm_config.fund_fee_rate <= FEE_RATE_DENOMINATOR_VALUE);
-    amm_config.protocol_fee_rate = protocol_fee_rate;
-}
-
-fn update_trade_fee_rate(amm_config: &mut Account<AmmConfig>, trade_fee_rate: u32) {
-    assert!(trade_fee_rate < FEE_RATE_DENOMINATOR_VALUE);
-    amm_config.trade_fee_rate = trade_fee_rate;
-}
-
-fn update_fund_fee_rate(amm_config: &mut Account<AmmConfig>, fund_fee_rate: u32) {
-    assert!(fund_fee_rate <= FEE_RATE_DENOMINATOR_VALUE);
-    assert!(fund_fee_rate + amm_config.protocol_fee_rate <= FEE_RATE_DENOMINATOR_VALUE);
-    amm_config.fund_fee_rate = fund_fee_rate;
-}
-
-fn set_new_owner(amm_config: &mut Account<AmmConfig>, new_owner: Pubkey) {
-    #[cfg(feature = "enable-log")]
-    msg!(
-        "amm_config, old_owner:{}, new_owner:{}",
-        amm_config.owner.to_string(),
-        new_owner.key().to_string()
-    );
-    amm_config.owner = new_owner;
-}
-
-fn set_new_fund_owner(amm_config: &mut Account<AmmConfig>, new_fund_owner: Pubkey) {
-    #[cfg(feature = "enable-log")]
-    msg!(
-        "amm_config, old_fund_owner:{}, new_fund_owner:{}",
-        amm_config.fund_owner.to_string(),
-        new_fund_owner.key().to_string()
-    );
-    amm_config.fund_owner = new_fund_owner;
-}
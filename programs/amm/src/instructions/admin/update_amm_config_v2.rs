@@ -0,0 +1,27 @@
+use super::apply_amm_config_update;
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdateAmmConfigV2<'info> {
+    /// Only admin can update the amm config
+    #[account(address = crate::admin::id() @ ErrorCode::NotApproved)]
+    pub owner: Signer<'info>,
+
+    /// Amm config account to be changed
+    #[account(mut)]
+    pub amm_config: Account<'info, AmmConfig>,
+}
+
+pub fn update_amm_config_v2(
+    ctx: Context<UpdateAmmConfigV2>,
+    update: AmmConfigUpdate,
+) -> Result<()> {
+    require!(!update.is_owner_change(), ErrorCode::OwnerChangeNotAccepted);
+    require!(
+        !update.is_fee_rate_change() && !ctx.accounts.amm_config.is_status_enabling(update),
+        ErrorCode::AdminChangeNotScheduled
+    );
+    apply_amm_config_update(&mut ctx.accounts.amm_config, update)
+}
@@ -0,0 +1,36 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdateMintRegistry<'info> {
+    /// Only admin can update the mint registry
+    #[account(
+        address = crate::admin::id() @ ErrorCode::NotApproved
+    )]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            MINT_REGISTRY_SEED.as_bytes(),
+        ],
+        bump,
+    )]
+    pub mint_registry: AccountLoader<'info, MintRegistryState>,
+}
+
+pub fn update_mint_registry(
+    ctx: Context<UpdateMintRegistry>,
+    update: MintRegistryUpdate,
+) -> Result<()> {
+    let mut mint_registry = ctx.accounts.mint_registry.load_mut()?;
+    mint_registry.apply_update(update)?;
+
+    emit!(MintRegistryChangeEvent {
+        update,
+        allowed_extension_flag: mint_registry.allowed_extension_flag,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdateObservationUpdateDuration<'info> {
+    #[account(
+        address = crate::admin::id()
+    )]
+    pub authority: Signer<'info>,
+
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// The program account for the oracle observation
+    #[account(mut, address = pool_state.load()?.observation_key)]
+    pub observation_state: AccountLoader<'info, ObservationState>,
+}
+
+pub fn update_observation_update_duration(
+    ctx: Context<UpdateObservationUpdateDuration>,
+    observation_update_duration: u32,
+) -> Result<()> {
+    require!(
+        observation_update_duration > 0
+            && observation_update_duration <= OBSERVATION_UPDATE_DURATION_MAX,
+        ErrorCode::InvalidObservationUpdateDuration
+    );
+    let mut observation_state = ctx.accounts.observation_state.load_mut()?;
+    let observation_update_duration_old = observation_state.update_duration();
+    observation_state.observation_update_duration = observation_update_duration;
+
+    emit!(ObservationUpdateDurationChangeEvent {
+        pool_state: ctx.accounts.pool_state.key(),
+        observation_update_duration_old,
+        observation_update_duration,
+    });
+    Ok(())
+}
@@ -1,11 +1,14 @@
 use crate::error::ErrorCode;
 use crate::states::*;
 use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
 
 #[derive(Accounts)]
 pub struct UpdateOperationAccount<'info> {
-    /// Address to be set as operation account owner.
+    /// Only the admin can update the operation account.
+    /// It pays to resize the operation accounts created before the operator roles existed.
     #[account(
+        mut,
         address = crate::admin::id() @ ErrorCode::NotApproved
     )]
     pub owner: Signer<'info>,
@@ -25,17 +28,71 @@ pub struct UpdateOperationAccount<'info> {
 
 pub fn update_operation_account(
     ctx: Context<UpdateOperationAccount>,
-    param: u8,
-    keys: Vec<Pubkey>,
+    update: OperationAccountUpdate,
 ) -> Result<()> {
+    resize_operation_account(&ctx)?;
+
+    let owner = ctx.accounts.owner.key();
     let mut operation_state = ctx.accounts.operation_state.load_mut()?;
-    let match_param = Some(param);
-    match match_param {
-        Some(0) => operation_state.update_operation_owner(keys),
-        Some(1) => operation_state.remove_operation_owner(keys),
-        Some(2) => operation_state.update_whitelist_mint(keys),
-        Some(3) => operation_state.remove_whitelist_mint(keys),
-        _ => return err!(ErrorCode::InvalidUpdateConfigFlag),
+    match update {
+        OperationAccountUpdate::SetOperatorRoles { operator, roles } => {
+            require_keys_eq!(owner, crate::admin::id(), ErrorCode::NotApproved);
+            let roles_before = operation_state.set_operator_roles(operator, roles)?;
+            emit!(OperatorRoleChangeEvent {
+                operator,
+                roles_before,
+                roles_after: roles,
+            });
+        }
+        OperationAccountUpdate::AddWhitelistMints(keys) => {
+            require!(
+                owner == crate::admin::id()
+                    || operation_state
+                        .validate_operator_role(owner, OperatorRoleBitIndex::WhitelistManager),
+                ErrorCode::NotApproved
+            );
+            operation_state.update_whitelist_mint(keys);
+        }
+        OperationAccountUpdate::RemoveWhitelistMints(keys) => {
+            require!(
+                owner == crate::admin::id()
+                    || operation_state
+                        .validate_operator_role(owner, OperatorRoleBitIndex::WhitelistManager),
+                ErrorCode::NotApproved
+            );
+            operation_state.remove_whitelist_mint(keys);
+        }
+    }
+    Ok(())
+}
+
+/// Resize the operation account created before the operator roles existed,
+/// its operators keep the reward admin role they had
+fn resize_operation_account(ctx: &Context<UpdateOperationAccount>) -> Result<()> {
+    let operation_info = ctx.accounts.operation_state.to_account_info();
+    if operation_info.data_len() >= OperationState::LEN {
+        return Ok(());
     }
+    let lamports_required = Rent::get()?
+        .minimum_balance(OperationState::LEN)
+        .saturating_sub(operation_info.lamports());
+    if lamports_required > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.owner.to_account_info(),
+                    to: operation_info.clone(),
+                },
+            ),
+            lamports_required,
+        )?;
+    }
+    operation_info.realloc(OperationState::LEN, true)?;
+
+    ctx.accounts
+        .operation_state
+        .load_mut()?
+        .grant_legacy_roles_to_operators();
     Ok(())
 }
@@ -0,0 +1,44 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdatePoolFeeRates<'info> {
+    #[account(
+        address = crate::admin::id() @ ErrorCode::NotApproved
+    )]
+    pub authority: Signer<'info>,
+
+    /// The amm config of the pool, the fee rates which are not overridden are read from it
+    #[account(
+        address = pool_state.load()?.amm_config
+    )]
+    pub amm_config: Account<'info, AmmConfig>,
+
+    #[account(mut)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+}
+
+pub fn update_pool_fee_rates(
+    ctx: Context<UpdatePoolFeeRates>,
+    trade_fee_rate: Option<u32>,
+    protocol_fee_rate: Option<u32>,
+    fund_fee_rate: Option<u32>,
+) -> Result<()> {
+    let mut pool_state = ctx.accounts.pool_state.load_mut()?;
+    let effective_amm_config = pool_state.update_fee_overrides(
+        &ctx.accounts.amm_config,
+        trade_fee_rate,
+        protocol_fee_rate,
+        fund_fee_rate,
+    )?;
+
+    emit!(PoolFeeChangeEvent {
+        pool_state: ctx.accounts.pool_state.key(),
+        trade_fee_rate: effective_amm_config.trade_fee_rate,
+        protocol_fee_rate: effective_amm_config.protocol_fee_rate,
+        fund_fee_rate: effective_amm_config.fund_fee_rate,
+    });
+
+    Ok(())
+}
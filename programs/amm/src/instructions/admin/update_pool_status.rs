@@ -1,20 +1,60 @@
+use crate::error::ErrorCode;
 use crate::states::*;
+use crate::util;
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
 pub struct UpdatePoolStatus<'info> {
-    #[account(
-        address = crate::admin::id()
-    )]
+    /// The admin, or the pause guardian which is an operator with the pool pauser role
+    /// and passes the operation account in the remaining accounts
     pub authority: Signer<'info>,
 
     #[account(mut)]
     pub pool_state: AccountLoader<'info, PoolState>,
 }
 
-pub fn update_pool_status(ctx: Context<UpdatePoolStatus>, status: u8) -> Result<()> {
-    require_gte!(255, status);
-    let mut pool_state = ctx.accounts.pool_state.load_mut()?;
-    pool_state.set_status(status);
+/// Only disabling status bits applies instantly, enabling them must be scheduled by the admin
+pub fn update_pool_status<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, UpdatePoolStatus<'info>>,
+    status: u8,
+) -> Result<()> {
+    check_pause_guardian(&ctx.accounts.authority, ctx.remaining_accounts)?;
+    let status_before = ctx.accounts.pool_state.load()?.status;
+    require_eq!(
+        status & status_before,
+        status_before,
+        ErrorCode::AdminChangeNotScheduled
+    );
+    disable_pool_status_bits(&ctx.accounts.pool_state, status)
+}
+
+pub(crate) fn check_pause_guardian<'info>(
+    authority: &Signer,
+    remaining_accounts: &'info [AccountInfo<'info>],
+) -> Result<()> {
+    require!(
+        authority.key() == crate::admin::id()
+            || util::operator_has_role(
+                remaining_accounts,
+                authority.key(),
+                OperatorRoleBitIndex::PoolPauser
+            )?,
+        ErrorCode::NotApproved
+    );
+    Ok(())
+}
+
+pub(crate) fn disable_pool_status_bits(
+    pool_state_loader: &AccountLoader<PoolState>,
+    disabled_bits: u8,
+) -> Result<()> {
+    let mut pool_state = pool_state_loader.load_mut()?;
+    let status_before = pool_state.status;
+    pool_state.set_status(status_before | disabled_bits);
+    emit!(PoolStatusChangeEvent::new(
+        pool_state_loader.key(),
+        status_before,
+        pool_state.status
+    ));
     Ok(())
 }
@@ -48,6 +48,10 @@ pub struct ClosePosition<'info> {
 pub fn close_position<'a, 'b, 'c, 'info>(
     ctx: Context<'a, 'b, 'c, 'info, ClosePosition<'info>>,
 ) -> Result<()> {
+    require!(
+        !ctx.accounts.personal_position.is_locked(),
+        ErrorCode::PositionLocked
+    );
     if ctx.accounts.personal_position.liquidity != 0
         || ctx.accounts.personal_position.token_fees_owed_0 != 0
         || ctx.accounts.personal_position.token_fees_owed_1 != 0
@@ -71,6 +75,16 @@ pub fn close_position<'a, 'b, 'c, 'info>(
             return err!(ErrorCode::ClosePositionErr);
         }
     }
+    for i in 0..ctx.accounts.personal_position.extension_reward_infos.len() {
+        if ctx.accounts.personal_position.extension_reward_infos[i].reward_amount_owed != 0 {
+            msg!(
+                "remaing extension reward index:{},amount:{}",
+                i,
+                ctx.accounts.personal_position.extension_reward_infos[i].reward_amount_owed,
+            );
+            return err!(ErrorCode::ClosePositionErr);
+        }
+    }
 //token program
     let token_program = ctx.accounts.token_program.to_account_info();
     //nft mint
@@ -1,6 +1,6 @@
 use crate::error::ErrorCode;
 use crate::states::*;
-use crate::util::transfer_from_pool_vault_to_user;
+use crate::util::{load_reward_extension, transfer_from_pool_vault_to_user};
 use anchor_lang::prelude::*;
 use anchor_spl::{
     token::{self, Token},
@@ -40,16 +40,26 @@ pub struct CollectRemainingRewards<'info> {
     pub memo_program: UncheckedAccount<'info>,
 }
 
-pub fn collect_remaining_rewards(
-    ctx: Context<CollectRemainingRewards>,
+pub fn collect_remaining_rewards<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, CollectRemainingRewards<'info>>,
     reward_index: u8,
 ) -> Result<()> {
     // invoke_memo_instruction(
     //     COLLECT_REMAINING_MEMO_MSG,
     //     ctx.accounts.memo_program.to_account_info(),
     // )?;
+    // the indexes beyond the pool reward infos are the rewards of the reward extension
+    let reward_extension = if (reward_index as usize) < REWARD_NUM {
+        None
+    } else {
+        match load_reward_extension(&*ctx.accounts.pool_state.load()?, ctx.remaining_accounts)? {
+            Some(reward_extension) => Some(reward_extension),
+            None => return err!(ErrorCode::MissingRewardExtensionAccount),
+        }
+    };
     let amount_remaining = get_remaining_reward_amount(
         &ctx.accounts.pool_state,
+        reward_extension.as_ref(),
         &ctx.accounts.reward_token_vault,
         &ctx.accounts.reward_funder.key(),
         reward_index,
@@ -70,6 +80,7 @@ pub fn collect_remaining_rewards(
 
 fn get_remaining_reward_amount(
     pool_state_loader: &AccountLoader<PoolState>,
+    reward_extension: Option<&AccountLoader<PoolRewardExtension>>,
     reward_token_vault: &InterfaceAccount<TokenAccount>,
     reward_funder: &Pubkey,
     reward_index: u8,
@@ -78,7 +89,15 @@ fn get_remaining_reward_amount(
     let mut pool_state = pool_state_loader.load_mut()?;
     pool_state.update_reward_infos(current_timestamp)?;
 
-    let reward_info = pool_state.reward_infos[reward_index as usize];
+    let reward_info = match reward_extension {
+        Some(reward_extension) => {
+            let mut reward_extension = reward_extension.load_mut()?;
+            reward_extension
+                .update_reward_infos(pool_state.reward_liquidity(), current_timestamp)?;
+            reward_extension.reward_infos[reward_index as usize - REWARD_NUM]
+        }
+        None => pool_state.reward_infos[reward_index as usize],
+    };
     if !reward_info.initialized() {
         return err!(ErrorCode::UnInitializedRewardInfo);
     }
@@ -178,7 +178,11 @@ So your program supports either one, and the client can pass in whichever token
     // pub support_mint1_associated: Account<'info, SupportMintAssociated>,
 }
 
-pub fn create_pool(ctx: Context<CreatePool>, sqrt_price_x64: u128, open_time: u64) -> Result<()> {
+pub fn create_pool<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, CreatePool<'info>>,
+    sqrt_price_x64: u128,
+    open_time: u64,
+) -> Result<()> {
     let mint0_associated_is_initialized = util::support_mint_associated_is_initialized(
         &ctx.remaining_accounts,
         &ctx.accounts.token_mint_0,
@@ -187,16 +191,39 @@ pub fn create_pool(ctx: Context<CreatePool>, sqrt_price_x64: u128, open_time: u6
         &ctx.remaining_accounts,
         &ctx.accounts.token_mint_1,
     )?;
-    if !(util::is_supported_mint(&ctx.accounts.token_mint_0, mint0_associated_is_initialized)
-        .unwrap()
-        && util::is_supported_mint(&ctx.accounts.token_mint_1, mint1_associated_is_initialized)
-            .unwrap())
-    {
+    let mint_registry = if util::is_registry_checked_mint(
+        &ctx.accounts.token_mint_0,
+        mint0_associated_is_initialized,
+    ) || util::is_registry_checked_mint(
+        &ctx.accounts.token_mint_1,
+        mint1_associated_is_initialized,
+    ) {
+        util::load_mint_registry(ctx.remaining_accounts)?
+    } else {
+        None
+    };
+    let mint_registry = mint_registry
+        .as_ref()
+        .map(|mint_registry| mint_registry.load())
+        .transpose()?;
+    if !(util::is_supported_mint(
+        &ctx.accounts.token_mint_0,
+        mint0_associated_is_initialized,
+        mint_registry.as_deref(),
+    )? && util::is_supported_mint(
+        &ctx.accounts.token_mint_1,
+        mint1_associated_is_initialized,
+        mint_registry.as_deref(),
+    )?) {
         return err!(ErrorCode::NotSupportMint);
     }
     let block_timestamp = solana_program::clock::Clock::get()?.unix_timestamp as u64;
-    /// this checks that the block_timestamp should be greater than open_time
-    require_gt!(block_timestamp, open_time);
+    // the pool can open in the future, but not further than the launch delay
+    require_gte!(
+        block_timestamp + MAX_LAUNCH_DELAY,
+        open_time,
+        ErrorCode::InvalidOpenTime
+    );
     let pool_id = ctx.accounts.pool_state.key();
     //Here we load the pool_state as THE MUT REF, it should be called once when account is being initialised
     //When initializing a zero-copy account for the first time, use load_init to get a mutable reference to the account data. The load_init method also sets the account discriminator.
@@ -225,7 +252,7 @@ pub fn create_pool(ctx: Context<CreatePool>, sqrt_price_x64: u128, open_time: u6
     pool_state.initialize(
         bump,
         sqrt_price_x64,
-        0,
+        open_time,
         tick,
         ctx.accounts.pool_creator.key(),
         ctx.accounts.token_vault_0.key(),
@@ -253,6 +280,9 @@ pub fn create_pool(ctx: Context<CreatePool>, sqrt_price_x64: u128, open_time: u6
         tick,
         token_vault_0: ctx.accounts.token_vault_0.key(),
         token_vault_1: ctx.accounts.token_vault_1.key(),
+        trade_fee_rate: ctx.accounts.amm_config.trade_fee_rate,
+        protocol_fee_rate: ctx.accounts.amm_config.protocol_fee_rate,
+        fund_fee_rate: ctx.accounts.amm_config.fund_fee_rate,
     });
     Ok(())
 }
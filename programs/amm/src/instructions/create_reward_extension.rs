@@ -0,0 +1,60 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use crate::util::operator_has_role;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CreateRewardExtension<'info> {
+    /// The admin or an operator with the reward admin role, pays to create the reward extension account.
+    /// The operator passes the operation account in the remaining accounts
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// The pool to add more rewards to
+    #[account(mut)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// Initialize the reward extension account to store the rewards beyond the pool reward infos
+    #[account(
+        init,
+        seeds = [
+            POOL_REWARD_EXTENSION_SEED.as_bytes(),
+            pool_state.key().as_ref(),
+        ],
+        bump,
+        payer = owner,
+        space = PoolRewardExtension::LEN
+    )]
+    pub reward_extension: AccountLoader<'info, PoolRewardExtension>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_reward_extension<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, CreateRewardExtension<'info>>,
+) -> Result<()> {
+    // every client of the pool must pass the reward extension once it exists,
+    // so the permissionless pool creator can't create it
+    let owner = ctx.accounts.owner.key();
+    require!(
+        owner == crate::admin::id()
+            || operator_has_role(
+                ctx.remaining_accounts,
+                owner,
+                OperatorRoleBitIndex::RewardAdmin
+            )?,
+        ErrorCode::NotApproved
+    );
+    let mut pool_state = ctx.accounts.pool_state.load_mut()?;
+    let mut reward_extension = ctx.accounts.reward_extension.load_init()?;
+    reward_extension.initialize(ctx.accounts.pool_state.key(), pool_state.owner);
+    // from now on every instruction changing the liquidity of a position must pass the reward extension
+    pool_state.reward_extension = 1;
+    pool_state.reward_extension_bump = ctx.bumps.reward_extension;
+
+    emit!(CreateRewardExtensionEvent {
+        pool_state: ctx.accounts.pool_state.key(),
+        reward_extension: ctx.accounts.reward_extension.key(),
+    });
+    Ok(())
+}
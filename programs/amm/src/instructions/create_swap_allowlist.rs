@@ -0,0 +1,60 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CreateSwapAllowlist<'info> {
+    /// The pool creator or the admin, pays to create the swap allowlist account
+    #[account(
+        mut,
+        constraint = (owner.key() == pool_state.load()?.owner || owner.key() == crate::admin::id()) @ ErrorCode::NotApproved
+    )]
+    pub owner: Signer<'info>,
+
+    /// The pool to start with a launch phase
+    #[account(mut)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// Initialize the swap allowlist account to store the addresses allowed to swap during the launch phase
+    #[account(
+        init,
+        seeds = [
+            SWAP_ALLOWLIST_SEED.as_bytes(),
+            pool_state.key().as_ref(),
+        ],
+        bump,
+        payer = owner,
+        space = SwapAllowlistState::LEN
+    )]
+    pub swap_allowlist: AccountLoader<'info, SwapAllowlistState>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_swap_allowlist(
+    ctx: Context<CreateSwapAllowlist>,
+    launch_duration: u64,
+    keys: Vec<Pubkey>,
+) -> Result<()> {
+    require!(
+        launch_duration > 0 && launch_duration <= MAX_LAUNCH_DURATION,
+        ErrorCode::InvalidLaunchDuration
+    );
+    let block_timestamp = Clock::get()?.unix_timestamp as u64;
+    let mut pool_state = ctx.accounts.pool_state.load_mut()?;
+    // the launch phase can't be added to a pool which is already open for swaps
+    require_gt!(
+        pool_state.open_time,
+        block_timestamp,
+        ErrorCode::PoolAlreadyOpen
+    );
+    pool_state.launch_end_time = pool_state
+        .open_time
+        .checked_add(launch_duration)
+        .ok_or(ErrorCode::CalculateOverflow)?;
+    pool_state.swap_allowlist_bump = ctx.bumps.swap_allowlist;
+
+    let mut swap_allowlist = ctx.accounts.swap_allowlist.load_init()?;
+    swap_allowlist.initialize(ctx.bumps.swap_allowlist, ctx.accounts.pool_state.key());
+    swap_allowlist.update_swappers(keys)
+}
@@ -92,6 +92,7 @@ pub struct DecreaseLiquidity<'info> {
     //     bump
     // )]
     // pub tick_array_bitmap: AccountLoader<'info, TickArrayBitmapExtension>,
+    // the amm config of the pool, its status is inherited by the pool when it is passed
 }
 
 pub fn decrease_liquidity_v1<'a, 'b, 'c: 'info, 'info>(
@@ -147,20 +148,20 @@ pub fn decrease_liquidity<'a, 'b, 'c: 'info, 'info>(
     //     invoke_memo_instruction(DECREASE_MEMO_MSG, memp_program)?;
     // }
     assert!(liquidity <= personal_position.liquidity);
+    // the fees and rewards of a locked position can still be collected
+    if liquidity > 0 {
+        require!(!personal_position.is_locked(), ErrorCode::PositionLocked);
+    }
     let liquidity_before;
     let pool_sqrt_price_x64;
     let pool_tick_current;
     let mut tickarray_bitmap_extension = None;
+    let mut reward_extension_info = None;
+    let mut amm_config_info = None;
 
     let remaining_collect_accounts = &mut Vec::new();
     {
         let pool_state = pool_state_loader.load()?;
-        if !pool_state.get_status_by_bit(PoolStatusBitIndex::DecreaseLiquidity)
-            && !pool_state.get_status_by_bit(PoolStatusBitIndex::CollectFee)
-            && !pool_state.get_status_by_bit(PoolStatusBitIndex::CollectReward)
-        {
-            return err!(ErrorCode::NotApproved);
-        }
         liquidity_before = pool_state.liquidity;
         pool_sqrt_price_x64 = pool_state.sqrt_price_x64;
         pool_tick_current = pool_state.tick_current;
@@ -170,6 +171,14 @@ pub fn decrease_liquidity<'a, 'b, 'c: 'info, 'info>(
             tick_array_upper_loader.load()?.start_tick_index,
         ]);
 
+        let reward_extension_key = if pool_state.reward_extension != 0 {
+            Some(PoolRewardExtension::key(
+                pool_state.key(),
+                pool_state.reward_extension_bump,
+            )?)
+        } else {
+            None
+        };
         for account_info in remaining_accounts.into_iter() {
             if account_info
                 .key()
@@ -178,6 +187,14 @@ pub fn decrease_liquidity<'a, 'b, 'c: 'info, 'info>(
                 tickarray_bitmap_extension = Some(account_info);
                 continue;
             }
+            if Some(account_info.key()) == reward_extension_key {
+                reward_extension_info = Some(account_info);
+                continue;
+            }
+            if account_info.key() == pool_state.amm_config {
+                amm_config_info = Some(account_info);
+                continue;
+            }
             remaining_collect_accounts.push(account_info);
         }
         if use_tickarray_bitmap_extension {
@@ -186,16 +203,42 @@ pub fn decrease_liquidity<'a, 'b, 'c: 'info, 'info>(
                 ErrorCode::MissingTickArrayBitmapExtensionAccount
             );
         }
+        if pool_state.reward_extension != 0 {
+            require!(
+                reward_extension_info.is_some(),
+                ErrorCode::MissingRewardExtensionAccount
+            );
+        }
+    }
+    let reward_extension = reward_extension_info
+        .map(AccountLoader::<PoolRewardExtension>::try_from)
+        .transpose()?;
+    let amm_config = amm_config_info
+        .map(Account::<AmmConfig>::try_from)
+        .transpose()?;
+    let amm_config = amm_config.as_deref();
+    {
+        let pool_state = pool_state_loader.load()?;
+        if !pool_state
+            .get_status_by_bit_with_config(PoolStatusBitIndex::DecreaseLiquidity, amm_config)
+            && !pool_state.get_status_by_bit_with_config(PoolStatusBitIndex::CollectFee, amm_config)
+            && !pool_state
+                .get_status_by_bit_with_config(PoolStatusBitIndex::CollectReward, amm_config)
+        {
+            return err!(ErrorCode::NotApproved);
+        }
     }
 
     let (decrease_amount_0, latest_fees_owed_0, decrease_amount_1, latest_fees_owed_1) =
         decrease_liquidity_and_update_position(
             pool_state_loader,
+            amm_config,
             protocol_position,
             personal_position,
             tick_array_lower_loader,
             tick_array_upper_loader,
             tickarray_bitmap_extension,
+            reward_extension.as_ref(),
             liquidity,
         )?;
 
@@ -272,8 +315,10 @@ pub fn decrease_liquidity<'a, 'b, 'c: 'info, 'info>(
 
     check_unclaimed_fees_and_vault(pool_state_loader, token_vault_0, token_vault_1)?;
 
-    let reward_amounts = collect_rewards(
+    let (reward_amounts, extension_reward_amounts) = collect_rewards(
         pool_state_loader,
+        reward_extension.as_ref(),
+        amm_config,
         remaining_collect_accounts.as_slice(),
         token_program,
         token_2022_program_opt.clone(),
@@ -294,6 +339,7 @@ pub fn decrease_liquidity<'a, 'b, 'c: 'info, 'info>(
         reward_amounts,
         transfer_fee_0: transfer_fee_0,
         transfer_fee_1: transfer_fee_1,
+        extension_reward_amounts,
     });
 
     Ok(())
@@ -301,19 +347,26 @@ pub fn decrease_liquidity<'a, 'b, 'c: 'info, 'info>(
 
 pub fn decrease_liquidity_and_update_position<'a, 'b, 'c: 'info, 'info>(
     pool_state_loader: &AccountLoader<'info, PoolState>,
+    amm_config: Option<&AmmConfig>,
     protocol_position: &mut Box<Account<'info, ProtocolPositionState>>,
     personal_position: &mut Box<Account<'info, PersonalPositionState>>,
     tick_array_lower: &AccountLoader<'info, TickArrayState>,
     tick_array_upper: &AccountLoader<'info, TickArrayState>,
     tick_array_bitmap_extension: Option<&'c AccountInfo<'info>>,
+    reward_extension: Option<&AccountLoader<'info, PoolRewardExtension>>,
     liquidity: u128,
 ) -> Result<(u64, u64, u64, u64)> {
     let mut pool_state = pool_state_loader.load_mut()?;
     let mut decrease_amount_0 = 0;
     let mut decrease_amount_1 = 0;
-    if pool_state.get_status_by_bit(PoolStatusBitIndex::DecreaseLiquidity) {
+    if pool_state.get_status_by_bit_with_config(PoolStatusBitIndex::DecreaseLiquidity, amm_config) {
+        let mut reward_extension = match reward_extension {
+            Some(loader) => Some(loader.load_mut()?),
+            None => None,
+        };
         (decrease_amount_0, decrease_amount_1) = burn_liquidity(
             &mut pool_state,
+            reward_extension.as_deref_mut(),
             tick_array_lower,
             tick_array_upper,
             protocol_position,
@@ -341,13 +394,19 @@ pub fn decrease_liquidity_and_update_position<'a, 'b, 'c: 'info, 'info>(
             protocol_position.fee_growth_inside_1_last_x64;
 
         // update rewards, must update before decrease liquidity
-        personal_position.update_rewards(protocol_position.reward_growth_inside, true)?;
+        personal_position.update_rewards(
+            protocol_position.reward_growth_inside,
+            protocol_position.reward_growth_inside_extension,
+            pool_state.reward_generations,
+            PoolRewardExtension::generations_of(reward_extension.as_deref()),
+            true,
+        )?;
         personal_position.liquidity = personal_position.liquidity.checked_sub(liquidity).unwrap();
     }
 
     let mut latest_fees_owed_0 = 0;
     let mut latest_fees_owed_1 = 0;
-    if pool_state.get_status_by_bit(PoolStatusBitIndex::CollectFee) {
+    if pool_state.get_status_by_bit_with_config(PoolStatusBitIndex::CollectFee, amm_config) {
         latest_fees_owed_0 = personal_position.token_fees_owed_0;
         latest_fees_owed_1 = personal_position.token_fees_owed_1;
 
@@ -383,6 +442,7 @@ pub fn decrease_liquidity_and_update_position<'a, 'b, 'c: 'info, 'info>(
 
 pub fn burn_liquidity<'c: 'info, 'info>(
     pool_state: &mut RefMut<PoolState>,
+    reward_extension: Option<&mut PoolRewardExtension>,
     tick_array_lower_loader: &AccountLoader<'info, TickArrayState>,
     tick_array_upper_loader: &AccountLoader<'info, TickArrayState>,
     protocol_position: &mut ProtocolPositionState,
@@ -403,6 +463,7 @@ pub fn burn_liquidity<'c: 'info, 'info>(
     let (amount_0, amount_1, flip_tick_lower, flip_tick_upper) = modify_position(
         -i128::try_from(liquidity).unwrap(),
         pool_state,
+        reward_extension,
         protocol_position,
         &mut tick_lower_state,
         &mut tick_upper_state,
@@ -456,32 +517,35 @@ pub fn burn_liquidity<'c: 'info, 'info>(
 
 pub fn collect_rewards<'a, 'b, 'c, 'info>(
     pool_state_loader: &AccountLoader<'info, PoolState>,
+    reward_extension: Option<&AccountLoader<'info, PoolRewardExtension>>,
+    amm_config: Option<&AmmConfig>,
     remaining_accounts: &[&'info AccountInfo<'info>],
     token_program: &'b Program<'info, Token>,
     token_program_2022: Option<AccountInfo<'info>>,
     personal_position_state: &mut PersonalPositionState,
     need_reward_mint: bool,
-) -> Result<[u64; REWARD_NUM]> {
+) -> Result<([u64; REWARD_NUM], [u64; EXTENSION_REWARD_NUM])> {
     let mut reward_amounts: [u64; REWARD_NUM] = [0, 0, 0];
+    let mut extension_reward_amounts = [0u64; EXTENSION_REWARD_NUM];
     if !pool_state_loader
         .load()?
-        .get_status_by_bit(PoolStatusBitIndex::CollectReward)
+        .get_status_by_bit_with_config(PoolStatusBitIndex::CollectReward, amm_config)
     {
-        return Ok(reward_amounts);
+        return Ok((reward_amounts, extension_reward_amounts));
     }
     let mut reward_group_account_num = 3;
     if !need_reward_mint {
         reward_group_account_num = reward_group_account_num - 1
     }
-    check_required_accounts_length(
-        pool_state_loader,
-        remaining_accounts,
-        reward_group_account_num,
-    )?;
+    // the reward groups of the pool rewards are followed by the ones of the reward extension,
+    // a retired reward slot has no group
+    let reward_indexes = get_initialized_reward_indexes(pool_state_loader, reward_extension)?;
+    if remaining_accounts.len() != reward_indexes.len() * reward_group_account_num {
+        return err!(ErrorCode::InvalidRewardInputAccountNumber);
+    }
 
-    let remaining_accounts_len = remaining_accounts.len();
     let mut remaining_accounts = remaining_accounts.iter();
-    for i in 0..remaining_accounts_len / reward_group_account_num {
+    for i in reward_indexes {
         let reward_token_vault = InterfaceAccount::<token_interface::TokenAccount>::try_from(
             remaining_accounts.next().unwrap(),
         )?;
@@ -496,18 +560,37 @@ pub fn collect_rewards<'a, 'b, 'c, 'info>(
             )?));
         }
         require_keys_eq!(reward_token_vault.mint, recipient_token_account.mint);
-        require_keys_eq!(
-            reward_token_vault.key(),
-            pool_state_loader.load_mut()?.reward_infos[i].token_vault
-        );
 
-        let reward_amount_owed = personal_position_state.reward_infos[i].reward_amount_owed;
+        let reward_amount_owed = if i < REWARD_NUM {
+            require_keys_eq!(
+                reward_token_vault.key(),
+                pool_state_loader.load_mut()?.reward_infos[i].token_vault
+            );
+            personal_position_state.reward_infos[i].reward_amount_owed
+        } else {
+            let reward_extension = match reward_extension {
+                Some(reward_extension) => reward_extension,
+                None => return err!(ErrorCode::MissingRewardExtensionAccount),
+            };
+            require_keys_eq!(
+                reward_token_vault.key(),
+                reward_extension.load()?.reward_infos[i - REWARD_NUM].token_vault
+            );
+            personal_position_state.extension_reward_infos[i - REWARD_NUM].reward_amount_owed
+        };
         if reward_amount_owed == 0 {
             continue;
         }
-        pool_state_loader
-            .load()?
-            .check_unclaimed_reward(i, reward_amount_owed)?;
+        if i < REWARD_NUM {
+            pool_state_loader
+                .load()?
+                .check_unclaimed_reward(i, reward_amount_owed)?;
+        } else {
+            reward_extension
+                .unwrap()
+                .load()?
+                .check_unclaimed_reward(i - REWARD_NUM, reward_amount_owed)?;
+        }
 
         let transfer_amount = if reward_amount_owed > reward_token_vault.amount {
             reward_token_vault.amount
@@ -522,11 +605,20 @@ pub fn collect_rewards<'a, 'b, 'c, 'info>(
                 transfer_amount,
                 reward_amount_owed
             );
-            personal_position_state.reward_infos[i].reward_amount_owed =
-                reward_amount_owed.checked_sub(transfer_amount).unwrap();
-            pool_state_loader
-                .load_mut()?
-                .add_reward_clamed(i, transfer_amount)?;
+            if i < REWARD_NUM {
+                personal_position_state.reward_infos[i].reward_amount_owed =
+                    reward_amount_owed.checked_sub(transfer_amount).unwrap();
+                pool_state_loader
+                    .load_mut()?
+                    .add_reward_clamed(i, transfer_amount)?;
+            } else {
+                personal_position_state.extension_reward_infos[i - REWARD_NUM].reward_amount_owed =
+                    reward_amount_owed.checked_sub(transfer_amount).unwrap();
+                reward_extension
+                    .unwrap()
+                    .load_mut()?
+                    .add_reward_clamed(i - REWARD_NUM, transfer_amount)?;
+            }
 
             transfer_from_pool_vault_to_user(
                 &pool_state_loader,
@@ -538,29 +630,37 @@ pub fn collect_rewards<'a, 'b, 'c, 'info>(
                 transfer_amount,
             )?;
         }
-        reward_amounts[i] = transfer_amount
+        if i < REWARD_NUM {
+            reward_amounts[i] = transfer_amount
+        } else {
+            extension_reward_amounts[i - REWARD_NUM] = transfer_amount
+        }
     }
 
-    Ok(reward_amounts)
+    Ok((reward_amounts, extension_reward_amounts))
 }
 
-fn check_required_accounts_length(
+/// Returns the indexes of the initialized rewards, the rewards of the reward extension are indexed after the pool ones
+fn get_initialized_reward_indexes(
     pool_state_loader: &AccountLoader<PoolState>,
-    remaining_accounts: &[&AccountInfo],
-    reward_group_account_num: usize,
-) -> Result<()> {
-    let pool_state = pool_state_loader.load()?;
-    let mut valid_reward_count = 0;
-    for item in pool_state.reward_infos {
+    reward_extension: Option<&AccountLoader<PoolRewardExtension>>,
+) -> Result<Vec<usize>> {
+    let reward_infos = pool_state_loader.load()?.reward_infos;
+    let mut reward_indexes = Vec::new();
+    for (i, item) in reward_infos.iter().enumerate() {
         if item.initialized() {
-            valid_reward_count = valid_reward_count + 1;
+            reward_indexes.push(i);
         }
     }
-    let remaining_accounts_len = remaining_accounts.len();
-    if remaining_accounts_len != valid_reward_count * reward_group_account_num {
-        return err!(ErrorCode::InvalidRewardInputAccountNumber);
+    if let Some(reward_extension) = reward_extension {
+        let extension_reward_infos = reward_extension.load()?.reward_infos;
+        for (i, item) in extension_reward_infos.iter().enumerate() {
+            if item.initialized() {
+                reward_indexes.push(REWARD_NUM + i);
+            }
+        }
     }
-    Ok(())
+    Ok(reward_indexes)
 }
 
 pub fn check_unclaimed_fees_and_vault(
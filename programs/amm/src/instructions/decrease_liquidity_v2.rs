@@ -106,6 +106,8 @@ pub struct DecreaseLiquidityV2<'info> {
     //     bump
     // )]
     // pub tick_array_bitmap: AccountLoader<'info, TickArrayBitmapExtension>,
+    // the amm config of the pool, its status is inherited by the pool when it is passed,
+    // required by the v3 instruction
 }
 
 pub fn decrease_liquidity_v2<'a, 'b, 'c: 'info, 'info>(
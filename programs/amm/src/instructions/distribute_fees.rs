@@ -0,0 +1,178 @@
+use crate::decrease_liquidity::check_unclaimed_fees_and_vault;
+use crate::error::ErrorCode;
+use crate::states::*;
+use crate::util::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token::Token;
+use anchor_spl::token_interface::{Mint, Token2022, TokenAccount};
+
+#[derive(Accounts)]
+pub struct DistributeFees<'info> {
+    /// Anyone can distribute the fees to the recipients of the fee distribution
+    pub payer: Signer<'info>,
+
+    /// Pool state stores accumulated protocol and fund fee amount
+    #[account(mut)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// The fee distribution of the amm config of the pool
+    #[account(
+        seeds = [
+            FEE_DISTRIBUTION_SEED.as_bytes(),
+            pool_state.load()?.amm_config.as_ref(),
+        ],
+        bump = fee_distribution.bump,
+    )]
+    pub fee_distribution: Account<'info, FeeDistributionState>,
+
+    /// The address that holds pool tokens for token_0
+    #[account(
+        mut,
+        constraint = token_vault_0.key() == pool_state.load()?.token_vault_0
+    )]
+    pub token_vault_0: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The address that holds pool tokens for token_1
+    #[account(
+        mut,
+        constraint = token_vault_1.key() == pool_state.load()?.token_vault_1
+    )]
+    pub token_vault_1: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The mint of token vault 0
+    #[account(
+        address = token_vault_0.mint
+    )]
+    pub vault_0_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The mint of token vault 1
+    #[account(
+        address = token_vault_1.mint
+    )]
+    pub vault_1_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The SPL program to perform token transfers
+    pub token_program: Program<'info, Token>,
+
+    /// The SPL program 2022 to perform token transfers
+    pub token_program_2022: Program<'info, Token2022>,
+}
+
+/// The remaining accounts are the token_0 and token_1 accounts of each protocol fee recipient,
+/// followed by those of each fund fee recipient, in the order of the recipients of the fee distribution.
+/// The protocol or fund fees without recipients stay in the pool
+pub fn distribute_fees<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, DistributeFees<'info>>,
+) -> Result<()> {
+    let protocol_recipients = &ctx.accounts.fee_distribution.protocol_recipients;
+    let fund_recipients = &ctx.accounts.fee_distribution.fund_recipients;
+    require_eq!(
+        ctx.remaining_accounts.len(),
+        (protocol_recipients.len() + fund_recipients.len()) * 2,
+        ErrorCode::InvalidFeeRecipientTokenAccount
+    );
+
+    let mut protocol_fees = (0, 0);
+    let mut fund_fees = (0, 0);
+    {
+        let mut pool_state = ctx.accounts.pool_state.load_mut()?;
+        if !protocol_recipients.is_empty() {
+            protocol_fees = (
+                pool_state.protocol_fees_token_0,
+                pool_state.protocol_fees_token_1,
+            );
+            pool_state.protocol_fees_token_0 = 0;
+            pool_state.protocol_fees_token_1 = 0;
+        }
+        if !fund_recipients.is_empty() {
+            fund_fees = (pool_state.fund_fees_token_0, pool_state.fund_fees_token_1);
+            pool_state.fund_fees_token_0 = 0;
+            pool_state.fund_fees_token_1 = 0;
+        }
+    }
+
+    let (protocol_token_accounts, fund_token_accounts) = ctx
+        .remaining_accounts
+        .split_at(protocol_recipients.len() * 2);
+    distribute_to_recipients(
+        ctx.accounts,
+        protocol_recipients,
+        protocol_token_accounts,
+        protocol_fees,
+        false,
+    )?;
+    distribute_to_recipients(
+        ctx.accounts,
+        fund_recipients,
+        fund_token_accounts,
+        fund_fees,
+        true,
+    )?;
+
+    check_unclaimed_fees_and_vault(
+        &ctx.accounts.pool_state,
+        &ctx.accounts.token_vault_0.to_account_info(),
+        &ctx.accounts.token_vault_1.to_account_info(),
+    )?;
+
+    Ok(())
+}
+
+/// Split the amounts of token_0 and token_1 by the shares of the recipients
+/// and transfer them to the token accounts of each recipient
+fn distribute_to_recipients<'info>(
+    accounts: &DistributeFees<'info>,
+    recipients: &[FeeRecipient],
+    token_accounts: &'info [AccountInfo<'info>],
+    amounts: (u64, u64),
+    fund_fee: bool,
+) -> Result<()> {
+    let amounts_0 = FeeDistributionState::split(recipients, amounts.0);
+    let amounts_1 = FeeDistributionState::split(recipients, amounts.1);
+
+    for (i, fee_recipient) in recipients.iter().enumerate() {
+        let recipient_token_account_0 = &token_accounts[i * 2];
+        let recipient_token_account_1 = &token_accounts[i * 2 + 1];
+        for (token_account_info, mint) in [
+            (recipient_token_account_0, accounts.vault_0_mint.key()),
+            (recipient_token_account_1, accounts.vault_1_mint.key()),
+        ] {
+            let token_account = InterfaceAccount::<TokenAccount>::try_from(token_account_info)?;
+            require!(
+                token_account.mint == mint && token_account.owner == fee_recipient.recipient,
+                ErrorCode::InvalidFeeRecipientTokenAccount
+            );
+        }
+
+        transfer_from_pool_vault_to_user(
+            &accounts.pool_state,
+            &accounts.token_vault_0.to_account_info(),
+            recipient_token_account_0,
+            Some(accounts.vault_0_mint.clone()),
+            &accounts.token_program,
+            Some(accounts.token_program_2022.to_account_info()),
+            amounts_0[i],
+        )?;
+
+        transfer_from_pool_vault_to_user(
+            &accounts.pool_state,
+            &accounts.token_vault_1.to_account_info(),
+            recipient_token_account_1,
+            Some(accounts.vault_1_mint.clone()),
+            &accounts.token_program,
+            Some(accounts.token_program_2022.to_account_info()),
+            amounts_1[i],
+        )?;
+
+        emit!(FeeDistributedEvent {
+            pool_state: accounts.pool_state.key(),
+            fund_fee,
+            recipient: fee_recipient.recipient,
+            recipient_token_account_0: recipient_token_account_0.key(),
+            recipient_token_account_1: recipient_token_account_1.key(),
+            amount_0: amounts_0[i],
+            amount_1: amounts_1[i],
+        });
+    }
+    Ok(())
+}
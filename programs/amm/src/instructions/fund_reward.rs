@@ -0,0 +1,349 @@
+use crate::error::ErrorCode;
+use crate::libraries::{fixed_point_64, full_math::MulDiv, U256};
+use crate::util::transfer_from_user_to_pool_vault;
+use crate::{states::*, util};
+use anchor_lang::prelude::*;
+use anchor_spl::token::Token;
+use anchor_spl::token_interface::{Mint, Token2022, TokenAccount};
+
+#[derive(Accounts)]
+pub struct FundReward<'info> {
+    /// Anyone can fund an initialized reward by increasing its emissions,
+    /// only the reward authority can extend its end time
+    pub funder: Signer<'info>,
+
+    /// The funder's reward token account
+    #[account(
+        mut,
+        token::mint = reward_vault_mint
+    )]
+    pub funder_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Fund reward for this pool
+    #[account(mut)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// The reward vault of the funded reward
+    #[account(mut)]
+    pub reward_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The mint of reward vault
+    #[account(
+        address = reward_token_vault.mint
+    )]
+    pub reward_vault_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Token program
+    pub token_program: Program<'info, Token>,
+    /// Token program 2022
+    pub token_program_2022: Program<'info, Token2022>,
+    // remaining account
+    // the reward extension of the pool, required when the funded reward is an extension reward
+}
+
+/// How the funded amount is added to the reward emission
+#[derive(Copy, Clone, AnchorSerialize, AnchorDeserialize, Debug, PartialEq, Eq)]
+pub enum FundRewardMode {
+    /// Keep the emissions and extend the end time, only for the reward authority
+    ExtendEndTime,
+    /// Keep the end time and increase the emissions of the remaining period
+    IncreaseEmissions,
+}
+
+pub fn fund_reward<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, FundReward<'info>>,
+    reward_index: u8,
+    amount: u64,
+    mode: FundRewardMode,
+) -> Result<()> {
+    let index = reward_index as usize;
+    require_gt!(REWARD_NUM + EXTENSION_REWARD_NUM, index);
+    require_gt!(amount, 0);
+    let current_timestamp = u64::try_from(Clock::get()?.unix_timestamp).unwrap();
+
+    let mut pool_state = ctx.accounts.pool_state.load_mut()?;
+    // the indexes beyond the pool reward infos are the rewards of the reward extension
+    let reward_extension = if index < REWARD_NUM {
+        None
+    } else {
+        match util::load_reward_extension(&pool_state, ctx.remaining_accounts)? {
+            Some(reward_extension) => Some(reward_extension),
+            None => return err!(ErrorCode::MissingRewardExtensionAccount),
+        }
+    };
+
+    pool_state.update_reward_infos(current_timestamp)?;
+
+    let (mut reward_info, reward_schedule) = match &reward_extension {
+        Some(reward_extension) => {
+            let mut reward_extension = reward_extension.load_mut()?;
+            reward_extension
+                .update_reward_infos(pool_state.reward_liquidity(), current_timestamp)?;
+            (
+                reward_extension.reward_infos[index - REWARD_NUM],
+                reward_extension.reward_schedules[index - REWARD_NUM],
+            )
+        }
+        None => (
+            pool_state.reward_infos[index],
+            pool_state.reward_schedules[index],
+        ),
+    };
+    if !reward_info.initialized() {
+        return err!(ErrorCode::UnInitializedRewardInfo);
+    }
+    // the emissions of a scheduled reward are fixed by its schedule
+    if !reward_schedule.is_constant() {
+        return err!(ErrorCode::ScheduledRewardRunning);
+    }
+    require_keys_eq!(
+        ctx.accounts.reward_token_vault.key(),
+        reward_info.token_vault
+    );
+
+    fund_reward_info(
+        &mut reward_info,
+        ctx.accounts.funder.key(),
+        current_timestamp,
+        amount,
+        mode,
+    )?;
+
+    match &reward_extension {
+        Some(reward_extension) => {
+            reward_extension.load_mut()?.reward_infos[index - REWARD_NUM] = reward_info
+        }
+        None => pool_state.reward_infos[index] = reward_info,
+    }
+
+    let transfer_fee =
+        util::get_transfer_inverse_fee(ctx.accounts.reward_vault_mint.clone(), amount).unwrap();
+    let amount_with_transfer_fee = amount.checked_add(transfer_fee).unwrap();
+    require_gte!(
+        ctx.accounts.funder_token_account.amount,
+        amount_with_transfer_fee
+    );
+    transfer_from_user_to_pool_vault(
+        &ctx.accounts.funder,
+        &ctx.accounts.funder_token_account.to_account_info(),
+        &ctx.accounts.reward_token_vault.to_account_info(),
+        Some(ctx.accounts.reward_vault_mint.clone()),
+        &ctx.accounts.token_program,
+        Some(ctx.accounts.token_program_2022.to_account_info()),
+        amount_with_transfer_fee,
+    )?;
+
+    emit!(FundRewardEvent {
+        pool_state: ctx.accounts.pool_state.key(),
+        funder: ctx.accounts.funder.key(),
+        reward_index,
+        amount,
+        end_time: reward_info.end_time,
+        emissions_per_second_x64: reward_info.emissions_per_second_x64,
+    });
+
+    Ok(())
+}
+
+/// Add the funded amount to a running reward, the reward infos must be updated to current_timestamp
+fn fund_reward_info(
+    reward_info: &mut RewardInfo,
+    funder: Pubkey,
+    current_timestamp: u64,
+    amount: u64,
+    mode: FundRewardMode,
+) -> Result<()> {
+    // an ended reward has to be restarted by set_reward_params
+    if reward_info.end_time <= current_timestamp {
+        return err!(ErrorCode::RewardEmissionEnded);
+    }
+    match mode {
+        FundRewardMode::ExtendEndTime => {
+            // a later end time delays restarting or retiring the reward, so outsiders can't push it out
+            require_keys_eq!(funder, reward_info.authority, ErrorCode::NotApproved);
+            let extend_period = U256::from(amount)
+                .mul_div_floor(
+                    U256::from(fixed_point_64::Q64),
+                    U256::from(reward_info.emissions_per_second_x64),
+                )
+                .unwrap();
+            if extend_period == U256::default() {
+                return err!(ErrorCode::FundRewardAmountTooSmall);
+            }
+            if extend_period > U256::from(reward_period_limit::MAX_REWARD_PERIOD) {
+                return err!(ErrorCode::InvalidRewardPeriod);
+            }
+            let extend_period = extend_period.as_u64();
+            // the extended reward ends at most MAX_REWARD_PERIOD from now,
+            // checked before adding so the new end time can't overflow
+            if reward_info.end_time - current_timestamp
+                > reward_period_limit::MAX_REWARD_PERIOD - extend_period
+            {
+                return err!(ErrorCode::InvalidRewardPeriod);
+            }
+            reward_info.end_time += extend_period;
+        }
+        FundRewardMode::IncreaseEmissions => {
+            let left_reward_time =
+                reward_info.end_time - std::cmp::max(current_timestamp, reward_info.open_time);
+            let emission_diff_x64 = U256::from(amount)
+                .mul_div_floor(
+                    U256::from(fixed_point_64::Q64),
+                    U256::from(left_reward_time),
+                )
+                .unwrap();
+            if emission_diff_x64 == U256::default() {
+                return err!(ErrorCode::FundRewardAmountTooSmall);
+            }
+            reward_info.emissions_per_second_x64 = reward_info
+                .emissions_per_second_x64
+                .checked_add(emission_diff_x64.as_u128())
+                .unwrap();
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod fund_reward_test {
+    use super::*;
+
+    fn running_reward_info() -> RewardInfo {
+        RewardInfo {
+            reward_state: RewardState::Opening as u8,
+            open_time: 1000,
+            end_time: 2000,
+            last_update_time: 1500,
+            emissions_per_second_x64: 10 << fixed_point_64::RESOLUTION,
+            authority: Pubkey::new_unique(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn extend_end_time_test() {
+        let mut reward_info = running_reward_info();
+        let authority = reward_info.authority;
+        fund_reward_info(
+            &mut reward_info,
+            authority,
+            1500,
+            1005,
+            FundRewardMode::ExtendEndTime,
+        )
+        .unwrap();
+        let end_time = reward_info.end_time;
+        let emissions_per_second_x64 = reward_info.emissions_per_second_x64;
+        assert_eq!(end_time, 2100);
+        assert_eq!(emissions_per_second_x64, 10 << fixed_point_64::RESOLUTION);
+    }
+
+    #[test]
+    fn extend_end_time_by_outsider_test() {
+        let mut reward_info = running_reward_info();
+        assert!(fund_reward_info(
+            &mut reward_info,
+            Pubkey::new_unique(),
+            1500,
+            1005,
+            FundRewardMode::ExtendEndTime
+        )
+        .is_err());
+        let end_time = reward_info.end_time;
+        assert_eq!(end_time, 2000);
+    }
+
+    #[test]
+    fn increase_emissions_test() {
+        let mut reward_info = running_reward_info();
+        fund_reward_info(
+            &mut reward_info,
+            Pubkey::new_unique(),
+            1500,
+            1000,
+            FundRewardMode::IncreaseEmissions,
+        )
+        .unwrap();
+        let end_time = reward_info.end_time;
+        let emissions_per_second_x64 = reward_info.emissions_per_second_x64;
+        assert_eq!(end_time, 2000);
+        assert_eq!(emissions_per_second_x64, 12 << fixed_point_64::RESOLUTION);
+    }
+
+    #[test]
+    fn increase_emissions_before_open_test() {
+        let mut reward_info = running_reward_info();
+        fund_reward_info(
+            &mut reward_info,
+            Pubkey::new_unique(),
+            500,
+            1000,
+            FundRewardMode::IncreaseEmissions,
+        )
+        .unwrap();
+        let emissions_per_second_x64 = reward_info.emissions_per_second_x64;
+        assert_eq!(emissions_per_second_x64, 11 << fixed_point_64::RESOLUTION);
+    }
+
+    #[test]
+    fn fund_ended_reward_test() {
+        let mut reward_info = running_reward_info();
+        let authority = reward_info.authority;
+        assert!(fund_reward_info(
+            &mut reward_info,
+            authority,
+            2000,
+            1000,
+            FundRewardMode::ExtendEndTime
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn fund_amount_too_small_test() {
+        let mut reward_info = running_reward_info();
+        let authority = reward_info.authority;
+        assert!(fund_reward_info(
+            &mut reward_info,
+            authority,
+            1500,
+            9,
+            FundRewardMode::ExtendEndTime
+        )
+        .is_err());
+        assert!(fund_reward_info(
+            &mut reward_info,
+            authority,
+            1500,
+            0,
+            FundRewardMode::IncreaseEmissions
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn extend_beyond_max_period_test() {
+        let mut reward_info = running_reward_info();
+        let authority = reward_info.authority;
+        let amount = 10 * reward_period_limit::MAX_REWARD_PERIOD;
+        assert!(fund_reward_info(
+            &mut reward_info,
+            authority,
+            1500,
+            amount,
+            FundRewardMode::ExtendEndTime
+        )
+        .is_err());
+
+        // an end time far in the future is rejected without overflowing
+        reward_info.end_time = u64::MAX;
+        assert!(fund_reward_info(
+            &mut reward_info,
+            authority,
+            1500,
+            10,
+            FundRewardMode::ExtendEndTime
+        )
+        .is_err());
+    }
+}
@@ -87,6 +87,7 @@ pub struct IncreaseLiquidity<'info> {
     //     bump
     // )]
     // pub tick_array_bitmap: AccountLoader<'info, TickArrayBitmapExtension>,
+    // the amm config of the pool, its status is inherited by the pool when it is passed
 }
 
 pub fn increase_liquidity_v1<'a, 'b, 'c: 'info, 'info>(
@@ -141,9 +142,15 @@ pub fn increase_liquidity<'a, 'b, 'c: 'info, 'info>(
     amount_1_max: u64,
     base_flag: Option<bool>,
 ) -> Result<()> {
+    // the boosted liquidity of a locked position is fixed when it is locked
+    require!(!personal_position.is_locked(), ErrorCode::PositionLocked);
     let mut liquidity = liquidity;
     let pool_state = &mut pool_state_loader.load_mut()?;
-    if !pool_state.get_status_by_bit(PoolStatusBitIndex::OpenPositionOrIncreaseLiquidity) {
+    let amm_config = find_amm_config(pool_state, remaining_accounts)?;
+    if !pool_state.get_status_by_bit_with_config(
+        PoolStatusBitIndex::OpenPositionOrIncreaseLiquidity,
+        amm_config.as_deref(),
+    ) {
         return err!(ErrorCode::NotApproved);
     }
     let tick_lower = personal_position.tick_lower_index;
@@ -151,6 +158,11 @@ pub fn increase_liquidity<'a, 'b, 'c: 'info, 'info>(
 
     let use_tickarray_bitmap_extension =
         pool_state.is_overflow_default_tickarray_bitmap(vec![tick_lower, tick_upper]);
+    let reward_extension_loader = load_reward_extension(pool_state, remaining_accounts)?;
+    let mut reward_extension = match &reward_extension_loader {
+        Some(loader) => Some(loader.load_mut()?),
+        None => None,
+    };
 
     let (amount_0, amount_1, amount_0_transfer_fee, amount_1_transfer_fee) = add_liquidity(
         &nft_owner,
@@ -174,6 +186,7 @@ pub fn increase_liquidity<'a, 'b, 'c: 'info, 'info>(
         } else {
             None
         },
+        reward_extension.as_deref_mut(),
         pool_state,
         &mut liquidity,
         amount_0_max,
@@ -200,7 +213,13 @@ pub fn increase_liquidity<'a, 'b, 'c: 'info, 'info>(
     personal_position.fee_growth_inside_1_last_x64 = protocol_position.fee_growth_inside_1_last_x64;
 
     // update rewards, must update before increase liquidity
-    personal_position.update_rewards(protocol_position.reward_growth_inside, true)?;
+    personal_position.update_rewards(
+        protocol_position.reward_growth_inside,
+        protocol_position.reward_growth_inside_extension,
+        pool_state.reward_generations,
+        PoolRewardExtension::generations_of(reward_extension.as_deref()),
+        true,
+    )?;
     personal_position.liquidity = personal_position.liquidity.checked_add(liquidity).unwrap();
 
     emit!(IncreaseLiquidityEvent {
@@ -137,6 +137,8 @@ here instead of initialising two token program we can use Interface<'info, T>
     //     bump
     // )]
     // pub tick_array_bitmap: AccountLoader<'info, TickArrayBitmapExtension>,
+    // the amm config of the pool, its status is inherited by the pool when it is passed,
+    // required by the v3 instruction
 }
 
 pub fn increase_liquidity_v2<'a, 'b, 'c: 'info, 'info>(
@@ -0,0 +1,72 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::MAX_PERMITTED_DATA_INCREASE;
+use anchor_lang::system_program::{transfer, Transfer};
+
+#[derive(Accounts)]
+pub struct IncreaseObservationCardinality<'info> {
+    /// Pays the rent of the additional observations
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The pool whose oracle is grown
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// The program account for the oracle observation
+    #[account(mut, address = pool_state.load()?.observation_key)]
+    pub observation_state: AccountLoader<'info, ObservationState>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn increase_observation_cardinality(
+    ctx: Context<IncreaseObservationCardinality>,
+    observation_cardinality_next: u16,
+) -> Result<()> {
+    let observation_cardinality_next_old =
+        ctx.accounts.observation_state.load()?.cardinality_next();
+    require_gt!(
+        observation_cardinality_next,
+        observation_cardinality_next_old,
+        ErrorCode::InvalidObservationCardinality
+    );
+
+    let observation_info = ctx.accounts.observation_state.to_account_info();
+    let new_len = ObservationState::space(observation_cardinality_next);
+    // the runtime limits how much an account can grow within one instruction
+    require_gte!(
+        observation_info.data_len() + MAX_PERMITTED_DATA_INCREASE,
+        new_len,
+        ErrorCode::InvalidObservationCardinality
+    );
+
+    let additional_lamports = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(observation_info.lamports());
+    if additional_lamports > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: observation_info.clone(),
+                },
+            ),
+            additional_lamports,
+        )?;
+    }
+    observation_info.realloc(new_len, true)?;
+
+    ctx.accounts
+        .observation_state
+        .load_mut()?
+        .observation_cardinality_next = observation_cardinality_next;
+
+    emit!(IncreaseObservationCardinalityNextEvent {
+        pool_state: ctx.accounts.pool_state.key(),
+        observation_cardinality_next_old,
+        observation_cardinality_next,
+    });
+    Ok(())
+}
@@ -101,27 +101,41 @@ impl InitializeRewardParam {
     }
 }
 
-pub fn initialize_reward(
-    ctx: Context<InitializeReward>,
+pub fn initialize_reward<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, InitializeReward<'info>>,
     param: InitializeRewardParam,
 ) -> Result<()> {
     let mint_associated_is_initialized = util::support_mint_associated_is_initialized(
         &ctx.remaining_accounts,
         &ctx.accounts.reward_token_mint,
     )?;
+    let mint_registry = if util::is_registry_checked_mint(
+        &ctx.accounts.reward_token_mint,
+        mint_associated_is_initialized,
+    ) {
+        util::load_mint_registry(ctx.remaining_accounts)?
+    } else {
+        None
+    };
+    let mint_registry = mint_registry
+        .as_ref()
+        .map(|mint_registry| mint_registry.load())
+        .transpose()?;
     if !util::is_supported_mint(
         &ctx.accounts.reward_token_mint,
         mint_associated_is_initialized,
-    )
-    .unwrap()
-    {
+        mint_registry.as_deref(),
+    )? {
         return err!(ErrorCode::NotSupportMint);
     }
-    let operation_state = ctx.accounts.operation_state.load()?;
+    let operation_state = OperationState::load_with_roles(&ctx.accounts.operation_state)?;
     require!(
         ctx.accounts.reward_funder.key() == crate::admin::id()
             || ctx.accounts.reward_funder.key() == ctx.accounts.pool_state.load()?.owner
-            || operation_state.validate_operation_owner(ctx.accounts.reward_funder.key()),
+            || operation_state.validate_operator_role(
+                ctx.accounts.reward_funder.key(),
+                OperatorRoleBitIndex::RewardAdmin
+            ),
         ErrorCode::NotApproved
     );
 
@@ -150,19 +164,50 @@ pub fn initialize_reward(
     );
     //here it adds more details to the pool_state pda such open time, end time
     let mut pool_state = ctx.accounts.pool_state.load_mut()?;
-    pool_state.initialize_reward(
-        param.open_time,
-        param.end_time,
-        param.emissions_per_second_x64,
-        //the mint
-        &ctx.accounts.reward_token_mint.key(),
-        //the freeze_authority
-        ctx.accounts.reward_token_mint.freeze_authority,
-        //the vault ata key
-        &ctx.accounts.reward_token_vault.key(),
-        &ctx.accounts.reward_funder.key(),
-        &operation_state,
-    )?;
+    let reward_infos = pool_state.reward_infos;
+    let reward_extension = util::load_reward_extension(&pool_state, &ctx.remaining_accounts)?;
+    if reward_infos.iter().all(|r| r.initialized()) {
+        // the rewards beyond the pool reward infos go to the reward extension of the pool
+        let reward_extension = match reward_extension {
+            Some(reward_extension) => reward_extension,
+            None => return err!(ErrorCode::FullRewardInfo),
+        };
+        reward_extension.load_mut()?.initialize_reward(
+            &pool_state,
+            param.open_time,
+            param.end_time,
+            param.emissions_per_second_x64,
+            &ctx.accounts.reward_token_mint.key(),
+            ctx.accounts.reward_token_mint.freeze_authority,
+            &ctx.accounts.reward_token_vault.key(),
+            &ctx.accounts.reward_funder.key(),
+            &operation_state,
+        )?;
+    } else {
+        // a retired pool reward slot can't be reused with a mint of the reward extension
+        if let Some(reward_extension) = reward_extension {
+            let extension_reward_infos = reward_extension.load()?.reward_infos;
+            require!(
+                !extension_reward_infos
+                    .iter()
+                    .any(|r| r.token_mint == ctx.accounts.reward_token_mint.key()),
+                ErrorCode::RewardTokenAlreadyInUse
+            );
+        }
+        pool_state.initialize_reward(
+            param.open_time,
+            param.end_time,
+            param.emissions_per_second_x64,
+            //the mint
+            &ctx.accounts.reward_token_mint.key(),
+            //the freeze_authority
+            ctx.accounts.reward_token_mint.freeze_authority,
+            //the vault ata key
+            &ctx.accounts.reward_token_vault.key(),
+            &ctx.accounts.reward_funder.key(),
+            &operation_state,
+        )?;
+    }
 
     transfer_from_user_to_pool_vault(
         &ctx.accounts.reward_funder,
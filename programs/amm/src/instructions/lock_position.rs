@@ -0,0 +1,198 @@
+use super::modify_position;
+use crate::error::ErrorCode;
+use crate::libraries::liquidity_math;
+use crate::states::*;
+use crate::util::load_reward_extension;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+use std::cell::RefMut;
+
+#[derive(Accounts)]
+pub struct LockPosition<'info> {
+    /// The position owner, pays for the lock account
+    #[account(mut)]
+    pub nft_owner: Signer<'info>,
+
+    /// The token account for the tokenized position
+    #[account(
+        constraint = nft_account.mint == personal_position.nft_mint,
+        constraint = nft_account.amount == 1,
+        token::authority = nft_owner,
+    )]
+    pub nft_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Lock this position
+    #[account(mut, constraint = personal_position.pool_id == pool_state.key())]
+    pub personal_position: Box<Account<'info, PersonalPositionState>>,
+
+    #[account(mut)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    #[account(
+        mut,
+        seeds = [
+            POSITION_SEED.as_bytes(),
+            pool_state.key().as_ref(),
+            &personal_position.tick_lower_index.to_be_bytes(),
+            &personal_position.tick_upper_index.to_be_bytes(),
+        ],
+        bump,
+        constraint = protocol_position.pool_id == pool_state.key(),
+    )]
+    pub protocol_position: Box<Account<'info, ProtocolPositionState>>,
+
+    /// Stores init state for the lower tick
+    #[account(mut, constraint = tick_array_lower.load()?.pool_id == pool_state.key())]
+    pub tick_array_lower: AccountLoader<'info, TickArrayState>,
+
+    /// Stores init state for the upper tick
+    #[account(mut, constraint = tick_array_upper.load()?.pool_id == pool_state.key())]
+    pub tick_array_upper: AccountLoader<'info, TickArrayState>,
+
+    /// The lock of the position
+    #[account(
+        init,
+        seeds = [
+            POSITION_LOCK_SEED.as_bytes(),
+            personal_position.nft_mint.as_ref(),
+        ],
+        bump,
+        payer = nft_owner,
+        space = PositionLockState::LEN
+    )]
+    pub position_lock: Box<Account<'info, PositionLockState>>,
+
+    pub system_program: Program<'info, System>,
+    // remaining account
+    // the reward extension of the pool, required when the pool has one
+}
+
+pub fn lock_position<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, LockPosition<'info>>,
+    lock_until: u64,
+) -> Result<()> {
+    let current_timestamp = u64::try_from(Clock::get()?.unix_timestamp).unwrap();
+    let personal_position = &mut ctx.accounts.personal_position;
+    require!(!personal_position.is_locked(), ErrorCode::PositionLocked);
+    require_gt!(personal_position.liquidity, 0);
+
+    let lock_reward_multiplier;
+    let reward_extension = {
+        let pool_state = ctx.accounts.pool_state.load()?;
+        lock_reward_multiplier = pool_state.lock_reward_multiplier;
+        require!(lock_reward_multiplier != 0, ErrorCode::PositionLockDisabled);
+        require_gte!(
+            lock_until,
+            current_timestamp + u64::from(pool_state.min_lock_duration),
+            ErrorCode::InvalidLockDuration
+        );
+        load_reward_extension(&pool_state, ctx.remaining_accounts)?
+    };
+    let boosted_liquidity = update_position_boost(
+        &mut ctx.accounts.pool_state.load_mut()?,
+        reward_extension.as_ref(),
+        &mut ctx.accounts.protocol_position,
+        personal_position,
+        &ctx.accounts.tick_array_lower,
+        &ctx.accounts.tick_array_upper,
+        lock_reward_multiplier,
+        current_timestamp,
+    )?;
+
+    let position_lock = &mut ctx.accounts.position_lock;
+    position_lock.bump = [ctx.bumps.position_lock];
+    position_lock.nft_mint = personal_position.nft_mint;
+    position_lock.lock_until = lock_until;
+    position_lock.boosted_liquidity = boosted_liquidity;
+    position_lock.rent_payer = ctx.accounts.nft_owner.key();
+
+    emit!(LockPositionEvent {
+        position_nft_mint: personal_position.nft_mint,
+        pool_state: ctx.accounts.pool_state.key(),
+        lock_until,
+        lock_reward_multiplier,
+        boosted_liquidity,
+    });
+
+    Ok(())
+}
+
+/// Settles the rewards of the position with its current reward liquidity, then changes its
+/// reward multiplier and moves the boosted liquidity of the ticks and the pool accordingly.
+/// Returns the boosted liquidity of the position after the change.
+pub fn update_position_boost<'info>(
+    pool_state: &mut RefMut<PoolState>,
+    reward_extension: Option<&AccountLoader<'info, PoolRewardExtension>>,
+    protocol_position: &mut ProtocolPositionState,
+    personal_position: &mut PersonalPositionState,
+    tick_array_lower_loader: &AccountLoader<'info, TickArrayState>,
+    tick_array_upper_loader: &AccountLoader<'info, TickArrayState>,
+    lock_reward_multiplier: u16,
+    timestamp: u64,
+) -> Result<u128> {
+    let mut tick_lower_state = *tick_array_lower_loader
+        .load_mut()?
+        .get_tick_state_mut(protocol_position.tick_lower_index, pool_state.tick_spacing)?;
+    let mut tick_upper_state = *tick_array_upper_loader
+        .load_mut()?
+        .get_tick_state_mut(protocol_position.tick_upper_index, pool_state.tick_spacing)?;
+
+    let mut reward_extension = match reward_extension {
+        Some(loader) => Some(loader.load_mut()?),
+        None => None,
+    };
+    // accumulate the rewards up to now without changing the liquidity
+    modify_position(
+        0,
+        pool_state,
+        reward_extension.as_deref_mut(),
+        protocol_position,
+        &mut tick_lower_state,
+        &mut tick_upper_state,
+        timestamp,
+    )?;
+    personal_position.update_rewards(
+        protocol_position.reward_growth_inside,
+        protocol_position.reward_growth_inside_extension,
+        pool_state.reward_generations,
+        PoolRewardExtension::generations_of(reward_extension.as_deref()),
+        true,
+    )?;
+
+    let boosted_liquidity_before = personal_position.boosted_liquidity();
+    personal_position.lock_reward_multiplier = lock_reward_multiplier;
+    let boosted_liquidity = personal_position.boosted_liquidity();
+    let boosted_liquidity_delta = i128::try_from(boosted_liquidity).unwrap()
+        - i128::try_from(boosted_liquidity_before).unwrap();
+
+    if boosted_liquidity_delta != 0 {
+        // the boosted liquidity is crossed like the liquidity net of the ticks
+        tick_lower_state.boosted_liquidity_net = tick_lower_state
+            .boosted_liquidity_net
+            .checked_add(boosted_liquidity_delta)
+            .unwrap();
+        tick_upper_state.boosted_liquidity_net = tick_upper_state
+            .boosted_liquidity_net
+            .checked_sub(boosted_liquidity_delta)
+            .unwrap();
+        if pool_state.tick_current >= tick_lower_state.tick
+            && pool_state.tick_current < tick_upper_state.tick
+        {
+            pool_state.boosted_liquidity =
+                liquidity_math::add_delta(pool_state.boosted_liquidity, boosted_liquidity_delta)?;
+        }
+    }
+
+    tick_array_lower_loader.load_mut()?.update_tick_state(
+        protocol_position.tick_lower_index,
+        pool_state.tick_spacing,
+        tick_lower_state,
+    )?;
+    tick_array_upper_loader.load_mut()?.update_tick_state(
+        protocol_position.tick_upper_index,
+        pool_state.tick_spacing,
+        tick_upper_state,
+    )?;
+
+    Ok(boosted_liquidity)
+}
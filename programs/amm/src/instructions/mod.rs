@@ -34,6 +34,12 @@ pub use swap_v2::*;
 pub mod swap_router_base_in;
 pub use swap_router_base_in::*;
 
+pub mod swap_router_base_out;
+pub use swap_router_base_out::*;
+
+pub mod swap_router_base_in_v2;
+pub use swap_router_base_in_v2::*;
+
 pub mod update_reward_info;
 pub use update_reward_info::*;
 
@@ -46,5 +52,41 @@ pub use set_reward_params::*;
 pub mod collect_remaining_rewards;
 pub use collect_remaining_rewards::*;
 
+pub mod create_reward_extension;
+pub use create_reward_extension::*;
+
+pub mod fund_reward;
+pub use fund_reward::*;
+
+pub mod retire_reward;
+pub use retire_reward::*;
+
+pub mod set_reward_schedule;
+pub use set_reward_schedule::*;
+
+pub mod lock_position;
+pub use lock_position::*;
+
+pub mod unlock_position;
+pub use unlock_position::*;
+
+pub mod set_position_lock_config;
+pub use set_position_lock_config::*;
+
+pub mod observe;
+pub use observe::*;
+
+pub mod increase_observation_cardinality;
+pub use increase_observation_cardinality::*;
+
+pub mod create_swap_allowlist;
+pub use create_swap_allowlist::*;
+
+pub mod update_swap_allowlist;
+pub use update_swap_allowlist::*;
+
+pub mod distribute_fees;
+pub use distribute_fees::*;
+
 pub mod admin;
 pub use admin::*;
@@ -0,0 +1,74 @@
+use crate::error::ErrorCode;
+use crate::libraries::tick_math;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct Observe<'info> {
+    /// The pool to read the oracle of
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// The program account for the oracle observation
+    #[account(address = pool_state.load()?.observation_key)]
+    pub observation_state: AccountLoader<'info, ObservationState>,
+}
+
+/// The result of an `observe` call, returned through the transaction return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct ObserveResult {
+    /// The tick cumulative as of each `seconds_ago`, in the same order as the input
+    pub tick_cumulatives: Vec<i64>,
+    /// The seconds per in range liquidity cumulative as of each `seconds_ago`, as Q64.64
+    pub seconds_per_liquidity_cumulative_x64s: Vec<u128>,
+    /// The time weighted average tick between the oldest and the newest requested point
+    pub twap_tick: i32,
+    /// The sqrt price at `twap_tick`
+    pub twap_sqrt_price_x64: u128,
+}
+
+pub fn observe(ctx: Context<Observe>, seconds_agos: Vec<u32>) -> Result<ObserveResult> {
+    require!(
+        seconds_agos.len() >= 2 && seconds_agos.len() <= OBSERVATION_NUM,
+        ErrorCode::InvalidObserveParams
+    );
+    let pool_state = ctx.accounts.pool_state.load()?;
+    let (observation_state, observation_extension) =
+        ObservationState::load_with_extension(&ctx.accounts.observation_state)?;
+    require_keys_eq!(observation_state.pool_id, ctx.accounts.pool_state.key());
+
+    let current_timestamp = block_timestamp();
+    let (tick_cumulatives, seconds_per_liquidity_cumulative_x64s) = observation_state.observe(
+        &observation_extension,
+        current_timestamp,
+        &seconds_agos,
+        pool_state.tick_current,
+        pool_state.seconds_per_liquidity_cumulative_x64(u64::from(current_timestamp)),
+    )?;
+    let twap_tick = get_twap_tick(&seconds_agos, &tick_cumulatives)?;
+
+    Ok(ObserveResult {
+        tick_cumulatives,
+        seconds_per_liquidity_cumulative_x64s,
+        twap_tick,
+        twap_sqrt_price_x64: tick_math::get_sqrt_price_at_tick(twap_tick)?,
+    })
+}
+
+/// Calculates the arithmetic mean tick between the furthest and the nearest requested points.
+pub fn get_twap_tick(seconds_agos: &[u32], tick_cumulatives: &[i64]) -> Result<i32> {
+    let (mut oldest, mut newest) = (0usize, 0usize);
+    for (i, seconds_ago) in seconds_agos.iter().enumerate() {
+        if *seconds_ago > seconds_agos[oldest] {
+            oldest = i;
+        }
+        if *seconds_ago < seconds_agos[newest] {
+            newest = i;
+        }
+    }
+    let window = i64::from(seconds_agos[oldest] - seconds_agos[newest]);
+    require_gt!(window, 0, ErrorCode::InvalidObserveParams);
+
+    let tick_cumulative_delta = tick_cumulatives[newest].wrapping_sub(tick_cumulatives[oldest]);
+    let twap_tick = get_mean_tick(tick_cumulative_delta, window);
+    Ok(twap_tick.clamp(tick_math::MIN_TICK.into(), tick_math::MAX_TICK.into()) as i32)
+}
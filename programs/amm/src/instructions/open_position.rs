@@ -165,6 +165,7 @@ pub struct OpenPosition<'info> {
     //     bump
     // )]
     // pub tick_array_bitmap: AccountLoader<'info, TickArrayBitmapExtension>,
+    // the amm config of the pool, its status is inherited by the pool when it is passed
 }
 
 pub fn open_position_v1<'a, 'b, 'c: 'info, 'info>(
@@ -259,7 +260,11 @@ pub fn open_position<'a, 'b, 'c: 'info, 'info>(
     let mut liquidity = liquidity;
     {
         let pool_state = &mut pool_state_loader.load_mut()?;
-        if !pool_state.get_status_by_bit(PoolStatusBitIndex::OpenPositionOrIncreaseLiquidity) {
+        let amm_config = find_amm_config(pool_state, remaining_accounts)?;
+        if !pool_state.get_status_by_bit_with_config(
+            PoolStatusBitIndex::OpenPositionOrIncreaseLiquidity,
+            amm_config.as_deref(),
+        ) {
             return err!(ErrorCode::NotApproved);
         }
         check_ticks_order(tick_lower_index, tick_upper_index)?;
@@ -341,6 +346,11 @@ pub fn open_position<'a, 'b, 'c: 'info, 'info>(
             tick_array_lower_start_index,
             tick_array_upper_start_index,
         ]);
+        let reward_extension_loader = load_reward_extension(pool_state, remaining_accounts)?;
+        let mut reward_extension = match &reward_extension_loader {
+            Some(loader) => Some(loader.load_mut()?),
+            None => None,
+        };
 
         let (amount_0, amount_1, amount_0_transfer_fee, amount_1_transfer_fee) = add_liquidity(
             payer,
@@ -364,6 +374,7 @@ pub fn open_position<'a, 'b, 'c: 'info, 'info>(
             } else {
                 None
             },
+            reward_extension.as_deref_mut(),
             pool_state,
             &mut liquidity,
             amount_0_max,
@@ -386,7 +397,13 @@ pub fn open_position<'a, 'b, 'c: 'info, 'info>(
             protocol_position.fee_growth_inside_1_last_x64;
 
         // update rewards, must update before update liquidity
-        personal_position.update_rewards(protocol_position.reward_growth_inside, false)?;
+        personal_position.update_rewards(
+            protocol_position.reward_growth_inside,
+            protocol_position.reward_growth_inside_extension,
+            pool_state.reward_generations,
+            PoolRewardExtension::generations_of(reward_extension.as_deref()),
+            false,
+        )?;
         personal_position.liquidity = liquidity;
 
         emit!(CreatePersonalPositionEvent {
@@ -435,6 +452,7 @@ pub fn add_liquidity<'b, 'c: 'info, 'info>(
     vault_0_mint: Option<Box<InterfaceAccount<'info, token_interface::Mint>>>,
     vault_1_mint: Option<Box<InterfaceAccount<'info, token_interface::Mint>>>,
     tick_array_bitmap_extension: Option<&'c AccountInfo<'info>>,
+    reward_extension: Option<&mut PoolRewardExtension>,
     pool_state: &mut RefMut<PoolState>,
     liquidity: &mut u128,
     amount_0_max: u64,
@@ -508,6 +526,7 @@ pub fn add_liquidity<'b, 'c: 'info, 'info>(
     let (amount_0, amount_1, flip_tick_lower, flip_tick_upper) = modify_position(
         i128::try_from(*liquidity).unwrap(),
         pool_state,
+        reward_extension,
         protocol_position,
         &mut tick_lower_state,
         &mut tick_upper_state,
@@ -636,6 +655,7 @@ pub fn add_liquidity<'b, 'c: 'info, 'info>(
 pub fn modify_position(
     liquidity_delta: i128,
     pool_state: &mut RefMut<PoolState>,
+    reward_extension: Option<&mut PoolRewardExtension>,
     protocol_position_state: &mut ProtocolPositionState,
     tick_lower_state: &mut TickState,
     tick_upper_state: &mut TickState,
@@ -644,6 +664,7 @@ pub fn modify_position(
     let (flip_tick_lower, flip_tick_upper) = update_position(
         liquidity_delta,
         pool_state,
+        reward_extension,
         protocol_position_state,
         tick_lower_state,
         tick_upper_state,
@@ -663,6 +684,8 @@ pub fn modify_position(
         if pool_state.tick_current >= tick_lower_state.tick
             && pool_state.tick_current < tick_upper_state.tick
         {
+            // the seconds per liquidity since the last change is accumulated with the liquidity before this one
+            pool_state.update_seconds_per_liquidity(timestamp);
             pool_state.liquidity =
                 liquidity_math::add_delta(pool_state.liquidity, liquidity_delta)?;
         }
@@ -675,12 +698,19 @@ pub fn modify_position(
 pub fn update_position(
     liquidity_delta: i128,
     pool_state: &mut RefMut<PoolState>,
+    reward_extension: Option<&mut PoolRewardExtension>,
     protocol_position_state: &mut ProtocolPositionState,
     tick_lower_state: &mut TickState,
     tick_upper_state: &mut TickState,
     timestamp: u64,
 ) -> Result<(bool, bool)> {
     let updated_reward_infos = pool_state.update_reward_infos(timestamp)?;
+    let updated_extension_reward_infos = match reward_extension {
+        Some(reward_extension) => {
+            reward_extension.update_reward_infos(pool_state.reward_liquidity(), timestamp)?
+        }
+        None => [RewardInfo::default(); EXTENSION_REWARD_NUM],
+    };
 
     let mut flipped_lower = false;
     let mut flipped_upper = false;
@@ -695,6 +725,8 @@ pub fn update_position(
             pool_state.fee_growth_global_1_x64,
             false,
             &updated_reward_infos,
+            &updated_extension_reward_infos,
+            timestamp as u32,
         )?;
         flipped_upper = tick_upper_state.update(
             pool_state.tick_current,
@@ -703,6 +735,8 @@ pub fn update_position(
             pool_state.fee_growth_global_1_x64,
             true,
             &updated_reward_infos,
+            &updated_extension_reward_infos,
+            timestamp as u32,
         )?;
         #[cfg(feature = "enable-log")]
         msg!(
@@ -728,6 +762,12 @@ pub fn update_position(
         pool_state.tick_current,
         &updated_reward_infos,
     );
+    let extension_reward_growths_inside = tick_array::get_extension_reward_growths_inside(
+        tick_lower_state.deref(),
+        tick_upper_state.deref(),
+        pool_state.tick_current,
+        &updated_extension_reward_infos,
+    );
 
     protocol_position_state.update(
         tick_lower_state.tick,
@@ -736,6 +776,7 @@ pub fn update_position(
         fee_growth_inside_0_x64,
         fee_growth_inside_1_x64,
         reward_growths_inside,
+        extension_reward_growths_inside,
     )?;
     if liquidity_delta < 0 {
         if flipped_lower {
@@ -975,6 +1016,7 @@ mod modify_position_test {
         let result = modify_position(
             0,
             pool_state,
+            None,
             &mut ProtocolPositionState::default(),
             tick_lower_state,
             tick_upper_state,
@@ -1006,6 +1048,7 @@ mod modify_position_test {
         let (amount_0_int, amount_1_int, flip_tick_lower, flip_tick_upper) = modify_position(
             liquidity_delta,
             pool_state,
+            None,
             protocol_position,
             tick_lower_state,
             tick_upper_state,
@@ -1073,6 +1116,7 @@ mod modify_position_test {
         let (amount_0_int, amount_1_int, flip_tick_lower, flip_tick_upper) = modify_position(
             liquidity_delta,
             pool_state,
+            None,
             protocol_position,
             tick_lower_state,
             tick_upper_state,
@@ -1138,6 +1182,7 @@ mod modify_position_test {
         let (amount_0_int, amount_1_int, flip_tick_lower, flip_tick_upper) = modify_position(
             liquidity_delta,
             pool_state,
+            None,
             protocol_position,
             tick_lower_state,
             tick_upper_state,
@@ -154,6 +154,8 @@ pub struct OpenPositionV2<'info> {
     //     bump
     // )]
     // pub tick_array_bitmap: AccountLoader<'info, TickArrayBitmapExtension>,
+    // the amm config of the pool, its status is inherited by the pool when it is passed,
+    // required by the v3 instruction
 }
 
 pub fn open_position_v2<'a, 'b, 'c: 'info, 'info>(
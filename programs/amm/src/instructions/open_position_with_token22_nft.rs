@@ -155,6 +155,7 @@ pub struct OpenPositionWithToken22Nft<'info> {
     //     bump
     // )]
     // pub tick_array_bitmap: AccountLoader<'info, TickArrayBitmapExtension>,
+    // the amm config of the pool, its status is inherited by the pool when it is passed
 }
 
 pub fn open_position_with_token22_nft<'a, 'b, 'c: 'info, 'info>(
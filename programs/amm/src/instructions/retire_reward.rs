@@ -0,0 +1,125 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use crate::util::{load_reward_extension, transfer_from_pool_vault_to_user};
+use anchor_lang::prelude::*;
+use anchor_spl::token::Token;
+use anchor_spl::token_interface::{Mint, Token2022, TokenAccount};
+
+#[derive(Accounts)]
+pub struct RetireReward<'info> {
+    /// The pool creator or the admin
+    #[account(
+        constraint = (owner.key() == pool_state.load()?.owner || owner.key() == crate::admin::id()) @ ErrorCode::NotApproved
+    )]
+    pub owner: Signer<'info>,
+
+    /// Retire a reward of this pool
+    #[account(mut)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// The vault of the retired reward, its balance beyond the unclaimed reward is swept to the pool owner
+    #[account(mut)]
+    pub reward_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The mint of the reward vault
+    #[account(
+        address = reward_token_vault.mint
+    )]
+    pub reward_vault_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The token account of the pool owner receiving the remaining balance of the vault
+    #[account(
+        mut,
+        token::mint = reward_vault_mint,
+        constraint = owner_token_account.owner == pool_state.load()?.owner @ ErrorCode::NotApproved
+    )]
+    pub owner_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token program
+    pub token_program: Program<'info, Token>,
+    /// Token program 2022
+    pub token_program_2022: Program<'info, Token2022>,
+    // remaining account
+    // the reward extension of the pool, required when the retired reward is an extension reward
+}
+
+pub fn retire_reward<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, RetireReward<'info>>,
+    reward_index: u8,
+) -> Result<()> {
+    let index = reward_index as usize;
+    require_gt!(REWARD_NUM + EXTENSION_REWARD_NUM, index);
+    let current_timestamp = u64::try_from(Clock::get()?.unix_timestamp).unwrap();
+
+    let (reward_info, generation) = {
+        let mut pool_state = ctx.accounts.pool_state.load_mut()?;
+        // the indexes beyond the pool reward infos are the rewards of the reward extension
+        let reward_extension = if index < REWARD_NUM {
+            None
+        } else {
+            match load_reward_extension(&pool_state, ctx.remaining_accounts)? {
+                Some(reward_extension) => Some(reward_extension),
+                None => return err!(ErrorCode::MissingRewardExtensionAccount),
+            }
+        };
+
+        pool_state.update_reward_infos(current_timestamp)?;
+
+        match &reward_extension {
+            Some(reward_extension) => {
+                let mut reward_extension = reward_extension.load_mut()?;
+                reward_extension
+                    .update_reward_infos(pool_state.reward_liquidity(), current_timestamp)?;
+                let reward_info = reward_extension.reward_infos[index - REWARD_NUM];
+                require_keys_eq!(
+                    ctx.accounts.reward_token_vault.key(),
+                    reward_info.token_vault
+                );
+                reward_info.check_settled(current_timestamp)?;
+
+                reward_extension.retire_reward(index - REWARD_NUM);
+                (
+                    reward_info,
+                    reward_extension.reward_generations[index - REWARD_NUM],
+                )
+            }
+            None => {
+                let reward_info = pool_state.reward_infos[index];
+                require_keys_eq!(
+                    ctx.accounts.reward_token_vault.key(),
+                    reward_info.token_vault
+                );
+                reward_info.check_settled(current_timestamp)?;
+
+                pool_state.retire_reward(index);
+                (reward_info, pool_state.reward_generations[index])
+            }
+        }
+    };
+
+    // the unclaimed reward is left in the vault, what is beyond it belongs to the pool owner
+    let amount_remaining = ctx
+        .accounts
+        .reward_token_vault
+        .amount
+        .saturating_sub(reward_info.unclaimed_reward());
+    transfer_from_pool_vault_to_user(
+        &ctx.accounts.pool_state,
+        &ctx.accounts.reward_token_vault.to_account_info(),
+        &ctx.accounts.owner_token_account.to_account_info(),
+        Some(ctx.accounts.reward_vault_mint.clone()),
+        &ctx.accounts.token_program,
+        Some(ctx.accounts.token_program_2022.to_account_info()),
+        amount_remaining,
+    )?;
+
+    emit!(RetireRewardEvent {
+        pool_state: ctx.accounts.pool_state.key(),
+        reward_index,
+        token_mint: reward_info.token_mint,
+        generation,
+        amount_remaining,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetPositionLockConfig<'info> {
+    /// The pool creator or the admin
+    #[account(
+        constraint = (owner.key() == pool_state.load()?.owner || owner.key() == crate::admin::id()) @ ErrorCode::NotApproved
+    )]
+    pub owner: Signer<'info>,
+
+    /// Set the position lock config of this pool
+    #[account(mut)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+}
+
+pub fn set_position_lock_config(
+    ctx: Context<SetPositionLockConfig>,
+    lock_reward_multiplier: u16,
+    min_lock_duration: u32,
+) -> Result<()> {
+    // zero disables new locks, the existing locks keep their multiplier until unlocked
+    require!(
+        lock_reward_multiplier == 0
+            || (lock_reward_multiplier >= LOCK_REWARD_MULTIPLIER_DENOMINATOR
+                && lock_reward_multiplier <= MAX_LOCK_REWARD_MULTIPLIER),
+        ErrorCode::InvalidLockRewardMultiplier
+    );
+    let mut pool_state = ctx.accounts.pool_state.load_mut()?;
+    pool_state.lock_reward_multiplier = lock_reward_multiplier;
+    pool_state.min_lock_duration = min_lock_duration;
+    Ok(())
+}
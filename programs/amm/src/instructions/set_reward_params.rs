@@ -48,14 +48,16 @@ pub fn set_reward_params<'a, 'b, 'c: 'info, 'info>(
     open_time: u64,
     end_time: u64,
 ) -> Result<()> {
-    assert!((reward_index as usize) < REWARD_NUM);
+    let reward_index = reward_index as usize;
+    assert!(reward_index < REWARD_NUM + EXTENSION_REWARD_NUM);
     //this checks the end_time should be greater than the open_time
     require_gt!(end_time, open_time);
     require_gt!(emissions_per_second_x64, 0);
-    let operation_state = ctx.accounts.operation_state.load()?;
-    let admin_keys = operation_state.operation_owners.to_vec();
-    let admin_operator = admin_keys.contains(&ctx.accounts.authority.key())
-        && ctx.accounts.authority.key() != Pubkey::default();
+    let operation_state = OperationState::load_with_roles(&ctx.accounts.operation_state)?;
+    let admin_operator = operation_state.validate_operator_role(
+        ctx.accounts.authority.key(),
+        OperatorRoleBitIndex::RewardAdmin,
+    );
 
     let current_timestamp = u64::try_from(Clock::get()?.unix_timestamp).unwrap();
     require_gt!(open_time, current_timestamp);
@@ -67,12 +69,45 @@ pub fn set_reward_params<'a, 'b, 'c: 'info, 'info>(
         require_keys_eq!(ctx.accounts.authority.key(), pool_state.owner);
     }
 
+    // the indexes beyond the pool reward infos are the rewards of the reward extension
+    let reward_extension = if reward_index < REWARD_NUM {
+        None
+    } else {
+        match util::load_reward_extension(&pool_state, ctx.remaining_accounts)? {
+            Some(reward_extension) => Some(reward_extension),
+            None => return err!(ErrorCode::MissingRewardExtensionAccount),
+        }
+    };
+    let reward_extension_key = reward_extension.as_ref().map(|loader| loader.key());
+
     pool_state.update_reward_infos(current_timestamp)?;
 
-    let mut reward_info = pool_state.reward_infos[reward_index as usize];
+    let mut reward_info = match &reward_extension {
+        Some(reward_extension) => {
+            let mut reward_extension = reward_extension.load_mut()?;
+            reward_extension
+                .update_reward_infos(pool_state.reward_liquidity(), current_timestamp)?;
+            reward_extension.reward_infos[reward_index - REWARD_NUM]
+        }
+        None => pool_state.reward_infos[reward_index],
+    };
     if !reward_info.initialized() {
         return err!(ErrorCode::UnInitializedRewardInfo);
     }
+    let reward_schedule = match &reward_extension {
+        Some(reward_extension) => {
+            reward_extension.load()?.reward_schedules[reward_index - REWARD_NUM]
+        }
+        None => pool_state.reward_schedules[reward_index],
+    };
+    // a scheduled reward is funded for its schedule, it goes back to a constant emission
+    // before it opens or once it ended
+    if !reward_schedule.is_constant()
+        && current_timestamp > reward_info.open_time
+        && reward_info.last_update_time != reward_info.end_time
+    {
+        return err!(ErrorCode::ScheduledRewardRunning);
+    }
 
     let reward_amount = if admin_operator {
         admin_update(
@@ -97,10 +132,24 @@ pub fn set_reward_params<'a, 'b, 'c: 'info, 'info>(
         .unwrap()
     };
 
-    pool_state.reward_infos[reward_index as usize] = reward_info;
+    match &reward_extension {
+        Some(reward_extension) => {
+            let mut reward_extension = reward_extension.load_mut()?;
+            reward_extension.reward_infos[reward_index - REWARD_NUM] = reward_info;
+            reward_extension.reward_schedules[reward_index - REWARD_NUM] =
+                RewardSchedule::default();
+        }
+        None => {
+            pool_state.reward_infos[reward_index] = reward_info;
+            pool_state.reward_schedules[reward_index] = RewardSchedule::default();
+        }
+    }
 
     if reward_amount > 0 {
-        let mut remaining_accounts = ctx.remaining_accounts.iter();
+        let mut remaining_accounts = ctx
+            .remaining_accounts
+            .iter()
+            .filter(|account_info| Some(account_info.key()) != reward_extension_key);
 
         let reward_token_vault =
             InterfaceAccount::<TokenAccount>::try_from(&remaining_accounts.next().unwrap())?;
@@ -0,0 +1,93 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use crate::util::load_reward_extension;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetRewardSchedule<'info> {
+    /// The pool creator or a reward admin operator
+    pub authority: Signer<'info>,
+
+    /// Set the reward schedule of this pool
+    #[account(mut)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// load info from the account to judge reward permission
+    #[account(
+        seeds = [
+            OPERATION_SEED.as_bytes(),
+        ],
+        bump,
+    )]
+    pub operation_state: AccountLoader<'info, OperationState>,
+    // remaining account
+    // the reward extension of the pool, required when the reward is an extension reward
+}
+
+pub fn set_reward_schedule<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, SetRewardSchedule<'info>>,
+    reward_index: u8,
+    kind: RewardScheduleKind,
+    step_period: u32,
+    end_emissions_per_second_x64: u128,
+) -> Result<()> {
+    let index = reward_index as usize;
+    require_gt!(REWARD_NUM + EXTENSION_REWARD_NUM, index);
+    let current_timestamp = u64::try_from(Clock::get()?.unix_timestamp).unwrap();
+
+    let mut pool_state = ctx.accounts.pool_state.load_mut()?;
+    let admin_operator = OperationState::load_with_roles(&ctx.accounts.operation_state)?
+        .validate_operator_role(
+            ctx.accounts.authority.key(),
+            OperatorRoleBitIndex::RewardAdmin,
+        );
+    if !admin_operator {
+        require_keys_eq!(ctx.accounts.authority.key(), pool_state.owner);
+    }
+
+    // the indexes beyond the pool reward infos are the rewards of the reward extension
+    let reward_extension = if index < REWARD_NUM {
+        None
+    } else {
+        match load_reward_extension(&pool_state, ctx.remaining_accounts)? {
+            Some(reward_extension) => Some(reward_extension),
+            None => return err!(ErrorCode::MissingRewardExtensionAccount),
+        }
+    };
+
+    let reward_info = match &reward_extension {
+        Some(reward_extension) => reward_extension.load()?.reward_infos[index - REWARD_NUM],
+        None => pool_state.reward_infos[index],
+    };
+    if !reward_info.initialized() {
+        return err!(ErrorCode::UnInitializedRewardInfo);
+    }
+    // the emitted amount of a reward only follows the schedule from its open time
+    if current_timestamp >= reward_info.open_time {
+        return err!(ErrorCode::RewardScheduleLocked);
+    }
+
+    let reward_schedule = RewardSchedule::new(
+        &reward_info,
+        kind,
+        step_period,
+        end_emissions_per_second_x64,
+    )?;
+    match &reward_extension {
+        Some(reward_extension) => {
+            reward_extension.load_mut()?.reward_schedules[index - REWARD_NUM] = reward_schedule
+        }
+        None => pool_state.reward_schedules[index] = reward_schedule,
+    }
+
+    emit!(SetRewardScheduleEvent {
+        pool_state: ctx.accounts.pool_state.key(),
+        reward_index,
+        kind: reward_schedule.kind,
+        step_period: reward_schedule.step_period,
+        start_emissions_per_second_x64: reward_info.emissions_per_second_x64,
+        end_emissions_per_second_x64: reward_schedule.end_emissions_per_second_x64,
+    });
+
+    Ok(())
+}
@@ -105,6 +105,8 @@ pub struct SwapState {
     pub fund_fee: u64,
     // the current liquidity in range
     pub liquidity: u128,
+    // the current boosted liquidity of the locked positions in range
+    pub boosted_liquidity: u128,
 }
 
 #[derive(Default)]
@@ -125,79 +127,94 @@ struct StepComputations {
     fee_amount: u64,
 }
 
-pub fn swap_internal<'b, 'info>(
+/// The tick arrays a swap walks through in the swap direction, `swap_internal` reads them from the
+/// accounts and the off-chain quoter from a snapshot
+pub trait SwapTickArrays {
+    /// The tick array of the current step
+    fn current(&mut self) -> &mut TickArrayState;
+    /// Moves to the tick array the swap starts in
+    fn move_to_first(&mut self, start_tick_index: i32) -> Result<()> {
+        self.move_to(start_tick_index)
+    }
+    /// Moves to the next initialized tick array in the swap direction
+    fn move_to(&mut self, start_tick_index: i32) -> Result<()>;
+    /// Called once the crossed tick is updated in the current tick array
+    fn on_tick_crossed(&mut self, _tick: i32) {}
+}
+
+/// The tick array accounts of a swap, in the swap direction
+struct AccountTickArrays<'a, 'q> {
+    current: RefMut<'a, TickArrayState>,
+    remaining: &'q mut VecDeque<RefMut<'a, TickArrayState>>,
+    pool_id: Pubkey,
+}
+
+impl SwapTickArrays for AccountTickArrays<'_, '_> {
+    fn current(&mut self) -> &mut TickArrayState {
+        &mut self.current
+    }
+
+    fn move_to_first(&mut self, start_tick_index: i32) -> Result<()> {
+        // find the first active tick array account
+        for _ in 0..self.remaining.len() {
+            if self.current.start_tick_index == start_tick_index {
+                break;
+            }
+            self.current = self
+                .remaining
+                .pop_front()
+                .ok_or(ErrorCode::NotEnoughTickArrayAccount)?;
+        }
+        // check the first tick_array account is owned by the pool
+        require_keys_eq!(self.current.pool_id, self.pool_id);
+        // check first tick array account is correct
+        require_eq!(
+            self.current.start_tick_index,
+            start_tick_index,
+            ErrorCode::InvalidFirstTickArrayAccount
+        );
+        Ok(())
+    }
+
+    fn move_to(&mut self, start_tick_index: i32) -> Result<()> {
+        while self.current.start_tick_index != start_tick_index {
+            self.current = self
+                .remaining
+                .pop_front()
+                .ok_or(ErrorCode::NotEnoughTickArrayAccount)?;
+            // check the tick_array account is owned by the pool
+            require_keys_eq!(self.current.pool_id, self.pool_id);
+        }
+        Ok(())
+    }
+}
+
+/// Runs the steps of a swap until the amount is used or the price limit is reached, crossing the
+/// initialized ticks on the way
+/// The fee rates of `amm_config` must include the pool overrides
+pub fn swap_steps(
     amm_config: &AmmConfig,
-    pool_state: &mut RefMut<PoolState>,
-    tick_array_states: &mut VecDeque<RefMut<TickArrayState>>,
-    observation_state: &mut RefMut<ObservationState>,
+    pool_state: &mut PoolState,
+    tick_arrays: &mut impl SwapTickArrays,
     tickarray_bitmap_extension: &Option<TickArrayBitmapExtension>,
-    amount_specified: u64,
+    state: &mut SwapState,
+    updated_reward_infos: &[RewardInfo; REWARD_NUM],
+    updated_extension_reward_infos: Option<&[RewardInfo; EXTENSION_REWARD_NUM]>,
     sqrt_price_limit_x64: u128,
     zero_for_one: bool,
     is_base_input: bool,
     block_timestamp: u32,
-) -> Result<(u64, u64)> {
-    require!(amount_specified != 0, ErrorCode::ZeroAmountSpecified);
-    if !pool_state.get_status_by_bit(PoolStatusBitIndex::Swap) {
-        return err!(ErrorCode::NotApproved);
-    }
-    require!(
-        if zero_for_one {
-            sqrt_price_limit_x64 < pool_state.sqrt_price_x64
-                && sqrt_price_limit_x64 > tick_math::MIN_SQRT_PRICE_X64
-        } else {
-            sqrt_price_limit_x64 > pool_state.sqrt_price_x64
-                && sqrt_price_limit_x64 < tick_math::MAX_SQRT_PRICE_X64
-        },
-        ErrorCode::SqrtPriceLimitOverflow
-    );
-
-    let liquidity_start = pool_state.liquidity;
-
-    let updated_reward_infos = pool_state.update_reward_infos(block_timestamp as u64)?;
-
-    //this is the swap const...
-    let mut state = SwapState {
-        amount_specified_remaining: amount_specified,
-        amount_calculated: 0,
-        sqrt_price_x64: pool_state.sqrt_price_x64,
-        tick: pool_state.tick_current,
-        fee_growth_global_x64: if zero_for_one {
-            pool_state.fee_growth_global_0_x64
-        } else {
-            pool_state.fee_growth_global_1_x64
-        },
-        fee_amount: 0,
-        protocol_fee: 0,
-        fund_fee: 0,
-        liquidity: liquidity_start,
-    };
-    // we add pool_id in the oservation struct so that we can ensure by a check that correct observation_state is being passed to the function...
-    // check observation account is owned by the pool
-    require_keys_eq!(observation_state.pool_id, pool_state.key());
-
+) -> Result<()> {
     let (mut is_match_pool_current_tick_array, first_vaild_tick_array_start_index) =
-        pool_state.get_first_initialized_tick_array(&tickarray_bitmap_extension, zero_for_one)?;
+        pool_state.get_first_initialized_tick_array(tickarray_bitmap_extension, zero_for_one)?;
     let mut current_vaild_tick_array_start_index = first_vaild_tick_array_start_index;
 
-    let mut tick_array_current = tick_array_states.pop_front().unwrap();
-    // find the first active tick array account
-    for _ in 0..tick_array_states.len() {
-        if tick_array_current.start_tick_index == current_vaild_tick_array_start_index {
-            break;
-        }
-        tick_array_current = tick_array_states
-            .pop_front()
-            .ok_or(ErrorCode::NotEnoughTickArrayAccount)?;
+    tick_arrays.move_to_first(current_vaild_tick_array_start_index)?;
+
+    let dynamic_fee_enabled = amm_config.is_dynamic_fee_enabled();
+    if dynamic_fee_enabled {
+        pool_state.update_volatility_reference(amm_config, block_timestamp);
     }
-    /// check the first tick_array account is owned by the pool
-    require_keys_eq!(tick_array_current.pool_id, pool_state.key());
-    /// check first tick array account is correct
-    require_eq!(
-        tick_array_current.start_tick_index,
-        current_vaild_tick_array_start_index,
-        ErrorCode::InvalidFirstTickArrayAccount
-    );
 
     // continue swapping as long as we haven't used the entire input/output and haven't
     // reached the price limit
@@ -221,14 +238,15 @@ pub fn swap_internal<'b, 'info>(
         let mut step = StepComputations::default();
         step.sqrt_price_start_x64 = state.sqrt_price_x64;
 
-        let mut next_initialized_tick = if let Some(tick_state) = tick_array_current
+        let mut next_initialized_tick = if let Some(tick_state) = tick_arrays
+            .current()
             .next_initialized_tick(state.tick, pool_state.tick_spacing, zero_for_one)?
         {
             Box::new(*tick_state)
         } else {
             if !is_match_pool_current_tick_array {
                 is_match_pool_current_tick_array = true;
-                Box::new(*tick_array_current.first_initialized_tick(zero_for_one)?)
+                Box::new(*tick_arrays.current().first_initialized_tick(zero_for_one)?)
             } else {
                 Box::new(TickState::default())
             }
@@ -238,12 +256,12 @@ pub fn swap_internal<'b, 'info>(
             "next_initialized_tick, status:{}, tick_index:{}, tick_array_current:{}",
             next_initialized_tick.is_initialized(),
             identity(next_initialized_tick.tick),
-            tick_array_current.key().to_string(),
+            tick_arrays.current().key().to_string(),
         );
         if !next_initialized_tick.is_initialized() {
             let next_initialized_tickarray_index = pool_state
                 .next_initialized_tick_array_start_index(
-                    &tickarray_bitmap_extension,
+                    tickarray_bitmap_extension,
                     current_vaild_tick_array_start_index,
                     zero_for_one,
                 )?;
@@ -251,16 +269,11 @@ pub fn swap_internal<'b, 'info>(
                 return err!(ErrorCode::LiquidityInsufficient);
             }
 
-            while tick_array_current.start_tick_index != next_initialized_tickarray_index.unwrap() {
-                tick_array_current = tick_array_states
-                    .pop_front()
-                    .ok_or(ErrorCode::NotEnoughTickArrayAccount)?;
-                // check the tick_array account is owned by the pool
-                require_keys_eq!(tick_array_current.pool_id, pool_state.key());
-            }
+            tick_arrays.move_to(next_initialized_tickarray_index.unwrap())?;
             current_vaild_tick_array_start_index = next_initialized_tickarray_index.unwrap();
 
-            let first_initialized_tick = tick_array_current.first_initialized_tick(zero_for_one)?;
+            let first_initialized_tick =
+                tick_arrays.current().first_initialized_tick(zero_for_one)?;
             next_initialized_tick = Box::new(*first_initialized_tick);
         }
         step.tick_next = next_initialized_tick.tick;
@@ -298,12 +311,16 @@ pub fn swap_internal<'b, 'info>(
             state.liquidity,
             state.amount_specified_remaining
         );
+        // the dynamic fee of the step grows with the ticks moved since the volatility reference
+        if dynamic_fee_enabled {
+            pool_state.update_volatility_accumulator(state.tick);
+        }
         let swap_step = swap_math::compute_swap_step(
             step.sqrt_price_start_x64,
             target_price,
             state.liquidity,
             state.amount_specified_remaining,
-            amm_config.trade_fee_rate,
+            amm_config.get_trade_fee_rate(pool_state.volatility_accumulator),
             is_base_input,
             zero_for_one,
             block_timestamp,
@@ -406,19 +423,27 @@ pub fn swap_internal<'b, 'info>(
                     } else {
                         state.fee_growth_global_x64
                     },
-                    &updated_reward_infos,
+                    updated_reward_infos,
+                    updated_extension_reward_infos
+                        .ok_or(ErrorCode::MissingRewardExtensionAccount)?,
+                    block_timestamp,
                 );
                 // update tick_state to tick_array account
-                tick_array_current.update_tick_state(
+                tick_arrays.current().update_tick_state(
                     next_initialized_tick.tick,
                     pool_state.tick_spacing.into(),
                     *next_initialized_tick,
                 )?;
+                tick_arrays.on_tick_crossed(step.tick_next);
 
+                let mut boosted_liquidity_net = next_initialized_tick.boosted_liquidity_net;
                 if zero_for_one {
                     liquidity_net = liquidity_net.neg();
+                    boosted_liquidity_net = boosted_liquidity_net.neg();
                 }
                 state.liquidity = liquidity_math::add_delta(state.liquidity, liquidity_net)?;
+                state.boosted_liquidity =
+                    liquidity_math::add_delta(state.boosted_liquidity, boosted_liquidity_net)?;
             }
 
             state.tick = if zero_for_one {
@@ -461,10 +486,107 @@ pub fn swap_internal<'b, 'info>(
         //     zero_for_one,
         // });
     }
+    Ok(())
+}
+
+pub fn swap_internal<'b, 'info>(
+    amm_config: &AmmConfig,
+    pool_state: &mut RefMut<PoolState>,
+    tick_array_states: &mut VecDeque<RefMut<TickArrayState>>,
+    observation_state: &mut RefMut<ObservationState>,
+    observation_extension: &mut [Observation],
+    tickarray_bitmap_extension: &Option<TickArrayBitmapExtension>,
+    reward_extension: Option<&mut PoolRewardExtension>,
+    amount_specified: u64,
+    sqrt_price_limit_x64: u128,
+    zero_for_one: bool,
+    is_base_input: bool,
+    block_timestamp: u32,
+) -> Result<(u64, u64)> {
+    require!(amount_specified != 0, ErrorCode::ZeroAmountSpecified);
+    if !pool_state.get_status_by_bit_with_config(PoolStatusBitIndex::Swap, Some(amm_config)) {
+        return err!(ErrorCode::NotApproved);
+    }
+    require!(
+        if zero_for_one {
+            sqrt_price_limit_x64 < pool_state.sqrt_price_x64
+                && sqrt_price_limit_x64 > tick_math::MIN_SQRT_PRICE_X64
+        } else {
+            sqrt_price_limit_x64 > pool_state.sqrt_price_x64
+                && sqrt_price_limit_x64 < tick_math::MAX_SQRT_PRICE_X64
+        },
+        ErrorCode::SqrtPriceLimitOverflow
+    );
+
+    // the fee overrides of the pool replace the fee rates of the config
+    let amm_config = &pool_state.effective_amm_config(amm_config);
+
+    let liquidity_start = pool_state.liquidity;
+    let boosted_liquidity_start = pool_state.boosted_liquidity;
+
+    let updated_reward_infos = pool_state.update_reward_infos(block_timestamp as u64)?;
+    // without the reward extension the swap can't cross initialized ticks, so the extension
+    // rewards accrue with the same liquidity when the extension is passed again
+    let updated_extension_reward_infos = match reward_extension {
+        Some(reward_extension) => Some(
+            reward_extension
+                .update_reward_infos(pool_state.reward_liquidity(), block_timestamp as u64)?,
+        ),
+        None if pool_state.reward_extension != 0 => None,
+        None => Some([RewardInfo::default(); EXTENSION_REWARD_NUM]),
+    };
+
+    //this is the swap const...
+    let mut state = SwapState {
+        amount_specified_remaining: amount_specified,
+        amount_calculated: 0,
+        sqrt_price_x64: pool_state.sqrt_price_x64,
+        tick: pool_state.tick_current,
+        fee_growth_global_x64: if zero_for_one {
+            pool_state.fee_growth_global_0_x64
+        } else {
+            pool_state.fee_growth_global_1_x64
+        },
+        fee_amount: 0,
+        protocol_fee: 0,
+        fund_fee: 0,
+        liquidity: liquidity_start,
+        boosted_liquidity: boosted_liquidity_start,
+    };
+    // we add pool_id in the oservation struct so that we can ensure by a check that correct observation_state is being passed to the function...
+    // check observation account is owned by the pool
+    require_keys_eq!(observation_state.pool_id, pool_state.key());
+
+    let mut tick_arrays = AccountTickArrays {
+        current: tick_array_states.pop_front().unwrap(),
+        remaining: tick_array_states,
+        pool_id: pool_state.key(),
+    };
+    swap_steps(
+        amm_config,
+        pool_state,
+        &mut tick_arrays,
+        tickarray_bitmap_extension,
+        &mut state,
+        &updated_reward_infos,
+        updated_extension_reward_infos.as_ref(),
+        sqrt_price_limit_x64,
+        zero_for_one,
+        is_base_input,
+        block_timestamp,
+    )?;
+
+    // accumulate the seconds per liquidity with the liquidity before the swap
+    pool_state.update_seconds_per_liquidity(u64::from(block_timestamp));
     // update tick
     if state.tick != pool_state.tick_current {
         // update the previous tick to the observation
-        observation_state.update(block_timestamp, pool_state.tick_current);
+        observation_state.update(
+            observation_extension,
+            block_timestamp,
+            pool_state.tick_current,
+            pool_state.seconds_per_liquidity_global_x64,
+        );
         pool_state.tick_current = state.tick;
     }
     pool_state.sqrt_price_x64 = state.sqrt_price_x64;
@@ -472,6 +594,9 @@ pub fn swap_internal<'b, 'info>(
     if liquidity_start != state.liquidity {
         pool_state.liquidity = state.liquidity;
     }
+    if boosted_liquidity_start != state.boosted_liquidity {
+        pool_state.boosted_liquidity = state.boosted_liquidity;
+    }
 
     let (amount_0, amount_1) = if zero_for_one == is_base_input {
         (
@@ -573,6 +698,19 @@ pub fn exact_internal<'b, 'c: 'info, 'info>(
         zero_for_one = ctx.input_vault.mint == pool_state.token_mint_0;
 
         require_gt!(block_timestamp, pool_state.open_time);
+        // during the launch phase only the addresses of the swap allowlist can swap
+        let swap_allowlist_key = if block_timestamp < pool_state.launch_end_time {
+            let swap_allowlist_key =
+                SwapAllowlistState::key(pool_state.key(), pool_state.swap_allowlist_bump)?;
+            SwapAllowlistState::check_swapper(
+                swap_allowlist_key,
+                ctx.signer.key(),
+                remaining_accounts,
+            )?;
+            Some(swap_allowlist_key)
+        } else {
+            None
+        };
 
         require!(
             if zero_for_one {
@@ -589,6 +727,14 @@ pub fn exact_internal<'b, 'c: 'info, 'info>(
         let tick_array_states = &mut VecDeque::new();
         tick_array_states.push_back(ctx.tick_array_state.load_mut()?);
 
+        // the reward extension is optional, the swap only needs it to cross initialized ticks
+        let reward_extension_loader = find_reward_extension(pool_state, remaining_accounts)?;
+        let reward_extension_key = reward_extension_loader.as_ref().map(|loader| loader.key());
+        let mut reward_extension = match &reward_extension_loader {
+            Some(loader) => Some(loader.load_mut()?),
+            None => None,
+        };
+
         let tick_array_bitmap_extension_key = TickArrayBitmapExtension::key(pool_state.key());
         for account_info in remaining_accounts.into_iter() {
             if account_info.key().eq(&tick_array_bitmap_extension_key) {
@@ -599,15 +745,24 @@ pub fn exact_internal<'b, 'c: 'info, 'info>(
                 );
                 continue;
             }
+            if Some(account_info.key()) == swap_allowlist_key
+                || Some(account_info.key()) == reward_extension_key
+            {
+                continue;
+            }
             tick_array_states.push_back(AccountLoad::load_data_mut(account_info)?);
         }
 
+        let (mut observation_state, mut observation_extension) =
+            ObservationState::load_mut_with_extension(ctx.observation_state)?;
         (amount_0, amount_1) = swap_internal(
             &ctx.amm_config,
             pool_state,
             tick_array_states,
-            &mut ctx.observation_state.load_mut()?,
+            &mut observation_state,
+            &mut observation_extension,
             &tickarray_bitmap_extension,
+            reward_extension.as_deref_mut(),
             amount_specified,
             if sqrt_price_limit_x64 == 0 {
                 if zero_for_one {
@@ -716,7 +871,10 @@ pub fn exact_internal<'b, 'c: 'info, 'info>(
         zero_for_one,
         sqrt_price_x64: pool_state.sqrt_price_x64,
         liquidity: pool_state.liquidity,
-        tick: pool_state.tick_current
+        tick: pool_state.tick_current,
+        trade_fee_rate: pool_state
+            .effective_amm_config(&ctx.amm_config)
+            .get_trade_fee_rate(pool_state.volatility_accumulator),
     });
     if zero_for_one {
         require_gt!(swap_price_before, pool_state.sqrt_price_x64);
@@ -958,6 +1116,8 @@ mod swap_test {
                             0,
                             false,
                             &[RewardInfo::default(); 3],
+                            &[RewardInfo::default(); EXTENSION_REWARD_NUM],
+                            0,
                         )
                         .unwrap();
 
@@ -979,6 +1139,8 @@ mod swap_test {
                             0,
                             false,
                             &[RewardInfo::default(); 3],
+                            &[RewardInfo::default(); EXTENSION_REWARD_NUM],
+                            0,
                         )
                         .unwrap();
                 }
@@ -1006,6 +1168,8 @@ mod swap_test {
                             0,
                             true,
                             &[RewardInfo::default(); 3],
+                            &[RewardInfo::default(); EXTENSION_REWARD_NUM],
+                            0,
                         )
                         .unwrap();
 
@@ -1028,6 +1192,8 @@ mod swap_test {
                             0,
                             true,
                             &[RewardInfo::default(); 3],
+                            &[RewardInfo::default(); EXTENSION_REWARD_NUM],
+                            0,
                         )
                         .unwrap();
                 }
@@ -1121,7 +1287,9 @@ mod swap_test {
                 &mut pool_state.borrow_mut(),
                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                 &mut observation_state.borrow_mut(),
+                &mut [],
                 &None,
+                None,
                 12188240002,
                 3049500711113990606,
                 true,
@@ -1152,7 +1320,9 @@ mod swap_test {
                 &mut pool_state.borrow_mut(),
                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                 &mut observation_state.borrow_mut(),
+                &mut [],
                 &None,
+                None,
                 121882400020,
                 3049500711113990606,
                 true,
@@ -1180,7 +1350,9 @@ mod swap_test {
                 &mut pool_state.borrow_mut(),
                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                 &mut observation_state.borrow_mut(),
+                &mut [],
                 &None,
+                None,
                 60941200010,
                 3049500711113990606,
                 true,
@@ -1237,7 +1409,9 @@ mod swap_test {
                 &mut pool_state.borrow_mut(),
                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                 &mut observation_state.borrow_mut(),
+                &mut [],
                 &None,
+                None,
                 477470480,
                 3049500711113990606,
                 true,
@@ -1268,7 +1442,9 @@ mod swap_test {
                 &mut pool_state.borrow_mut(),
                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                 &mut observation_state.borrow_mut(),
+                &mut [],
                 &None,
+                None,
                 4751002622,
                 3049500711113990606,
                 true,
@@ -1296,7 +1472,9 @@ mod swap_test {
                 &mut pool_state.borrow_mut(),
                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                 &mut observation_state.borrow_mut(),
+                &mut [],
                 &None,
+                None,
                 2358130642,
                 3049500711113990606,
                 true,
@@ -1353,7 +1531,9 @@ mod swap_test {
                 &mut pool_state.borrow_mut(),
                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                 &mut observation_state.borrow_mut(),
+                &mut [],
                 &None,
+                None,
                 887470480,
                 5882283448660210779,
                 false,
@@ -1383,7 +1563,9 @@ mod swap_test {
                 &mut pool_state.borrow_mut(),
                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                 &mut observation_state.borrow_mut(),
+                &mut [],
                 &None,
+                None,
                 3087470480,
                 5882283448660210779,
                 false,
@@ -1412,7 +1594,9 @@ mod swap_test {
                 &mut pool_state.borrow_mut(),
                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                 &mut observation_state.borrow_mut(),
+                &mut [],
                 &None,
+                None,
                 200941200010,
                 5882283448660210779,
                 false,
@@ -1469,7 +1653,9 @@ mod swap_test {
                 &mut pool_state.borrow_mut(),
                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                 &mut observation_state.borrow_mut(),
+                &mut [],
                 &None,
+                None,
                 22796232052,
                 5882283448660210779,
                 false,
@@ -1499,7 +1685,9 @@ mod swap_test {
                 &mut pool_state.borrow_mut(),
                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                 &mut observation_state.borrow_mut(),
+                &mut [],
                 &None,
+                None,
                 79023558189,
                 5882283448660210779,
                 false,
@@ -1528,7 +1716,9 @@ mod swap_test {
                 &mut pool_state.borrow_mut(),
                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                 &mut observation_state.borrow_mut(),
+                &mut [],
                 &None,
+                None,
                 4315086194758,
                 5882283448660210779,
                 false,
@@ -1578,7 +1768,9 @@ mod swap_test {
                 &mut pool_state.borrow_mut(),
                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                 &mut observation_state.borrow_mut(),
+                &mut [],
                 &None,
+                None,
                 12188240002,
                 tick_math::get_sqrt_price_at_tick(-32400).unwrap(),
                 true,
@@ -1623,7 +1815,9 @@ mod swap_test {
                 &mut pool_state.borrow_mut(),
                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                 &mut observation_state.borrow_mut(),
+                &mut [],
                 &None,
+                None,
                 12188240002,
                 tick_math::get_sqrt_price_at_tick(-28860).unwrap(),
                 false,
@@ -1668,7 +1862,9 @@ mod swap_test {
                 &mut pool_state.borrow_mut(),
                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                 &mut observation_state.borrow_mut(),
+                &mut [],
                 &None,
+                None,
                 12188240002,
                 tick_math::get_sqrt_price_at_tick(-32400).unwrap(),
                 true,
@@ -1709,7 +1905,9 @@ mod swap_test {
             &mut pool_state.borrow_mut(),
             &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
             &mut observation_state.borrow_mut(),
+            &mut [],
             &None,
+            None,
             25,
             tick_math::get_sqrt_price_at_tick(-32400).unwrap(),
             true,
@@ -1732,7 +1930,9 @@ mod swap_test {
             &mut pool_state.borrow_mut(),
             &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
             &mut observation_state.borrow_mut(),
+            &mut [],
             &None,
+            None,
             3,
             tick_math::get_sqrt_price_at_tick(-32400).unwrap(),
             true,
@@ -1758,7 +1958,9 @@ mod swap_test {
             &mut pool_state.borrow_mut(),
             &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
             &mut observation_state.borrow_mut(),
+            &mut [],
             &None,
+            None,
             50,
             tick_math::get_sqrt_price_at_tick(-32400).unwrap(),
             true,
@@ -1811,7 +2013,9 @@ mod swap_test {
                 &mut pool_state.borrow_mut(),
                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                 &mut observation_state.borrow_mut(),
+                &mut [],
                 &None,
+                None,
                 27,
                 tick_math::get_sqrt_price_at_tick(-32400).unwrap(),
                 true,
@@ -1838,7 +2042,9 @@ mod swap_test {
                 &mut pool_state.borrow_mut(),
                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                 &mut observation_state.borrow_mut(),
+                &mut [],
                 &None,
+                None,
                 1,
                 tick_math::get_sqrt_price_at_tick(-32400).unwrap(),
                 true,
@@ -1863,7 +2069,9 @@ mod swap_test {
                 &mut pool_state.borrow_mut(),
                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                 &mut observation_state.borrow_mut(),
+                &mut [],
                 &None,
+                None,
                 10,
                 tick_math::get_sqrt_price_at_tick(-28800).unwrap(),
                 false,
@@ -1924,7 +2132,9 @@ mod swap_test {
                 &mut pool_state.borrow_mut(),
                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                 &mut observation_state.borrow_mut(),
+                &mut [],
                 &Some(bitmap_extension_state),
+                None,
                 amount_specified,
                 tick_math::MIN_SQRT_PRICE_X64 + 1,
                 zero_for_one,
@@ -1978,7 +2188,9 @@ mod swap_test {
                 &mut pool_state.borrow_mut(),
                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                 &mut observation_state.borrow_mut(),
+                &mut [],
                 &Some(bitmap_extension_state),
+                None,
                 amount_specified,
                 tick_math::MIN_SQRT_PRICE_X64 + 1,
                 zero_for_one,
@@ -2032,7 +2244,9 @@ mod swap_test {
                 &mut pool_state.borrow_mut(),
                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                 &mut observation_state.borrow_mut(),
+                &mut [],
                 &Some(bitmap_extension_state),
+                None,
                 amount_specified,
                 tick_math::MAX_SQRT_PRICE_X64 - 1,
                 zero_for_one,
@@ -2085,7 +2299,9 @@ mod swap_test {
                 &mut pool_state.borrow_mut(),
                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                 &mut observation_state.borrow_mut(),
+                &mut [],
                 &Some(bitmap_extension_state),
+                None,
                 amount_specified,
                 tick_math::MAX_SQRT_PRICE_X64 - 1,
                 zero_for_one,
@@ -2142,7 +2358,9 @@ mod swap_test {
                 &mut pool_state.borrow_mut(),
                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                 &mut observation_state.borrow_mut(),
+                &mut [],
                 &Some(bitmap_extension_state),
+                None,
                 amount_specified,
                 tick_math::MIN_SQRT_PRICE_X64 + 1,
                 zero_for_one,
@@ -2196,7 +2414,9 @@ mod swap_test {
                 &mut pool_state.borrow_mut(),
                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                 &mut observation_state.borrow_mut(),
+                &mut [],
                 &Some(bitmap_extension_state),
+                None,
                 amount_specified,
                 tick_math::MIN_SQRT_PRICE_X64 + 1,
                 zero_for_one,
@@ -2250,7 +2470,9 @@ mod swap_test {
                 &mut pool_state.borrow_mut(),
                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                 &mut observation_state.borrow_mut(),
+                &mut [],
                 &Some(bitmap_extension_state),
+                None,
                 amount_specified,
                 tick_math::MAX_SQRT_PRICE_X64 - 1,
                 zero_for_one,
@@ -2303,7 +2525,9 @@ mod swap_test {
                 &mut pool_state.borrow_mut(),
                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                 &mut observation_state.borrow_mut(),
+                &mut [],
                 &Some(bitmap_extension_state),
+                None,
                 amount_specified,
                 tick_math::MAX_SQRT_PRICE_X64 - 1,
                 zero_for_one,
@@ -2356,7 +2580,9 @@ mod swap_test {
                         &mut pool_state.borrow_mut(),
                         &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                         &mut observation_state.borrow_mut(),
+                        &mut [],
                         &Some(bitmap_extension_state),
+                        None,
                         amount_specified,
                         tick_math::MIN_SQRT_PRICE_X64 + 1,
                         zero_for_one,
@@ -2378,7 +2604,9 @@ mod swap_test {
                             &mut pool_state.borrow_mut(),
                             &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                             &mut observation_state.borrow_mut(),
+                            &mut [],
                             &Some(bitmap_extension_state),
+                            None,
                             amount_specified,
                             tick_math::MIN_SQRT_PRICE_X64 + 1,
                             zero_for_one,
@@ -2402,7 +2630,9 @@ mod swap_test {
                                 &mut pool_state.borrow_mut(),
                                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                                 &mut observation_state.borrow_mut(),
+                                &mut [],
                                 &Some(bitmap_extension_state),
+                                None,
                                 amount_specified,
                                 tick_math::MIN_SQRT_PRICE_X64 + 1,
                                 zero_for_one,
@@ -2447,7 +2677,9 @@ mod swap_test {
                         &mut pool_state.borrow_mut(),
                         &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                         &mut observation_state.borrow_mut(),
+                        &mut [],
                         &Some(bitmap_extension_state),
+                        None,
                         amount_specified,
                         tick_math::MIN_SQRT_PRICE_X64 + 1,
                         zero_for_one,
@@ -2469,7 +2701,9 @@ mod swap_test {
                             &mut pool_state.borrow_mut(),
                             &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                             &mut observation_state.borrow_mut(),
+                            &mut [],
                             &Some(bitmap_extension_state),
+                            None,
                             amount_specified,
                             tick_math::MIN_SQRT_PRICE_X64 + 1,
                             zero_for_one,
@@ -2493,7 +2727,9 @@ mod swap_test {
                                 &mut pool_state.borrow_mut(),
                                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                                 &mut observation_state.borrow_mut(),
+                                &mut [],
                                 &Some(bitmap_extension_state),
+                                None,
                                 amount_specified,
                                 tick_math::MIN_SQRT_PRICE_X64 + 1,
                                 zero_for_one,
@@ -2539,7 +2775,9 @@ mod swap_test {
                         &mut pool_state.borrow_mut(),
                         &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                         &mut observation_state.borrow_mut(),
+                        &mut [],
                         &Some(bitmap_extension_state),
+                        None,
                         amount_specified,
                         tick_math::MAX_SQRT_PRICE_X64 - 1,
                         zero_for_one,
@@ -2562,7 +2800,9 @@ mod swap_test {
                             &mut pool_state.borrow_mut(),
                             &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                             &mut observation_state.borrow_mut(),
+                            &mut [],
                             &Some(bitmap_extension_state),
+                            None,
                             amount_specified,
                             tick_math::MAX_SQRT_PRICE_X64 - 1,
                             zero_for_one,
@@ -2586,7 +2826,9 @@ mod swap_test {
                                 &mut pool_state.borrow_mut(),
                                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                                 &mut observation_state.borrow_mut(),
+                                &mut [],
                                 &Some(bitmap_extension_state),
+                                None,
                                 amount_specified,
                                 tick_math::MAX_SQRT_PRICE_X64 - 1,
                                 zero_for_one,
@@ -2630,7 +2872,9 @@ mod swap_test {
                         &mut pool_state.borrow_mut(),
                         &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                         &mut observation_state.borrow_mut(),
+                        &mut [],
                         &Some(bitmap_extension_state),
+                        None,
                         amount_specified,
                         tick_math::MAX_SQRT_PRICE_X64 - 1,
                         zero_for_one,
@@ -2652,7 +2896,9 @@ mod swap_test {
                             &mut pool_state.borrow_mut(),
                             &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                             &mut observation_state.borrow_mut(),
+                            &mut [],
                             &Some(bitmap_extension_state),
+                            None,
                             amount_specified,
                             tick_math::MAX_SQRT_PRICE_X64 - 1,
                             zero_for_one,
@@ -2676,7 +2922,9 @@ mod swap_test {
                                 &mut pool_state.borrow_mut(),
                                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                                 &mut observation_state.borrow_mut(),
+                                &mut [],
                                 &Some(bitmap_extension_state),
+                                None,
                                 amount_specified,
                                 tick_math::MAX_SQRT_PRICE_X64 - 1,
                                 zero_for_one,
@@ -2691,4 +2939,179 @@ mod swap_test {
             }
         }
     }
+
+    #[cfg(test)]
+    mod quoter_test {
+        use super::*;
+        use crate::quoter::{quote_swap, SwapQuote, SwapSnapshot};
+
+        fn assert_quote_matches_swap(
+            zero_for_one: bool,
+            is_base_input: bool,
+            amount: u64,
+            dynamic_fee_control: u32,
+        ) -> SwapQuote {
+            let (
+                mut amm_config,
+                pool_state,
+                tick_array_states,
+                observation_state,
+                bitmap_extension_state,
+                _sum_amount_0,
+                _sum_amount_1,
+            ) = setup_swap_test(
+                0,
+                10,
+                vec![
+                    OpenPositionParam {
+                        amount_0: 100_000_000,
+                        amount_1: 100_000_000,
+                        tick_lower: -1000,
+                        tick_upper: 1000,
+                    },
+                    OpenPositionParam {
+                        amount_0: 50_000_000,
+                        amount_1: 50_000_000,
+                        tick_lower: -300,
+                        tick_upper: 200,
+                    },
+                    OpenPositionParam {
+                        amount_0: 10_000_000,
+                        amount_1: 10_000_000,
+                        tick_lower: -20000,
+                        tick_upper: 20000,
+                    },
+                ],
+                zero_for_one,
+            );
+            amm_config.protocol_fee_rate = 120000;
+            amm_config.fund_fee_rate = 40000;
+            amm_config.dynamic_fee_control = dynamic_fee_control;
+            amm_config.max_trade_fee_rate = 100000;
+
+            let pool_before = *pool_state.borrow();
+            let tick_arrays: Vec<TickArrayState> = tick_array_states
+                .iter()
+                .map(|tick_array| *tick_array.borrow())
+                .collect();
+            let quote = quote_swap(
+                &SwapSnapshot {
+                    amm_config: &amm_config,
+                    pool_state: &pool_before,
+                    tickarray_bitmap_extension: &Some(bitmap_extension_state),
+                    tick_arrays: &tick_arrays,
+                },
+                amount,
+                None,
+                zero_for_one,
+                is_base_input,
+                oracle::block_timestamp_mock() as u32,
+            )
+            .unwrap();
+
+            let (amount_0, amount_1) = swap_internal(
+                &amm_config,
+                &mut pool_state.borrow_mut(),
+                &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
+                &mut observation_state.borrow_mut(),
+                &mut [],
+                &Some(bitmap_extension_state),
+                None,
+                amount,
+                if zero_for_one {
+                    tick_math::MIN_SQRT_PRICE_X64 + 1
+                } else {
+                    tick_math::MAX_SQRT_PRICE_X64 - 1
+                },
+                zero_for_one,
+                is_base_input,
+                oracle::block_timestamp_mock() as u32,
+            )
+            .unwrap();
+
+            let pool_after = pool_state.borrow();
+            let (amount_in, amount_out) = if zero_for_one {
+                (amount_0, amount_1)
+            } else {
+                (amount_1, amount_0)
+            };
+            let (lp_fee, protocol_fee, fund_fee) = if zero_for_one {
+                (
+                    pool_after.total_fees_token_0 - pool_before.total_fees_token_0,
+                    pool_after.protocol_fees_token_0 - pool_before.protocol_fees_token_0,
+                    pool_after.fund_fees_token_0 - pool_before.fund_fees_token_0,
+                )
+            } else {
+                (
+                    pool_after.total_fees_token_1 - pool_before.total_fees_token_1,
+                    pool_after.protocol_fees_token_1 - pool_before.protocol_fees_token_1,
+                    pool_after.fund_fees_token_1 - pool_before.fund_fees_token_1,
+                )
+            };
+            assert_eq!(quote.amount_in, amount_in);
+            assert_eq!(quote.amount_out, amount_out);
+            assert_eq!(quote.lp_fee, lp_fee);
+            assert_eq!(quote.protocol_fee, protocol_fee);
+            assert_eq!(quote.fund_fee, fund_fee);
+            assert!(quote.protocol_fee > 0 && quote.fund_fee > 0);
+            assert_eq!(quote.sqrt_price_x64, { pool_after.sqrt_price_x64 });
+            assert_eq!(quote.tick, { pool_after.tick_current });
+            assert_eq!(quote.liquidity, { pool_after.liquidity });
+            assert_eq!(quote.boosted_liquidity, { pool_after.boosted_liquidity });
+            assert_eq!(
+                quote.trade_fee_rate,
+                amm_config.get_trade_fee_rate(pool_after.volatility_accumulator)
+            );
+            assert_eq!(
+                quote.tick_array_keys.len(),
+                quote.tick_array_start_indexes.len()
+            );
+            quote
+        }
+
+        #[test]
+        fn zero_for_one_base_input_quote_test() {
+            let quote = assert_quote_matches_swap(true, true, 170_000_000, 0);
+            // the swap crosses the ticks of the two narrow positions
+            assert_eq!(quote.ticks_crossed.len(), 2);
+            assert_eq!(quote.trade_fee_rate, 1000);
+        }
+
+        #[test]
+        fn zero_for_one_base_output_quote_test() {
+            let quote = assert_quote_matches_swap(true, false, 155_000_000, 0);
+            // the swap crosses the ticks of the two narrow positions
+            assert_eq!(quote.ticks_crossed.len(), 2);
+            assert_eq!(quote.trade_fee_rate, 1000);
+        }
+
+        #[test]
+        fn one_for_zero_base_input_quote_test() {
+            let quote = assert_quote_matches_swap(false, true, 150_000_000, 0);
+            // the swap crosses the ticks of the two narrow positions
+            assert_eq!(quote.ticks_crossed.len(), 2);
+            assert_eq!(quote.trade_fee_rate, 1000);
+        }
+
+        #[test]
+        fn one_for_zero_base_output_quote_test() {
+            let quote = assert_quote_matches_swap(false, false, 138_000_000, 0);
+            // the swap crosses the ticks of the two narrow positions
+            assert_eq!(quote.ticks_crossed.len(), 2);
+            assert_eq!(quote.trade_fee_rate, 1000);
+        }
+
+        #[test]
+        fn dynamic_fee_quote_test() {
+            let static_fee_quote = assert_quote_matches_swap(true, true, 170_000_000, 0);
+            let quote = assert_quote_matches_swap(true, true, 170_000_000, 10);
+            // the fee rate grows with the tick spacings moved by the swap
+            assert!(quote.trade_fee_rate > 1000);
+            assert!(quote.fee_amount() > static_fee_quote.fee_amount());
+            assert!(quote.amount_out < static_fee_quote.amount_out);
+
+            let quote = assert_quote_matches_swap(false, false, 138_000_000, 10);
+            assert!(quote.trade_fee_rate > 1000);
+        }
+    }
 }
@@ -0,0 +1,127 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use crate::swap_router_base_in::SwapRouterBaseIn;
+use crate::swap_v2::{settle_swap_v2, swap_pool_v2, SwapSingleV2};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+/// The number of accounts every hop starts with, before its tickarray_bitmap_extension and tick arrays:
+/// amm_config, pool_state, output_token_account, input_vault, output_vault, output_token_mint, observation_state
+pub const ROUTE_HOP_FIXED_ACCOUNTS_LEN: usize = 7;
+
+pub fn swap_router_base_in_v2<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, SwapRouterBaseIn<'info>>,
+    amount_in: u64,
+    amount_out_minimum: u64,
+    hop_account_counts: Vec<u8>,
+    sqrt_price_limits_x64: Vec<u128>,
+) -> Result<()> {
+    require!(
+        !hop_account_counts.is_empty()
+            && (sqrt_price_limits_x64.is_empty()
+                || sqrt_price_limits_x64.len() == hop_account_counts.len()),
+        ErrorCode::InvalidRouteAccounts
+    );
+    require_eq!(
+        hop_account_counts
+            .iter()
+            .map(|count| usize::from(*count))
+            .sum::<usize>(),
+        ctx.remaining_accounts.len(),
+        ErrorCode::InvalidRouteAccounts
+    );
+
+    let mut amount_in_internal = amount_in;
+    let mut input_token_account = Box::new(ctx.accounts.input_token_account.clone());
+    let mut input_token_mint = Box::new(ctx.accounts.input_token_mint.clone());
+    let mut accounts: &'c [AccountInfo<'info>] = ctx.remaining_accounts;
+    let mut hops = Vec::with_capacity(hop_account_counts.len());
+    for (i, hop_account_count) in hop_account_counts.iter().enumerate() {
+        let hop_account_count = usize::from(*hop_account_count);
+        require_gte!(
+            hop_account_count,
+            ROUTE_HOP_FIXED_ACCOUNTS_LEN,
+            ErrorCode::InvalidRouteAccounts
+        );
+        let (hop_accounts, next_hop_accounts) = accounts.split_at(hop_account_count);
+        accounts = next_hop_accounts;
+
+        let mut remaining_accounts = hop_accounts.iter();
+        let amm_config = Box::new(Account::<AmmConfig>::try_from(
+            remaining_accounts.next().unwrap(),
+        )?);
+        let pool_state_loader =
+            AccountLoader::<PoolState>::try_from(remaining_accounts.next().unwrap())?;
+        let output_token_account = Box::new(InterfaceAccount::<TokenAccount>::try_from(
+            &remaining_accounts.next().unwrap(),
+        )?);
+        let input_vault = Box::new(InterfaceAccount::<TokenAccount>::try_from(
+            remaining_accounts.next().unwrap(),
+        )?);
+        let output_vault = Box::new(InterfaceAccount::<TokenAccount>::try_from(
+            remaining_accounts.next().unwrap(),
+        )?);
+        let output_token_mint = Box::new(InterfaceAccount::<Mint>::try_from(
+            remaining_accounts.next().unwrap(),
+        )?);
+        let observation_state =
+            AccountLoader::<ObservationState>::try_from(remaining_accounts.next().unwrap())?;
+
+        {
+            let pool_state = pool_state_loader.load()?;
+            // check observation account is owned by the pool
+            require_keys_eq!(pool_state.observation_key, observation_state.key());
+            // check ammConfig account is associate with the pool
+            require_keys_eq!(pool_state.amm_config, amm_config.key());
+        }
+
+        let swap_accounts = &mut SwapSingleV2 {
+            payer: ctx.accounts.payer.clone(),
+            amm_config,
+            input_token_account: input_token_account.clone(),
+            pool_state: pool_state_loader,
+            output_token_account: output_token_account.clone(),
+            input_vault,
+            output_vault,
+            input_vault_mint: input_token_mint.clone(),
+            output_vault_mint: output_token_mint.clone(),
+            observation_state,
+            token_program: ctx.accounts.token_program.clone(),
+            token_program_2022: ctx.accounts.token_program_2022.clone(),
+            memo_program: ctx.accounts.memo_program.clone(),
+        };
+        let amounts = swap_pool_v2(
+            swap_accounts,
+            remaining_accounts.as_slice(),
+            amount_in_internal,
+            sqrt_price_limits_x64.get(i).copied().unwrap_or(0),
+            true,
+        )?;
+        settle_swap_v2(swap_accounts, &amounts)?;
+
+        hops.push(RouteSwapHop {
+            pool_state: swap_accounts.pool_state.key(),
+            amount_in: amounts.input_transfer_amount(),
+            amount_out: amounts.output_amount(),
+            sqrt_price_x64: swap_accounts.pool_state.load()?.sqrt_price_x64,
+        });
+        amount_in_internal = amounts.output_amount();
+        // output token is the new swap input token
+        input_token_account = output_token_account;
+        input_token_mint = output_token_mint;
+    }
+    require_gte!(
+        amount_in_internal,
+        amount_out_minimum,
+        ErrorCode::TooLittleOutputReceived
+    );
+
+    emit!(RouteSwapEvent {
+        sender: ctx.accounts.payer.key(),
+        amount_in: hops[0].amount_in,
+        amount_out: amount_in_internal,
+        hops,
+    });
+
+    Ok(())
+}
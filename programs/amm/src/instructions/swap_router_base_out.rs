@@ -0,0 +1,115 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use crate::swap_router_base_in::SwapRouterBaseIn;
+use crate::swap_router_base_in_v2::ROUTE_HOP_FIXED_ACCOUNTS_LEN;
+use crate::swap_v2::{settle_swap_v2, swap_pool_v2, SwapSingleV2};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+pub fn swap_router_base_out<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, SwapRouterBaseIn<'info>>,
+    amount_out: u64,
+    amount_in_maximum: u64,
+    hop_account_counts: Vec<u8>,
+) -> Result<()> {
+    require!(
+        !hop_account_counts.is_empty(),
+        ErrorCode::InvalidRouteAccounts
+    );
+    require_eq!(
+        hop_account_counts
+            .iter()
+            .map(|count| usize::from(*count))
+            .sum::<usize>(),
+        ctx.remaining_accounts.len(),
+        ErrorCode::InvalidRouteAccounts
+    );
+
+    let mut hops: Vec<(SwapSingleV2<'info>, &'c [AccountInfo<'info>])> =
+        Vec::with_capacity(hop_account_counts.len());
+    let mut input_token_account = Box::new(ctx.accounts.input_token_account.clone());
+    let mut input_token_mint = Box::new(ctx.accounts.input_token_mint.clone());
+    let mut accounts: &'c [AccountInfo<'info>] = ctx.remaining_accounts;
+    for hop_account_count in hop_account_counts.iter() {
+        let hop_account_count = usize::from(*hop_account_count);
+        require_gte!(
+            hop_account_count,
+            ROUTE_HOP_FIXED_ACCOUNTS_LEN,
+            ErrorCode::InvalidRouteAccounts
+        );
+        let (hop_accounts, next_hop_accounts) = accounts.split_at(hop_account_count);
+        accounts = next_hop_accounts;
+
+        let mut remaining_accounts = hop_accounts.iter();
+        let amm_config = Box::new(Account::<AmmConfig>::try_from(
+            remaining_accounts.next().unwrap(),
+        )?);
+        let pool_state_loader =
+            AccountLoader::<PoolState>::try_from(remaining_accounts.next().unwrap())?;
+        let output_token_account = Box::new(InterfaceAccount::<TokenAccount>::try_from(
+            remaining_accounts.next().unwrap(),
+        )?);
+        let input_vault = Box::new(InterfaceAccount::<TokenAccount>::try_from(
+            remaining_accounts.next().unwrap(),
+        )?);
+        let output_vault = Box::new(InterfaceAccount::<TokenAccount>::try_from(
+            remaining_accounts.next().unwrap(),
+        )?);
+        let output_token_mint = Box::new(InterfaceAccount::<Mint>::try_from(
+            remaining_accounts.next().unwrap(),
+        )?);
+        let observation_state =
+            AccountLoader::<ObservationState>::try_from(remaining_accounts.next().unwrap())?;
+
+        {
+            let pool_state = pool_state_loader.load()?;
+            // check observation account is owned by the pool
+            require_keys_eq!(pool_state.observation_key, observation_state.key());
+            // check ammConfig account is associate with the pool
+            require_keys_eq!(pool_state.amm_config, amm_config.key());
+        }
+
+        hops.push((
+            SwapSingleV2 {
+                payer: ctx.accounts.payer.clone(),
+                amm_config,
+                input_token_account: input_token_account.clone(),
+                pool_state: pool_state_loader,
+                output_token_account: output_token_account.clone(),
+                input_vault,
+                output_vault,
+                input_vault_mint: input_token_mint.clone(),
+                output_vault_mint: output_token_mint.clone(),
+                observation_state,
+                token_program: ctx.accounts.token_program.clone(),
+                token_program_2022: ctx.accounts.token_program_2022.clone(),
+                memo_program: ctx.accounts.memo_program.clone(),
+            },
+            remaining_accounts.as_slice(),
+        ));
+        // output token is the new swap input token
+        input_token_account = output_token_account;
+        input_token_mint = output_token_mint;
+    }
+
+    // resolve the hops in reverse, each hop outputs exactly what the next hop pays in
+    let mut amount_out_internal = amount_out;
+    let mut hop_amounts = Vec::with_capacity(hops.len());
+    for (hop, tick_array_accounts) in hops.iter_mut().rev() {
+        let amounts = swap_pool_v2(hop, tick_array_accounts, amount_out_internal, 0, false)?;
+        amount_out_internal = amounts.input_transfer_amount();
+        hop_amounts.push(amounts);
+    }
+    require_gte!(
+        amount_in_maximum,
+        amount_out_internal,
+        ErrorCode::TooMuchInputPaid
+    );
+
+    // transfer the tokens from the first hop, so that each hop is paid by the output of the previous one
+    for ((hop, _), amounts) in hops.iter_mut().zip(hop_amounts.iter().rev()) {
+        settle_swap_v2(hop, amounts)?;
+    }
+
+    Ok(())
+}
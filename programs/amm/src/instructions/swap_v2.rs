@@ -75,6 +75,41 @@ pub struct SwapSingleV2<'info> {
     // tick_array_account_...
 }
 
+/// The token amounts of a swap whose pool state is updated but whose tokens are not transferred yet
+#[derive(Clone, Copy, Debug)]
+pub struct SwapV2Amounts {
+    pub zero_for_one: bool,
+    /// The pool amounts of token_0 and token_1, without transfer fee
+    pub amount_0: u64,
+    pub amount_1: u64,
+    /// The transfer fee charged by withheld_amount
+    pub transfer_fee_0: u64,
+    pub transfer_fee_1: u64,
+    /// The amounts to transfer
+    pub transfer_amount_0: u64,
+    pub transfer_amount_1: u64,
+}
+
+impl SwapV2Amounts {
+    /// The amount paid from the user input token account
+    pub fn input_transfer_amount(&self) -> u64 {
+        if self.zero_for_one {
+            self.transfer_amount_0
+        } else {
+            self.transfer_amount_1
+        }
+    }
+
+    /// The amount received by the user output token account
+    pub fn output_amount(&self) -> u64 {
+        if self.zero_for_one {
+            self.amount_1 - self.transfer_fee_1
+        } else {
+            self.amount_0 - self.transfer_fee_0
+        }
+    }
+}
+
 /// Performs a single exact input/output swap
 /// if is_base_input = true, return value is the max_amount_out, otherwise is min_amount_in
 pub fn exact_internal_v2<'c: 'info, 'info>(
@@ -86,6 +121,40 @@ pub fn exact_internal_v2<'c: 'info, 'info>(
 ) -> Result<u64> {
     // invoke_memo_instruction(SWAP_MEMO_MSG, ctx.memo_program.to_account_info())?;
 
+    let input_balance_before = ctx.input_token_account.amount;
+    let output_balance_before = ctx.output_token_account.amount;
+
+    let amounts = swap_pool_v2(
+        ctx,
+        remaining_accounts,
+        amount_specified,
+        sqrt_price_limit_x64,
+        is_base_input,
+    )?;
+    settle_swap_v2(ctx, &amounts)?;
+
+    if is_base_input {
+        Ok(ctx
+            .output_token_account
+            .amount
+            .checked_sub(output_balance_before)
+            .unwrap())
+    } else {
+        Ok(input_balance_before
+            .checked_sub(ctx.input_token_account.amount)
+            .unwrap())
+    }
+}
+
+/// Updates the pool state for a single exact input/output swap and returns the amounts to transfer,
+/// the tokens must be transferred by `settle_swap_v2` before the instruction ends
+pub fn swap_pool_v2<'c: 'info, 'info>(
+    ctx: &mut SwapSingleV2<'info>,
+    remaining_accounts: &'c [AccountInfo<'info>],
+    amount_specified: u64,
+    sqrt_price_limit_x64: u128,
+    is_base_input: bool,
+) -> Result<SwapV2Amounts> {
     let block_timestamp = solana_program::clock::Clock::get()?.unix_timestamp as u64;
 
     let amount_0;
@@ -93,9 +162,6 @@ pub fn exact_internal_v2<'c: 'info, 'info>(
     let zero_for_one;
     let swap_price_before;
 
-    let input_balance_before = ctx.input_token_account.amount;
-    let output_balance_before = ctx.output_token_account.amount;
-
     // calculate specified amount because the amount includes transfer_fee as input and without transfer_fee as output
     let amount_calculate_specified = if is_base_input {
         let transfer_fee =
@@ -114,6 +180,19 @@ pub fn exact_internal_v2<'c: 'info, 'info>(
         zero_for_one = ctx.input_vault.mint == pool_state.token_mint_0;
 
         require_gt!(block_timestamp, pool_state.open_time);
+        // during the launch phase only the addresses of the swap allowlist can swap
+        let swap_allowlist_key = if block_timestamp < pool_state.launch_end_time {
+            let swap_allowlist_key =
+                SwapAllowlistState::key(pool_state.key(), pool_state.swap_allowlist_bump)?;
+            SwapAllowlistState::check_swapper(
+                swap_allowlist_key,
+                ctx.payer.key(),
+                remaining_accounts,
+            )?;
+            Some(swap_allowlist_key)
+        } else {
+            None
+        };
 
         require!(
             if zero_for_one {
@@ -129,6 +208,14 @@ pub fn exact_internal_v2<'c: 'info, 'info>(
         let mut tickarray_bitmap_extension = None;
         let tick_array_states = &mut VecDeque::new();
 
+        // the reward extension is optional, the swap only needs it to cross initialized ticks
+        let reward_extension_loader = find_reward_extension(pool_state, remaining_accounts)?;
+        let reward_extension_key = reward_extension_loader.as_ref().map(|loader| loader.key());
+        let mut reward_extension = match &reward_extension_loader {
+            Some(loader) => Some(loader.load_mut()?),
+            None => None,
+        };
+
         let tick_array_bitmap_extension_key = TickArrayBitmapExtension::key(pool_state.key());
         for account_info in remaining_accounts.into_iter() {
             if account_info.key().eq(&tick_array_bitmap_extension_key) {
@@ -139,15 +226,24 @@ pub fn exact_internal_v2<'c: 'info, 'info>(
                 );
                 continue;
             }
+            if Some(account_info.key()) == swap_allowlist_key
+                || Some(account_info.key()) == reward_extension_key
+            {
+                continue;
+            }
             tick_array_states.push_back(AccountLoad::load_data_mut(account_info)?);
         }
 
+        let (mut observation_state, mut observation_extension) =
+            ObservationState::load_mut_with_extension(&ctx.observation_state)?;
         (amount_0, amount_1) = swap_internal(
             &ctx.amm_config,
             pool_state,
             tick_array_states,
-            &mut ctx.observation_state.load_mut()?,
+            &mut observation_state,
+            &mut observation_extension,
             &tickarray_bitmap_extension,
+            reward_extension.as_deref_mut(),
             amount_calculate_specified,
             if sqrt_price_limit_x64 == 0 {
                 if zero_for_one {
@@ -174,7 +270,70 @@ pub fn exact_internal_v2<'c: 'info, 'info>(
             amount_0 != 0 && amount_1 != 0,
             ErrorCode::TooSmallInputOrOutputAmount
         );
+        if zero_for_one {
+            require_gt!(swap_price_before, pool_state.sqrt_price_x64);
+        } else {
+            require_gt!(pool_state.sqrt_price_x64, swap_price_before);
+        }
+    }
+
+    // the transfer fee amount charged by withheld_amount
+    let transfer_fee_0;
+    let transfer_fee_1;
+    // transfer amount
+    let transfer_amount_0;
+    let transfer_amount_1;
+    if zero_for_one {
+        transfer_fee_0 =
+            util::get_transfer_inverse_fee(ctx.input_vault_mint.clone(), amount_0).unwrap();
+        transfer_fee_1 = util::get_transfer_fee(ctx.output_vault_mint.clone(), amount_1).unwrap();
+        (transfer_amount_0, transfer_amount_1) = (amount_0 + transfer_fee_0, amount_1);
+    } else {
+        transfer_fee_0 = util::get_transfer_fee(ctx.output_vault_mint.clone(), amount_0).unwrap();
+        transfer_fee_1 =
+            util::get_transfer_inverse_fee(ctx.input_vault_mint.clone(), amount_1).unwrap();
+        (transfer_amount_0, transfer_amount_1) = (amount_0, amount_1 + transfer_fee_1);
     }
+    #[cfg(feature = "enable-log")]
+    msg!(
+        "amount_0:{}, transfer_fee_0:{}, amount_1:{}, transfer_fee_1:{}",
+        amount_0,
+        transfer_fee_0,
+        amount_1,
+        transfer_fee_1
+    );
+
+    if sqrt_price_limit_x64 == 0 {
+        // Does't allow partial filled without specified limit_price.
+        if is_base_input {
+            if zero_for_one {
+                require_eq!(amount_specified, transfer_amount_0);
+            } else {
+                require_eq!(amount_specified, transfer_amount_1);
+            }
+        } else {
+            if zero_for_one {
+                require_eq!(amount_specified, transfer_amount_1);
+            } else {
+                require_eq!(amount_specified, transfer_amount_0);
+            }
+        }
+    }
+
+    Ok(SwapV2Amounts {
+        zero_for_one,
+        amount_0,
+        amount_1,
+        transfer_fee_0,
+        transfer_fee_1,
+        transfer_amount_0,
+        transfer_amount_1,
+    })
+}
+
+/// Transfers the tokens of a swap updated by `swap_pool_v2` and emits the swap event
+pub fn settle_swap_v2<'info>(ctx: &mut SwapSingleV2<'info>, amounts: &SwapV2Amounts) -> Result<()> {
+    let zero_for_one = amounts.zero_for_one;
     let (token_account_0, token_account_1, vault_0, vault_1, vault_0_mint, vault_1_mint) =
         if zero_for_one {
             (
@@ -199,27 +358,12 @@ pub fn exact_internal_v2<'c: 'info, 'info>(
     // user or pool real amount delta without tranfer fee
     let amount_0_without_fee;
     let amount_1_without_fee;
-    // the transfer fee amount charged by withheld_amount
-    let transfer_fee_0;
-    let transfer_fee_1;
-    // transfer amount
-    let transfer_amount_0;
-    let transfer_amount_1;
     if zero_for_one {
-        transfer_fee_0 = util::get_transfer_inverse_fee(vault_0_mint.clone(), amount_0).unwrap();
-        transfer_fee_1 = util::get_transfer_fee(vault_1_mint.clone(), amount_1).unwrap();
-
-        amount_0_without_fee = amount_0;
-        amount_1_without_fee = amount_1.checked_sub(transfer_fee_1).unwrap();
-        (transfer_amount_0, transfer_amount_1) = (amount_0 + transfer_fee_0, amount_1);
-        #[cfg(feature = "enable-log")]
-        msg!(
-            "amount_0:{}, transfer_fee_0:{}, amount_1:{}, transfer_fee_1:{}",
-            amount_0,
-            transfer_fee_0,
-            amount_1,
-            transfer_fee_1
-        );
+        amount_0_without_fee = amounts.amount_0;
+        amount_1_without_fee = amounts
+            .amount_1
+            .checked_sub(amounts.transfer_fee_1)
+            .unwrap();
         //  x -> y, deposit x token from user to pool vault.
         transfer_from_user_to_pool_vault(
             &ctx.payer,
@@ -228,9 +372,9 @@ pub fn exact_internal_v2<'c: 'info, 'info>(
             Some(vault_0_mint),
             &ctx.token_program,
             Some(ctx.token_program_2022.to_account_info()),
-            transfer_amount_0,
+            amounts.transfer_amount_0,
         )?;
-        if vault_1.amount <= transfer_amount_1 {
+        if vault_1.amount <= amounts.transfer_amount_1 {
             // freeze pool, disable all instructions
             ctx.pool_state.load_mut()?.set_status(255);
         }
@@ -242,23 +386,14 @@ pub fn exact_internal_v2<'c: 'info, 'info>(
             Some(vault_1_mint),
             &ctx.token_program,
             Some(ctx.token_program_2022.to_account_info()),
-            transfer_amount_1,
+            amounts.transfer_amount_1,
         )?;
     } else {
-        transfer_fee_0 = util::get_transfer_fee(vault_0_mint.clone(), amount_0).unwrap();
-        transfer_fee_1 = util::get_transfer_inverse_fee(vault_1_mint.clone(), amount_1).unwrap();
-
-        amount_0_without_fee = amount_0.checked_sub(transfer_fee_0).unwrap();
-        amount_1_without_fee = amount_1;
-        (transfer_amount_0, transfer_amount_1) = (amount_0, amount_1 + transfer_fee_1);
-        #[cfg(feature = "enable-log")]
-        msg!(
-            "amount_0:{}, transfer_fee_0:{}, amount_1:{}, transfer_fee_1:{}",
-            amount_0,
-            transfer_fee_0,
-            amount_1,
-            transfer_fee_1
-        );
+        amount_0_without_fee = amounts
+            .amount_0
+            .checked_sub(amounts.transfer_fee_0)
+            .unwrap();
+        amount_1_without_fee = amounts.amount_1;
         transfer_from_user_to_pool_vault(
             &ctx.payer,
             &token_account_1.to_account_info(),
@@ -266,9 +401,9 @@ pub fn exact_internal_v2<'c: 'info, 'info>(
             Some(vault_1_mint),
             &ctx.token_program,
             Some(ctx.token_program_2022.to_account_info()),
-            transfer_amount_1,
+            amounts.transfer_amount_1,
         )?;
-        if vault_0.amount <= transfer_amount_0 {
+        if vault_0.amount <= amounts.transfer_amount_0 {
             // freeze pool, disable all instructions
             ctx.pool_state.load_mut()?.set_status(255);
         }
@@ -279,7 +414,7 @@ pub fn exact_internal_v2<'c: 'info, 'info>(
             Some(vault_0_mint),
             &ctx.token_program,
             Some(ctx.token_program_2022.to_account_info()),
-            transfer_amount_0,
+            amounts.transfer_amount_0,
         )?;
     }
     ctx.output_token_account.reload()?;
@@ -292,47 +427,19 @@ pub fn exact_internal_v2<'c: 'info, 'info>(
         token_account_0: token_account_0.key(),
         token_account_1: token_account_1.key(),
         amount_0: amount_0_without_fee,
-        transfer_fee_0,
+        transfer_fee_0: amounts.transfer_fee_0,
         amount_1: amount_1_without_fee,
-        transfer_fee_1,
+        transfer_fee_1: amounts.transfer_fee_1,
         zero_for_one,
         sqrt_price_x64: pool_state.sqrt_price_x64,
         liquidity: pool_state.liquidity,
-        tick: pool_state.tick_current
+        tick: pool_state.tick_current,
+        trade_fee_rate: pool_state
+            .effective_amm_config(&ctx.amm_config)
+            .get_trade_fee_rate(pool_state.volatility_accumulator),
     });
-    if zero_for_one {
-        require_gt!(swap_price_before, pool_state.sqrt_price_x64);
-    } else {
-        require_gt!(pool_state.sqrt_price_x64, swap_price_before);
-    }
-    if sqrt_price_limit_x64 == 0 {
-        // Does't allow partial filled without specified limit_price.
-        if is_base_input {
-            if zero_for_one {
-                require_eq!(amount_specified, transfer_amount_0);
-            } else {
-                require_eq!(amount_specified, transfer_amount_1);
-            }
-        } else {
-            if zero_for_one {
-                require_eq!(amount_specified, transfer_amount_1);
-            } else {
-                require_eq!(amount_specified, transfer_amount_0);
-            }
-        }
-    }
 
-    if is_base_input {
-        Ok(ctx
-            .output_token_account
-            .amount
-            .checked_sub(output_balance_before)
-            .unwrap())
-    } else {
-        Ok(input_balance_before
-            .checked_sub(ctx.input_token_account.amount)
-            .unwrap())
-    }
+    Ok(())
 }
 
 pub fn swap_v2<'a, 'b, 'c: 'info, 'info>(
@@ -0,0 +1,93 @@
+use super::update_position_boost;
+use crate::error::ErrorCode;
+use crate::states::*;
+use crate::util::load_reward_extension;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UnlockPosition<'info> {
+    /// Anyone can unlock a position whose lock has expired
+    pub payer: Signer<'info>,
+
+    /// CHECK: The payer of the lock account, receives its rent
+    #[account(mut, address = position_lock.rent_payer)]
+    pub rent_payer: UncheckedAccount<'info>,
+
+    /// Unlock this position
+    #[account(mut, constraint = personal_position.pool_id == pool_state.key())]
+    pub personal_position: Box<Account<'info, PersonalPositionState>>,
+
+    #[account(mut)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    #[account(
+        mut,
+        seeds = [
+            POSITION_SEED.as_bytes(),
+            pool_state.key().as_ref(),
+            &personal_position.tick_lower_index.to_be_bytes(),
+            &personal_position.tick_upper_index.to_be_bytes(),
+        ],
+        bump,
+        constraint = protocol_position.pool_id == pool_state.key(),
+    )]
+    pub protocol_position: Box<Account<'info, ProtocolPositionState>>,
+
+    /// Stores init state for the lower tick
+    #[account(mut, constraint = tick_array_lower.load()?.pool_id == pool_state.key())]
+    pub tick_array_lower: AccountLoader<'info, TickArrayState>,
+
+    /// Stores init state for the upper tick
+    #[account(mut, constraint = tick_array_upper.load()?.pool_id == pool_state.key())]
+    pub tick_array_upper: AccountLoader<'info, TickArrayState>,
+
+    /// The lock of the position, closed after the unlock
+    #[account(
+        mut,
+        seeds = [
+            POSITION_LOCK_SEED.as_bytes(),
+            personal_position.nft_mint.as_ref(),
+        ],
+        bump,
+        close = rent_payer
+    )]
+    pub position_lock: Box<Account<'info, PositionLockState>>,
+    // remaining account
+    // the reward extension of the pool, required when the pool has one
+}
+
+pub fn unlock_position<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, UnlockPosition<'info>>,
+) -> Result<()> {
+    let current_timestamp = u64::try_from(Clock::get()?.unix_timestamp).unwrap();
+    require_gte!(
+        current_timestamp,
+        ctx.accounts.position_lock.lock_until,
+        ErrorCode::PositionLockNotExpired
+    );
+
+    let personal_position = &mut ctx.accounts.personal_position;
+    let boosted_liquidity = personal_position.boosted_liquidity();
+    let reward_extension = {
+        let pool_state = ctx.accounts.pool_state.load()?;
+        load_reward_extension(&pool_state, ctx.remaining_accounts)?
+    };
+    update_position_boost(
+        &mut ctx.accounts.pool_state.load_mut()?,
+        reward_extension.as_ref(),
+        &mut ctx.accounts.protocol_position,
+        personal_position,
+        &ctx.accounts.tick_array_lower,
+        &ctx.accounts.tick_array_upper,
+        0,
+        current_timestamp,
+    )?;
+
+    emit!(UnlockPositionEvent {
+        position_nft_mint: personal_position.nft_mint,
+        pool_state: ctx.accounts.pool_state.key(),
+        boosted_liquidity,
+    });
+
+    Ok(())
+}
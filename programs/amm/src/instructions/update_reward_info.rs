@@ -1,4 +1,5 @@
 use crate::states::*;
+use crate::util::load_reward_extension;
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
@@ -8,16 +9,47 @@ pub struct UpdateRewardInfos<'info> {
     pub pool_state: AccountLoader<'info, PoolState>,
 }
 
-pub fn update_reward_infos<'a, 'b, 'c, 'info>(
+/// The reward extension of the pool must be passed in the remaining accounts if the pool has one
+pub fn update_reward_infos<'a, 'b, 'c: 'info, 'info>(
     ctx: Context<'a, 'b, 'c, 'info, UpdateRewardInfos<'info>>,
 ) -> Result<()> {
     let clock = Clock::get()?;
+    let current_timestamp = u64::try_from(clock.unix_timestamp).unwrap();
     let mut pool_state = ctx.accounts.pool_state.load_mut()?;
-    let updated_reward_infos =
-        pool_state.update_reward_infos(u64::try_from(clock.unix_timestamp).unwrap())?;
+    let updated_reward_infos = pool_state.update_reward_infos(current_timestamp)?;
+    let reward_schedules = pool_state.reward_schedules;
+
+    let (updated_extension_reward_infos, extension_reward_schedules) =
+        match load_reward_extension(&pool_state, ctx.remaining_accounts)? {
+            Some(reward_extension) => {
+                let mut reward_extension = reward_extension.load_mut()?;
+                (
+                    reward_extension
+                        .update_reward_infos(pool_state.reward_liquidity(), current_timestamp)?,
+                    reward_extension.reward_schedules,
+                )
+            }
+            None => (
+                [RewardInfo::default(); EXTENSION_REWARD_NUM],
+                [RewardSchedule::default(); EXTENSION_REWARD_NUM],
+            ),
+        };
 
     emit!(UpdateRewardInfosEvent {
-        reward_growth_global_x64: RewardInfo::get_reward_growths(&updated_reward_infos)
+        reward_growth_global_x64: RewardInfo::get_reward_growths(&updated_reward_infos),
+        extension_reward_growth_global_x64: RewardInfo::get_reward_growths(
+            &updated_extension_reward_infos
+        ),
+        emissions_per_second_x64: get_emissions_per_second_x64(
+            &updated_reward_infos,
+            &reward_schedules,
+            current_timestamp
+        ),
+        extension_emissions_per_second_x64: get_emissions_per_second_x64(
+            &updated_extension_reward_infos,
+            &extension_reward_schedules,
+            current_timestamp
+        ),
     });
 
     Ok(())
@@ -0,0 +1,37 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdateSwapAllowlist<'info> {
+    /// The pool creator or the admin
+    #[account(
+        constraint = (owner.key() == pool_state.load()?.owner || owner.key() == crate::admin::id()) @ ErrorCode::NotApproved
+    )]
+    pub owner: Signer<'info>,
+
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// The swap allowlist of the pool
+    #[account(
+        mut,
+        seeds = [
+            SWAP_ALLOWLIST_SEED.as_bytes(),
+            pool_state.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub swap_allowlist: AccountLoader<'info, SwapAllowlistState>,
+}
+
+pub fn update_swap_allowlist(
+    ctx: Context<UpdateSwapAllowlist>,
+    update: SwapAllowlistUpdate,
+) -> Result<()> {
+    let mut swap_allowlist = ctx.accounts.swap_allowlist.load_mut()?;
+    match update {
+        SwapAllowlistUpdate::AddSwappers(keys) => swap_allowlist.update_swappers(keys)?,
+        SwapAllowlistUpdate::RemoveSwappers(keys) => swap_allowlist.remove_swappers(keys),
+    }
+    Ok(())
+}
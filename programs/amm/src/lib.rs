@@ -1,6 +1,8 @@
 pub mod error;
 pub mod instructions;
 pub mod libraries;
+#[cfg(any(test, feature = "client"))]
+pub mod quoter;
 pub mod states;
 pub mod util;
 
@@ -79,49 +81,211 @@ pub mod amm_v3 {
         instructions::create_support_mint_associated(ctx)
     }
 
-    /// Updates the owner of the amm config
-    /// Must be called by the current owner or admin
+    /// Create the registry of the token-2022 mints and extension types supported by pools and rewards
     ///
     /// # Arguments
     ///
     /// * `ctx`- The context of accounts
-    /// * `trade_fee_rate`- The new trade fee rate of amm config, be set when `param` is 0
-    /// * `protocol_fee_rate`- The new protocol fee rate of amm config, be set when `param` is 1
-    /// * `fund_fee_rate`- The new fund fee rate of amm config, be set when `param` is 2
-    /// * `new_owner`- The config's new owner, be set when `param` is 3
-    /// * `new_fund_owner`- The config's new fund owner, be set when `param` is 4
-    /// * `param`- The value can be 0 | 1 | 2 | 3 | 4, otherwise will report a error
+    ///
+    pub fn create_mint_registry(ctx: Context<CreateMintRegistry>) -> Result<()> {
+        instructions::create_mint_registry(ctx)
+    }
+
+    /// Allow or disallow an extension type, or add or remove a mint of the mint registry
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `update`- The change of the registry
+    ///
+    pub fn update_mint_registry(
+        ctx: Context<UpdateMintRegistry>,
+        update: MintRegistryUpdate,
+    ) -> Result<()> {
+        instructions::update_mint_registry(ctx, update)
+    }
+
+    /// Legacy update of a single field of the amm config, the fields are now changed through
+    /// `update_amm_config_v2`, `schedule_admin_change` or `propose_amm_config_owner`
+    /// so every `param` is rejected
+    /// Must be called by the admin
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `trade_fee_rate`- Rejected when `param` is 0, the fee rates are changed by `schedule_admin_change`
+    /// * `protocol_fee_rate`- Rejected when `param` is 1
+    /// * `fund_fee_rate`- Rejected when `param` is 2
+    /// * `new_owner`- Rejected when `param` is 3, the owner is changed by `propose_amm_config_owner`
+    /// * `new_fund_owner`- Rejected when `param` is 4, the fund owner is changed by `propose_amm_config_owner`
+    /// * `dynamic_fee_control`- Rejected when `param` is 5
+    /// * `min_trade_fee_rate`- Rejected when `param` is 6
+    /// * `max_trade_fee_rate`- Rejected when `param` is 7
+    /// * `volatility_filter_period`- Rejected when `param` is 8
+    /// * `volatility_decay_period`- Rejected when `param` is 9
+    /// * `param`- The value can be 0 | 1 | 2 | 3 | 4 | 5 | 6 | 7 | 8 | 9, otherwise will report a error
     ///
     pub fn update_amm_config(ctx: Context<UpdateAmmConfig>, param: u8, value: u32) -> Result<()> {
         instructions::update_amm_config(ctx, param, value)
     }
 
+    /// Updates a single field of the amm config, the fee rate, owner and status enabling changes are rejected
+    /// Must be called by the admin
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `update`- The field to change and its new value
+    ///
+    pub fn update_amm_config_v2(
+        ctx: Context<UpdateAmmConfigV2>,
+        update: AmmConfigUpdate,
+    ) -> Result<()> {
+        instructions::update_amm_config_v2(ctx, update)
+    }
+
+    /// Propose the new owners of the amm config, they take over once they sign `accept_amm_config_owner`
+    /// Must be called by the admin
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `new_owner`- The proposed owner, none to keep the current proposal, the default key to clear it
+    /// * `new_fund_owner`- The proposed fund owner, none to keep the current proposal, the default key to clear it
+    ///
+    pub fn propose_amm_config_owner(
+        ctx: Context<ProposeAmmConfigOwner>,
+        new_owner: Option<Pubkey>,
+        new_fund_owner: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::propose_amm_config_owner(ctx, new_owner, new_fund_owner)
+    }
+
+    /// Accept the owner or fund owner role of the amm config proposed to the signer
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    ///
+    pub fn accept_amm_config_owner(ctx: Context<AcceptAmmConfigOwner>) -> Result<()> {
+        instructions::accept_amm_config_owner(ctx)
+    }
+
     /// Creates a pool for the given token pair and the initial price
     ///
     /// # Arguments
     ///
     /// * `ctx`- The context of accounts
     /// * `sqrt_price_x64` - the initial sqrt price (amount_token_1 / amount_token_0) of the pool as a Q64.64
-    /// Note: The open_time must be smaller than the current block_timestamp on chain.
-    pub fn create_pool(
-        ctx: Context<CreatePool>,
+    /// * `open_time` - the timestamp after which swaps are allowed, liquidity can be provided before it, at most 30 days ahead
+    pub fn create_pool<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, CreatePool<'info>>,
         sqrt_price_x64: u128,
         open_time: u64,
     ) -> Result<()> {
         instructions::create_pool(ctx, sqrt_price_x64, open_time)
     }
 
-    /// Update pool status for given value
+    /// Disable status bits of the pool instantly by the admin or the pause guardian,
+    /// enabling them must be scheduled by the admin with `schedule_admin_change`
     ///
     /// # Arguments
     ///
     /// * `ctx`- The context of accounts
-    /// * `status` - The value of status
+    /// * `status` - The value of status, it must keep every bit already set in the current status
     ///
-    pub fn update_pool_status(ctx: Context<UpdatePoolStatus>, status: u8) -> Result<()> {
+    pub fn update_pool_status<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, UpdatePoolStatus<'info>>,
+        status: u8,
+    ) -> Result<()> {
         instructions::update_pool_status(ctx, status)
     }
 
+    /// Disable status bits of the amm config and of every pool of the config passed in the remaining accounts
+    /// instantly by the admin or the pause guardian, the status of the config is inherited by the pools
+    /// when the config is passed
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `disabled_bits` - The `PoolStatusBitIndex` bits to disable
+    ///
+    pub fn pause_amm_config_pools<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, PauseAmmConfigPools<'info>>,
+        disabled_bits: u8,
+    ) -> Result<()> {
+        instructions::pause_amm_config_pools(ctx, disabled_bits)
+    }
+
+    /// Override the fee rates of the amm config for a single pool
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `trade_fee_rate` - The trade fee rate of the pool, none to use the one of the amm config
+    /// * `protocol_fee_rate` - The protocol fee rate of the pool, none to use the one of the amm config
+    /// * `fund_fee_rate` - The fund fee rate of the pool, none to use the one of the amm config
+    ///
+    pub fn update_pool_fee_rates(
+        ctx: Context<UpdatePoolFeeRates>,
+        trade_fee_rate: Option<u32>,
+        protocol_fee_rate: Option<u32>,
+        fund_fee_rate: Option<u32>,
+    ) -> Result<()> {
+        instructions::update_pool_fee_rates(ctx, trade_fee_rate, protocol_fee_rate, fund_fee_rate)
+    }
+
+    /// Schedule a fee change of an amm config or the status bits to enable on an amm config or a pool,
+    /// anyone can execute it after the admin change delay
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `nonce` - Distinguishes the changes scheduled for the same target
+    /// * `change` - The change applied to the target
+    ///
+    pub fn schedule_admin_change(
+        ctx: Context<ScheduleAdminChange>,
+        nonce: u64,
+        change: AdminChange,
+    ) -> Result<()> {
+        instructions::schedule_admin_change(ctx, nonce, change)
+    }
+
+    /// Apply a scheduled change once it is effective, the rent goes back to the admin who scheduled it
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    ///
+    pub fn execute_admin_change(ctx: Context<ExecuteAdminChange>) -> Result<()> {
+        instructions::execute_admin_change(ctx)
+    }
+
+    /// Drop a scheduled change before it is executed
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    ///
+    pub fn cancel_admin_change(ctx: Context<CancelAdminChange>) -> Result<()> {
+        instructions::cancel_admin_change(ctx)
+    }
+
+    /// Update the min seconds between two oracle observations of the pool
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `observation_update_duration` - The min seconds between two observations, at most one hour
+    ///
+    pub fn update_observation_update_duration(
+        ctx: Context<UpdateObservationUpdateDuration>,
+        observation_update_duration: u32,
+    ) -> Result<()> {
+        instructions::update_observation_update_duration(ctx, observation_update_duration)
+    }
+
     /// Creates an operation account for the program
     ///
     /// # Arguments
@@ -137,21 +301,17 @@ pub mod amm_v3 {
     /// # Arguments
     ///
     /// * `ctx`- The context of accounts
-    /// * `param`- The value can be 0 | 1 | 2 | 3, otherwise will report a error
-    /// * `keys`- update operation owner when the `param` is 0
-    ///           remove operation owner when the `param` is 1
-    ///           update whitelist mint when the `param` is 2
-    ///           remove whitelist mint when the `param` is 3
+    /// * `update`- Set the roles of an operator, only admin can do it,
+    ///             or add or remove whitelist mints, admin or whitelist manager can do it
     ///
     pub fn update_operation_account(
         ctx: Context<UpdateOperationAccount>,
-        param: u8,
-        keys: Vec<Pubkey>,
+        update: OperationAccountUpdate,
     ) -> Result<()> {
-        instructions::update_operation_account(ctx, param, keys)
+        instructions::update_operation_account(ctx, update)
     }
 
-    /// Transfer reward owner
+    /// Propose the new reward owner of the pool, it takes over once it signs `accept_reward_owner`
     ///
     /// # Arguments
     ///
@@ -165,6 +325,16 @@ pub mod amm_v3 {
         instructions::transfer_reward_owner(ctx, new_owner)
     }
 
+    /// Accept the reward owner role of the pool proposed to the signer
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    ///
+    pub fn accept_reward_owner(ctx: Context<AcceptRewardOwner>) -> Result<()> {
+        instructions::accept_reward_owner(ctx)
+    }
+
     /// Initialize a reward info for a given pool and reward index
     ///
     /// # Arguments
@@ -175,8 +345,8 @@ pub mod amm_v3 {
     /// * `end_time` - reward end timestamp
     /// * `emissions_per_second_x64` - Token reward per second are earned per unit of liquidity.
     ///
-    pub fn initialize_reward(
-        ctx: Context<InitializeReward>,
+    pub fn initialize_reward<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, InitializeReward<'info>>,
         param: InitializeRewardParam,
     ) -> Result<()> {
         instructions::initialize_reward(ctx, param)
@@ -189,8 +359,8 @@ pub mod amm_v3 {
     /// * `ctx`- The context of accounts
     /// * `reward_index` - the index to reward info
     ///
-    pub fn collect_remaining_rewards(
-        ctx: Context<CollectRemainingRewards>,
+    pub fn collect_remaining_rewards<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, CollectRemainingRewards<'info>>,
         reward_index: u8,
     ) -> Result<()> {
         instructions::collect_remaining_rewards(ctx, reward_index)
@@ -202,12 +372,26 @@ pub mod amm_v3 {
     ///
     /// * `ctx`- The context of accounts
     ///
-    pub fn update_reward_infos<'a, 'b, 'c, 'info>(
+    pub fn update_reward_infos<'a, 'b, 'c: 'info, 'info>(
         ctx: Context<'a, 'b, 'c, 'info, UpdateRewardInfos<'info>>,
     ) -> Result<()> {
         instructions::update_reward_infos(ctx)
     }
 
+    /// Create the reward extension of a pool to initialize rewards beyond the `REWARD_NUM` pool rewards,
+    /// once created it must be passed in the remaining accounts of every instruction accruing rewards.
+    /// Must be called by the admin or an operator with the reward admin role.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of accounts
+    ///
+    pub fn create_reward_extension<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, CreateRewardExtension<'info>>,
+    ) -> Result<()> {
+        instructions::create_reward_extension(ctx)
+    }
+
     /// Reset reward param, start a new reward cycle or extend the current cycle.
     ///
     /// # Arguments
@@ -235,6 +419,119 @@ pub mod amm_v3 {
         )
     }
 
+    /// Fund an initialized reward with more reward token, can be called for everyone
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of accounts
+    /// * `reward_index` - The index of reward token in the pool.
+    /// * `amount` - The reward amount received by the reward vault, the transfer fee is paid on top of it
+    /// * `mode` - Extend the end time, only for the reward authority, or increase the emissions of the remaining period
+    ///
+    pub fn fund_reward<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, FundReward<'info>>,
+        reward_index: u8,
+        amount: u64,
+        mode: FundRewardMode,
+    ) -> Result<()> {
+        instructions::fund_reward(ctx, reward_index, amount, mode)
+    }
+
+    /// Retire an ended reward whose emitted rewards have all been claimed, or whose grace period to
+    /// claim has passed since its end time, so that its slot can be initialized again with a new mint.
+    /// The balance of the vault beyond the unclaimed reward is sent to the pool owner, positions drop
+    /// their checkpoint of the retired reward on their next update.
+    /// Must be called by the pool creator or the admin.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of accounts
+    /// * `reward_index` - The index of reward token in the pool.
+    ///
+    pub fn retire_reward<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, RetireReward<'info>>,
+        reward_index: u8,
+    ) -> Result<()> {
+        instructions::retire_reward(ctx, reward_index)
+    }
+
+    /// Set the emission schedule of a reward which is not open yet, the emission decreases from
+    /// the emissions_per_second_x64 of the reward to the end emission.
+    /// Must be called by the pool creator or a reward admin operator.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of accounts
+    /// * `reward_index` - The index of reward token in the pool.
+    /// * `kind` - Constant, linear decay or step emission
+    /// * `step_period` - The seconds of every step of a step schedule
+    /// * `end_emissions_per_second_x64` - The per second emission reward at the end of the schedule,
+    ///    can't be greater than the emissions_per_second_x64 of the reward
+    ///
+    pub fn set_reward_schedule<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, SetRewardSchedule<'info>>,
+        reward_index: u8,
+        kind: RewardScheduleKind,
+        step_period: u32,
+        end_emissions_per_second_x64: u128,
+    ) -> Result<()> {
+        instructions::set_reward_schedule(
+            ctx,
+            reward_index,
+            kind,
+            step_period,
+            end_emissions_per_second_x64,
+        )
+    }
+
+    /// Lock a position until the timestamp, the locked position earns the rewards with its
+    /// liquidity boosted by the lock reward multiplier of the pool. The liquidity of a locked
+    /// position can't be changed and the position can't be closed until it is unlocked.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of accounts
+    /// * `lock_until` - The position can be unlocked from this timestamp
+    ///
+    pub fn lock_position<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, LockPosition<'info>>,
+        lock_until: u64,
+    ) -> Result<()> {
+        instructions::lock_position(ctx, lock_until)
+    }
+
+    /// Unlock a position whose lock has expired, the position earns the rewards with its
+    /// liquidity again. Anyone can unlock an expired lock, so that the position doesn't keep
+    /// its boost after the lock; the rent of the lock account goes back to its payer.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of accounts
+    ///
+    pub fn unlock_position<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, UnlockPosition<'info>>,
+    ) -> Result<()> {
+        instructions::unlock_position(ctx)
+    }
+
+    /// Set the reward multiplier and the minimum duration of the position locks of a pool.
+    /// Must be called by the pool creator or the admin.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of accounts
+    /// * `lock_reward_multiplier` - The reward multiplier of the new locks in basis points,
+    ///    zero disables the locks
+    /// * `min_lock_duration` - The minimum seconds of the new locks
+    ///
+    pub fn set_position_lock_config(
+        ctx: Context<SetPositionLockConfig>,
+        lock_reward_multiplier: u16,
+        min_lock_duration: u32,
+    ) -> Result<()> {
+        instructions::set_position_lock_config(ctx, lock_reward_multiplier, min_lock_duration)
+    }
+
     /// Collect the protocol fee accrued to the pool
     ///
     /// # Arguments
@@ -243,8 +540,8 @@ pub mod amm_v3 {
     /// * `amount_0_requested` - The maximum amount of token_0 to send, can be 0 to collect fees in only token_1
     /// * `amount_1_requested` - The maximum amount of token_1 to send, can be 0 to collect fees in only token_0
     ///
-    pub fn collect_protocol_fee(
-        ctx: Context<CollectProtocolFee>,
+    pub fn collect_protocol_fee<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, CollectProtocolFee<'info>>,
         amount_0_requested: u64,
         amount_1_requested: u64,
     ) -> Result<()> {
@@ -259,14 +556,58 @@ pub mod amm_v3 {
     /// * `amount_0_requested` - The maximum amount of token_0 to send, can be 0 to collect fees in only token_1
     /// * `amount_1_requested` - The maximum amount of token_1 to send, can be 0 to collect fees in only token_0
     ///
-    pub fn collect_fund_fee(
-        ctx: Context<CollectFundFee>,
+    pub fn collect_fund_fee<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, CollectFundFee<'info>>,
         amount_0_requested: u64,
         amount_1_requested: u64,
     ) -> Result<()> {
         instructions::collect_fund_fee(ctx, amount_0_requested, amount_1_requested)
     }
 
+    /// Set the recipients of the protocol fees of the pools of an amm config
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of accounts
+    /// * `recipients` - The recipients with their shares in basis points, the shares sum up to 10000.
+    /// An empty list leaves the protocol fees to `collect_protocol_fee`
+    ///
+    pub fn set_fee_distribution(
+        ctx: Context<SetFeeDistribution>,
+        recipients: Vec<FeeRecipient>,
+    ) -> Result<()> {
+        instructions::set_fee_distribution(ctx, recipients)
+    }
+
+    /// Set the recipients of the fund fees of the pools of an amm config, signed by the admin or the fund owner
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of accounts
+    /// * `recipients` - The recipients with their shares in basis points, the shares sum up to 10000.
+    /// An empty list leaves the fund fees to `collect_fund_fee`
+    ///
+    pub fn set_fund_fee_distribution(
+        ctx: Context<SetFundFeeDistribution>,
+        recipients: Vec<FeeRecipient>,
+    ) -> Result<()> {
+        instructions::set_fund_fee_distribution(ctx, recipients)
+    }
+
+    /// Distribute the protocol and fund fees accrued to the pool by the fee distribution of its amm config,
+    /// anyone can call it
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of accounts, the remaining accounts are the token_0 and token_1 accounts
+    /// of each protocol fee recipient, followed by those of each fund fee recipient
+    ///
+    pub fn distribute_fees<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, DistributeFees<'info>>,
+    ) -> Result<()> {
+        instructions::distribute_fees(ctx)
+    }
+
     /// #[deprecated(note = "Use `open_position_with_token22_nft` instead.")]
     /// Creates a new position wrapped in a NFT
     ///
@@ -347,6 +688,52 @@ pub mod amm_v3 {
         )
     }
 
+    /// Creates a new position wrapped in a NFT, support Token2022, rejected once the deadline has passed
+    /// The amm config of the pool must be passed in the remaining accounts, its status is inherited by the pool
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of accounts
+    /// * `tick_lower_index` - The low boundary of market
+    /// * `tick_upper_index` - The upper boundary of market
+    /// * `tick_array_lower_start_index` - The start index of tick array which include tick low
+    /// * `tick_array_upper_start_index` - The start index of tick array which include tick upper
+    /// * `liquidity` - The liquidity to be added, if zero, and the base_flag is specified, calculate liquidity base amount_0_max or amount_1_max according base_flag, otherwise open position with zero liquidity
+    /// * `amount_0_max` - The max amount of token_0 to spend, which serves as a slippage check
+    /// * `amount_1_max` - The max amount of token_1 to spend, which serves as a slippage check
+    /// * `with_metadata` - The flag indicating whether to create NFT mint metadata
+    /// * `base_flag` - if the liquidity specified as zero, true: calculate liquidity base amount_0_max otherwise base amount_1_max
+    /// * `deadline` - The unix timestamp after which the transaction is rejected, none for no deadline
+    ///
+    pub fn open_position_v3<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, OpenPositionV2<'info>>,
+        tick_lower_index: i32,
+        tick_upper_index: i32,
+        tick_array_lower_start_index: i32,
+        tick_array_upper_start_index: i32,
+        liquidity: u128,
+        amount_0_max: u64,
+        amount_1_max: u64,
+        with_metadata: bool,
+        base_flag: Option<bool>,
+        deadline: Option<i64>,
+    ) -> Result<()> {
+        util::check_deadline(deadline, Clock::get()?.unix_timestamp)?;
+        util::load_amm_config(&*ctx.accounts.pool_state.load()?, ctx.remaining_accounts)?;
+        instructions::open_position_v2(
+            ctx,
+            liquidity,
+            amount_0_max,
+            amount_1_max,
+            tick_lower_index,
+            tick_upper_index,
+            tick_array_lower_start_index,
+            tick_array_upper_start_index,
+            with_metadata,
+            base_flag,
+        )
+    }
+
     /// Creates a new position wrapped in a Token2022 NFT without relying on metadata_program and metadata_account, reduce the cost for user to create a personal position.
     ///
     /// # Arguments
@@ -443,6 +830,34 @@ pub mod amm_v3 {
         instructions::increase_liquidity_v2(ctx, liquidity, amount_0_max, amount_1_max, base_flag)
     }
 
+    /// Increases liquidity for an existing position, with amount paid by `payer`, support Token2022, rejected once the deadline has passed
+    /// The amm config of the pool must be passed in the remaining accounts, its status is inherited by the pool
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of accounts
+    /// * `liquidity` - The desired liquidity to be added, if zero, calculate liquidity base amount_0 or amount_1 according base_flag
+    /// * `amount_0_max` - The max amount of token_0 to spend, which serves as a slippage check
+    /// * `amount_1_max` - The max amount of token_1 to spend, which serves as a slippage check
+    /// * `base_flag` - must be specified if liquidity is zero, true: calculate liquidity base amount_0_max otherwise base amount_1_max
+    /// * `deadline` - The unix timestamp after which the transaction is rejected, none for no deadline
+    ///
+    pub fn increase_liquidity_v3<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, IncreaseLiquidityV2<'info>>,
+        liquidity: u128,
+        amount_0_max: u64,
+        amount_1_max: u64,
+        base_flag: Option<bool>,
+        deadline: Option<i64>,
+    ) -> Result<()> {
+        util::check_deadline(deadline, Clock::get()?.unix_timestamp)?;
+        util::load_amm_config(&*ctx.accounts.pool_state.load()?, ctx.remaining_accounts)?;
+        if liquidity == 0 {
+            assert!(base_flag.is_some());
+        }
+        instructions::increase_liquidity_v2(ctx, liquidity, amount_0_max, amount_1_max, base_flag)
+    }
+
     /// #[deprecated(note = "Use `decrease_liquidity_v2` instead.")]
     /// Decreases liquidity for an existing position
     ///
@@ -480,6 +895,29 @@ pub mod amm_v3 {
         instructions::decrease_liquidity_v2(ctx, liquidity, amount_0_min, amount_1_min)
     }
 
+    /// Decreases liquidity for an existing position, support Token2022, rejected once the deadline has passed
+    /// The amm config of the pool must be passed in the remaining accounts, its status is inherited by the pool
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` -  The context of accounts
+    /// * `liquidity` - The amount by which liquidity will be decreased
+    /// * `amount_0_min` - The minimum amount of token_0 that should be accounted for the burned liquidity
+    /// * `amount_1_min` - The minimum amount of token_1 that should be accounted for the burned liquidity
+    /// * `deadline` - The unix timestamp after which the transaction is rejected, none for no deadline
+    ///
+    pub fn decrease_liquidity_v3<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, DecreaseLiquidityV2<'info>>,
+        liquidity: u128,
+        amount_0_min: u64,
+        amount_1_min: u64,
+        deadline: Option<i64>,
+    ) -> Result<()> {
+        util::check_deadline(deadline, Clock::get()?.unix_timestamp)?;
+        util::load_amm_config(&*ctx.accounts.pool_state.load()?, ctx.remaining_accounts)?;
+        instructions::decrease_liquidity_v2(ctx, liquidity, amount_0_min, amount_1_min)
+    }
+
     /// #[deprecated(note = "Use `swap_v2` instead.")]
     /// Swaps one token for as much as possible of another token across a single pool
     ///
@@ -533,6 +971,35 @@ pub mod amm_v3 {
         )
     }
 
+    /// Swaps one token for as much as possible of another token across a single pool, support token program 2022, rejected once the deadline has passed
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of accounts
+    /// * `amount` - Arranged in pairs with other_amount_threshold. (amount_in, amount_out_minimum) or (amount_out, amount_in_maximum)
+    /// * `other_amount_threshold` - For slippage check
+    /// * `sqrt_price_limit` - The Q64.64 sqrt price √P limit. If zero for one, the price cannot
+    /// * `is_base_input` - swap base input or swap base output
+    /// * `deadline` - The unix timestamp after which the transaction is rejected, none for no deadline
+    ///
+    pub fn swap_v3<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, SwapSingleV2<'info>>,
+        amount: u64,
+        other_amount_threshold: u64,
+        sqrt_price_limit_x64: u128,
+        is_base_input: bool,
+        deadline: Option<i64>,
+    ) -> Result<()> {
+        util::check_deadline(deadline, Clock::get()?.unix_timestamp)?;
+        instructions::swap_v2(
+            ctx,
+            amount,
+            other_amount_threshold,
+            sqrt_price_limit_x64,
+            is_base_input,
+        )
+    }
+
     /// Swap token for as much as possible of another token across the path provided, base input
     ///
     /// # Arguments
@@ -548,4 +1015,137 @@ pub mod amm_v3 {
     ) -> Result<()> {
         instructions::swap_router_base_in(ctx, amount_in, amount_out_minimum)
     }
+
+    /// Swap token for as much as possible of another token across the path provided, base input,
+    /// rejected once the deadline has passed
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of accounts
+    /// * `amount_in` - Token amount to be swapped in
+    /// * `amount_out_minimum` - Panic if output amount is below minimum amount. For slippage.
+    /// * `deadline` - The unix timestamp after which the transaction is rejected, none for no deadline
+    ///
+    pub fn swap_router_base_in_v3<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, SwapRouterBaseIn<'info>>,
+        amount_in: u64,
+        amount_out_minimum: u64,
+        deadline: Option<i64>,
+    ) -> Result<()> {
+        util::check_deadline(deadline, Clock::get()?.unix_timestamp)?;
+        instructions::swap_router_base_in(ctx, amount_in, amount_out_minimum)
+    }
+
+    /// Swap as little as possible of one token for an exact amount of another token across the path provided, base output
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of accounts, the remaining accounts follow the layout of `swap_router_base_in_v2`
+    /// * `amount_out` - Token amount to be swapped out of the last pool
+    /// * `amount_in_maximum` - Panic if input amount is above maximum amount. For slippage.
+    /// * `hop_account_counts` - The number of remaining accounts of each hop, the fixed accounts of the hop included
+    /// * `deadline` - The unix timestamp after which the transaction is rejected, none for no deadline
+    ///
+    pub fn swap_router_base_out<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, SwapRouterBaseIn<'info>>,
+        amount_out: u64,
+        amount_in_maximum: u64,
+        hop_account_counts: Vec<u8>,
+        deadline: Option<i64>,
+    ) -> Result<()> {
+        util::check_deadline(deadline, Clock::get()?.unix_timestamp)?;
+        instructions::swap_router_base_out(ctx, amount_out, amount_in_maximum, hop_account_counts)
+    }
+
+    /// Swap token for as much as possible of another token across the path provided, base input,
+    /// with the accounts of each hop framed explicitly
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of accounts
+    /// * `amount_in` - Token amount to be swapped in
+    /// * `amount_out_minimum` - Panic if output amount is below minimum amount. For slippage.
+    /// * `hop_account_counts` - The number of remaining accounts of each hop, the fixed accounts of the hop included
+    /// * `sqrt_price_limits_x64` - The sqrt price limit of each hop, 0 for no limit. Empty for no limit on every hop
+    /// * `deadline` - The unix timestamp after which the transaction is rejected, none for no deadline
+    ///
+    pub fn swap_router_base_in_v2<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, SwapRouterBaseIn<'info>>,
+        amount_in: u64,
+        amount_out_minimum: u64,
+        hop_account_counts: Vec<u8>,
+        sqrt_price_limits_x64: Vec<u128>,
+        deadline: Option<i64>,
+    ) -> Result<()> {
+        util::check_deadline(deadline, Clock::get()?.unix_timestamp)?;
+        instructions::swap_router_base_in_v2(
+            ctx,
+            amount_in,
+            amount_out_minimum,
+            hop_account_counts,
+            sqrt_price_limits_x64,
+        )
+    }
+
+    /// Add a launch phase to a pool which is not open yet, during the first `launch_duration` seconds
+    /// after the open time only the addresses of the swap allowlist can swap.
+    /// Must be called by the pool creator or the admin.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of accounts
+    /// * `launch_duration` - The duration of the launch phase in seconds
+    /// * `keys` - The addresses allowed to swap during the launch phase
+    ///
+    pub fn create_swap_allowlist(
+        ctx: Context<CreateSwapAllowlist>,
+        launch_duration: u64,
+        keys: Vec<Pubkey>,
+    ) -> Result<()> {
+        instructions::create_swap_allowlist(ctx, launch_duration, keys)
+    }
+
+    /// Update the addresses allowed to swap during the launch phase of the pool.
+    /// Must be called by the pool creator or the admin.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of accounts
+    /// * `update` - The addresses to add to or remove from the allowlist
+    ///
+    pub fn update_swap_allowlist(
+        ctx: Context<UpdateSwapAllowlist>,
+        update: SwapAllowlistUpdate,
+    ) -> Result<()> {
+        instructions::update_swap_allowlist(ctx, update)
+    }
+
+    /// Read the oracle of the pool, the result is returned through the transaction return data
+    /// so that other programs can use the pool as a price oracle by CPI.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of accounts
+    /// * `seconds_agos` - From how long ago each tick cumulative should be returned, at least two
+    ///    different values are required to derive the time weighted average tick
+    ///
+    pub fn observe(ctx: Context<Observe>, seconds_agos: Vec<u32>) -> Result<ObserveResult> {
+        instructions::observe(ctx, seconds_agos)
+    }
+
+    /// Grow the observation account of the pool to store more observations, paid by the caller.
+    /// The new slots are used once the ring buffer reaches its current end.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of accounts
+    /// * `observation_cardinality_next` - The number of observations the account should have room for,
+    ///    can grow by at most 10KiB of account data per call
+    ///
+    pub fn increase_observation_cardinality(
+        ctx: Context<IncreaseObservationCardinality>,
+        observation_cardinality_next: u16,
+    ) -> Result<()> {
+        instructions::increase_observation_cardinality(ctx, observation_cardinality_next)
+    }
 }
@@ -0,0 +1,206 @@
+/// Off-chain swap quoter, it runs the steps of `swap_internal` over an in-memory snapshot of the
+/// pool accounts, without any rpc call.
+use crate::error::ErrorCode;
+use crate::instructions::{swap_steps, SwapState, SwapTickArrays};
+use crate::libraries::tick_math;
+use crate::states::*;
+use anchor_lang::prelude::*;
+use std::collections::BTreeMap;
+
+/// The accounts a quote is computed from
+pub struct SwapSnapshot<'a> {
+    pub amm_config: &'a AmmConfig,
+    pub pool_state: &'a PoolState,
+    pub tickarray_bitmap_extension: &'a Option<TickArrayBitmapExtension>,
+    /// The initialized tick arrays of the pool, in any order
+    pub tick_arrays: &'a [TickArrayState],
+}
+
+/// The result of a quoted swap
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SwapQuote {
+    /// The amount of input token paid by the user, fees included
+    pub amount_in: u64,
+    /// The amount of output token received by the user
+    pub amount_out: u64,
+    /// The part of the input amount which is not swapped because the price limit is reached
+    pub amount_specified_remaining: u64,
+    /// The input token fee paid to the liquidity providers
+    pub lp_fee: u64,
+    /// The input token fee paid to the protocol
+    pub protocol_fee: u64,
+    /// The input token fee paid to the fund
+    pub fund_fee: u64,
+    /// The trade fee rate charged by the last step of the swap
+    pub trade_fee_rate: u32,
+    /// The sqrt price of the pool after the swap
+    pub sqrt_price_x64: u128,
+    /// The tick of the pool after the swap
+    pub tick: i32,
+    /// The in range liquidity of the pool after the swap
+    pub liquidity: u128,
+    /// The in range boosted liquidity of the pool after the swap
+    pub boosted_liquidity: u128,
+    /// The initialized ticks crossed by the swap, in the swap direction
+    pub ticks_crossed: Vec<i32>,
+    /// The start index of the tick arrays the swap has to be given, in the swap direction
+    pub tick_array_start_indexes: Vec<i32>,
+    /// The addresses of the tick arrays the swap has to be given, in the swap direction
+    pub tick_array_keys: Vec<Pubkey>,
+}
+
+impl SwapQuote {
+    /// The total input token fee
+    pub fn fee_amount(&self) -> u64 {
+        self.lp_fee + self.protocol_fee + self.fund_fee
+    }
+}
+
+/// The snapshot tick arrays in the swap direction, the crossed ticks are only updated in the copies
+struct SnapshotTickArrays<'a> {
+    tick_arrays: BTreeMap<i32, &'a TickArrayState>,
+    current: Box<TickArrayState>,
+    start_indexes: Vec<i32>,
+    ticks_crossed: Vec<i32>,
+}
+
+impl SwapTickArrays for SnapshotTickArrays<'_> {
+    fn current(&mut self) -> &mut TickArrayState {
+        &mut self.current
+    }
+
+    fn move_to(&mut self, start_tick_index: i32) -> Result<()> {
+        self.current = Box::new(
+            **self
+                .tick_arrays
+                .get(&start_tick_index)
+                .ok_or(ErrorCode::NotEnoughTickArrayAccount)?,
+        );
+        self.start_indexes.push(start_tick_index);
+        Ok(())
+    }
+
+    fn on_tick_crossed(&mut self, tick: i32) {
+        self.ticks_crossed.push(tick);
+    }
+}
+
+/// Quotes a single pool swap with the steps of `swap_internal`
+///
+/// # Arguments
+///
+/// * `snapshot` - The pool accounts to quote against
+/// * `amount_specified` - The exact amount of input token if `is_base_input`, otherwise of output token
+/// * `sqrt_price_limit_x64` - The price limit, `None` for no limit
+/// * `zero_for_one` - The swap direction
+/// * `is_base_input` - Whether `amount_specified` is the input or the output amount
+/// * `block_timestamp` - The timestamp the swap is quoted at
+///
+pub fn quote_swap(
+    snapshot: &SwapSnapshot,
+    amount_specified: u64,
+    sqrt_price_limit_x64: Option<u128>,
+    zero_for_one: bool,
+    is_base_input: bool,
+    block_timestamp: u32,
+) -> Result<SwapQuote> {
+    // the volatility of the dynamic fee is tracked on a copy of the pool
+    let pool_state = &mut snapshot.pool_state.clone();
+    // the fee overrides of the pool replace the fee rates of the config
+    let amm_config = &pool_state.effective_amm_config(snapshot.amm_config);
+    let tickarray_bitmap_extension = snapshot.tickarray_bitmap_extension;
+
+    require!(amount_specified != 0, ErrorCode::ZeroAmountSpecified);
+    if !pool_state.get_status_by_bit_with_config(PoolStatusBitIndex::Swap, Some(amm_config)) {
+        return err!(ErrorCode::NotApproved);
+    }
+    let sqrt_price_limit_x64 = sqrt_price_limit_x64.unwrap_or(if zero_for_one {
+        tick_math::MIN_SQRT_PRICE_X64 + 1
+    } else {
+        tick_math::MAX_SQRT_PRICE_X64 - 1
+    });
+    require!(
+        if zero_for_one {
+            sqrt_price_limit_x64 < pool_state.sqrt_price_x64
+                && sqrt_price_limit_x64 > tick_math::MIN_SQRT_PRICE_X64
+        } else {
+            sqrt_price_limit_x64 > pool_state.sqrt_price_x64
+                && sqrt_price_limit_x64 < tick_math::MAX_SQRT_PRICE_X64
+        },
+        ErrorCode::SqrtPriceLimitOverflow
+    );
+
+    let mut tick_arrays = SnapshotTickArrays {
+        tick_arrays: BTreeMap::new(),
+        current: Box::default(),
+        start_indexes: Vec::new(),
+        ticks_crossed: Vec::new(),
+    };
+    for tick_array in snapshot.tick_arrays {
+        require_keys_eq!(tick_array.pool_id, pool_state.key());
+        tick_arrays
+            .tick_arrays
+            .insert(tick_array.start_tick_index, tick_array);
+    }
+
+    let mut state = SwapState {
+        amount_specified_remaining: amount_specified,
+        amount_calculated: 0,
+        sqrt_price_x64: pool_state.sqrt_price_x64,
+        tick: pool_state.tick_current,
+        fee_growth_global_x64: if zero_for_one {
+            pool_state.fee_growth_global_0_x64
+        } else {
+            pool_state.fee_growth_global_1_x64
+        },
+        fee_amount: 0,
+        protocol_fee: 0,
+        fund_fee: 0,
+        liquidity: pool_state.liquidity,
+        boosted_liquidity: pool_state.boosted_liquidity,
+    };
+    // the reward growths of the crossed ticks are only updated in the copies of the tick arrays
+    let reward_infos = pool_state.reward_infos;
+    swap_steps(
+        amm_config,
+        pool_state,
+        &mut tick_arrays,
+        tickarray_bitmap_extension,
+        &mut state,
+        &reward_infos,
+        Some(&[RewardInfo::default(); EXTENSION_REWARD_NUM]),
+        sqrt_price_limit_x64,
+        zero_for_one,
+        is_base_input,
+        block_timestamp,
+    )?;
+
+    let amount_swapped = amount_specified - state.amount_specified_remaining;
+    let (amount_in, amount_out) = if is_base_input {
+        (amount_swapped, state.amount_calculated)
+    } else {
+        (state.amount_calculated, amount_swapped)
+    };
+    let quote = SwapQuote {
+        amount_in,
+        amount_out,
+        amount_specified_remaining: state.amount_specified_remaining,
+        lp_fee: state.fee_amount,
+        protocol_fee: state.protocol_fee,
+        fund_fee: state.fund_fee,
+        trade_fee_rate: amm_config.get_trade_fee_rate(pool_state.volatility_accumulator),
+        sqrt_price_x64: state.sqrt_price_x64,
+        tick: state.tick,
+        liquidity: state.liquidity,
+        boosted_liquidity: state.boosted_liquidity,
+        tick_array_keys: tick_arrays
+            .start_indexes
+            .iter()
+            .map(|start_tick_index| tick_arrays.tick_arrays[start_tick_index].key())
+            .collect(),
+        tick_array_start_indexes: tick_arrays.start_indexes,
+        ticks_crossed: tick_arrays.ticks_crossed,
+    };
+
+    Ok(quote)
+}
@@ -2,9 +2,63 @@ use crate::error::ErrorCode;
 use anchor_lang::prelude::*;
 
 pub const AMM_CONFIG_SEED: &str = "amm_config";
+pub const AMM_CONFIG_PENDING_OWNER_SEED: &str = "amm_config_pending_owner";
 
 pub const FEE_RATE_DENOMINATOR_VALUE: u32 = 1_000_000;
 
+/// A change of a single field of the amm config
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AmmConfigUpdate {
+    /// The trade fee, denominated in hundredths of a bip (10^-6)
+    TradeFeeRate(u32),
+    /// The protocol fee, the rate of protocol fee within trade fee
+    ProtocolFeeRate(u32),
+    /// The fund fee, the rate of fund fee within trade fee
+    FundFeeRate(u32),
+    /// The protocol owner, only applied once the proposed owner accepts
+    Owner(Pubkey),
+    /// The owner of the fund fees, only applied once the proposed owner accepts
+    FundOwner(Pubkey),
+    /// The dynamic fee surcharge per tick spacing of volatility, zero disables the dynamic fee
+    DynamicFeeControl(u32),
+    /// The lower bound of the dynamic trade fee
+    MinTradeFeeRate(u32),
+    /// The upper bound of the dynamic trade fee
+    MaxTradeFeeRate(u32),
+    /// The seconds after which the volatility reference tick moves
+    VolatilityFilterPeriod(u32),
+    /// The seconds over which the accumulated volatility decays
+    VolatilityDecayPeriod(u32),
+    /// The status inherited by every pool of the config, disabling bits applies instantly
+    /// while enabling them must be scheduled
+    Status(u8),
+}
+
+impl AmmConfigUpdate {
+    pub fn is_owner_change(&self) -> bool {
+        matches!(
+            self,
+            AmmConfigUpdate::Owner(_) | AmmConfigUpdate::FundOwner(_)
+        )
+    }
+
+    /// Changes of the fee rates and of the volatility periods the dynamic fee is measured over
+    /// must be scheduled and only apply after the admin change delay
+    pub fn is_fee_rate_change(&self) -> bool {
+        matches!(
+            self,
+            AmmConfigUpdate::TradeFeeRate(_)
+                | AmmConfigUpdate::ProtocolFeeRate(_)
+                | AmmConfigUpdate::FundFeeRate(_)
+                | AmmConfigUpdate::DynamicFeeControl(_)
+                | AmmConfigUpdate::MinTradeFeeRate(_)
+                | AmmConfigUpdate::MaxTradeFeeRate(_)
+                | AmmConfigUpdate::VolatilityFilterPeriod(_)
+                | AmmConfigUpdate::VolatilityDecayPeriod(_)
+        )
+    }
+}
+
 /// Holds the current owner of the factory
 #[account]
 #[derive(Default, Debug)]
@@ -22,10 +76,23 @@ pub struct AmmConfig {
     pub tick_spacing: u16,
     /// The fund fee, denominated in hundredths of a bip (10^-6)
     pub fund_fee_rate: u32,
-    // padding space for upgrade
-    pub padding_u32: u32,
+    /// The dynamic fee surcharge per tick spacing of accumulated volatility, denominated in hundredths of a bip (10^-6).
+    /// Zero disables the dynamic fee, the trade fee is then always trade_fee_rate
+    pub dynamic_fee_control: u32,
     pub fund_owner: Pubkey,
-    pub padding: [u64; 3],
+    /// The lower bound of the dynamic trade fee, denominated in hundredths of a bip (10^-6)
+    pub min_trade_fee_rate: u32,
+    /// The upper bound of the dynamic trade fee, denominated in hundredths of a bip (10^-6)
+    pub max_trade_fee_rate: u32,
+    /// The seconds since the last swap after which the volatility reference tick moves to the current tick
+    pub volatility_filter_period: u32,
+    /// The seconds since the last swap after which the accumulated volatility is fully decayed
+    pub volatility_decay_period: u32,
+    /// Bitwise representation of the status inherited by every pool of the config,
+    /// with the same bits as the status of the pool, see `PoolStatusBitIndex`
+    pub status: u8,
+    // padding space for upgrade
+    pub padding: [u8; 7],
 }
 
 impl AmmConfig {
@@ -42,6 +109,137 @@ impl AmmConfig {
         );
         Ok(())
     }
+
+    /// Apply a single field change, the fee rates are checked against the other fee rates of the config
+    pub fn apply_update(&mut self, update: AmmConfigUpdate) -> Result<()> {
+        match update {
+            AmmConfigUpdate::TradeFeeRate(trade_fee_rate) => {
+                require_gt!(
+                    FEE_RATE_DENOMINATOR_VALUE,
+                    trade_fee_rate,
+                    ErrorCode::InvalidTradeFeeRate
+                );
+                self.trade_fee_rate = trade_fee_rate;
+            }
+            AmmConfigUpdate::ProtocolFeeRate(protocol_fee_rate) => {
+                require!(
+                    u64::from(protocol_fee_rate) + u64::from(self.fund_fee_rate)
+                        <= u64::from(FEE_RATE_DENOMINATOR_VALUE),
+                    ErrorCode::InvalidProtocolFeeRate
+                );
+                self.protocol_fee_rate = protocol_fee_rate;
+            }
+            AmmConfigUpdate::FundFeeRate(fund_fee_rate) => {
+                require!(
+                    u64::from(fund_fee_rate) + u64::from(self.protocol_fee_rate)
+                        <= u64::from(FEE_RATE_DENOMINATOR_VALUE),
+                    ErrorCode::InvalidFundFeeRate
+                );
+                self.fund_fee_rate = fund_fee_rate;
+            }
+            AmmConfigUpdate::Owner(owner) => self.owner = owner,
+            AmmConfigUpdate::FundOwner(fund_owner) => self.fund_owner = fund_owner,
+            AmmConfigUpdate::DynamicFeeControl(dynamic_fee_control) => {
+                // the bounds must be set before the dynamic fee is enabled
+                require!(
+                    dynamic_fee_control == 0 || self.max_trade_fee_rate > 0,
+                    ErrorCode::InvalidDynamicFeeBounds
+                );
+                self.dynamic_fee_control = dynamic_fee_control;
+            }
+            AmmConfigUpdate::MinTradeFeeRate(min_trade_fee_rate) => {
+                require_gte!(
+                    self.max_trade_fee_rate,
+                    min_trade_fee_rate,
+                    ErrorCode::InvalidDynamicFeeBounds
+                );
+                self.min_trade_fee_rate = min_trade_fee_rate;
+            }
+            AmmConfigUpdate::MaxTradeFeeRate(max_trade_fee_rate) => {
+                require!(
+                    max_trade_fee_rate < FEE_RATE_DENOMINATOR_VALUE
+                        && max_trade_fee_rate >= self.min_trade_fee_rate
+                        && (max_trade_fee_rate > 0 || self.dynamic_fee_control == 0),
+                    ErrorCode::InvalidDynamicFeeBounds
+                );
+                self.max_trade_fee_rate = max_trade_fee_rate;
+            }
+            AmmConfigUpdate::VolatilityFilterPeriod(volatility_filter_period) => {
+                self.volatility_filter_period = volatility_filter_period
+            }
+            AmmConfigUpdate::VolatilityDecayPeriod(volatility_decay_period) => {
+                self.volatility_decay_period = volatility_decay_period
+            }
+            AmmConfigUpdate::Status(status) => self.status = status,
+        }
+        Ok(())
+    }
+
+    /// Whether the update enables a status bit of the config, which must be scheduled
+    pub fn is_status_enabling(&self, update: AmmConfigUpdate) -> bool {
+        match update {
+            AmmConfigUpdate::Status(status) => status & self.status != self.status,
+            _ => false,
+        }
+    }
+
+    /// Check the trade fee and the split of the trade fee into protocol and fund fee,
+    /// the trade fee can't be above the upper bound of the dynamic fee when it is enabled
+    pub fn check_fee_rates(&self) -> Result<()> {
+        require_gt!(
+            FEE_RATE_DENOMINATOR_VALUE,
+            self.trade_fee_rate,
+            ErrorCode::InvalidTradeFeeRate
+        );
+        if self.is_dynamic_fee_enabled() {
+            require_gte!(
+                self.max_trade_fee_rate,
+                self.trade_fee_rate,
+                ErrorCode::InvalidTradeFeeRate
+            );
+        }
+        require!(
+            u64::from(self.protocol_fee_rate) + u64::from(self.fund_fee_rate)
+                <= u64::from(FEE_RATE_DENOMINATOR_VALUE),
+            ErrorCode::InvalidProtocolFeeRate
+        );
+        Ok(())
+    }
+
+    pub fn values(&self) -> AmmConfigValues {
+        AmmConfigValues {
+            owner: self.owner,
+            protocol_fee_rate: self.protocol_fee_rate,
+            trade_fee_rate: self.trade_fee_rate,
+            tick_spacing: self.tick_spacing,
+            fund_fee_rate: self.fund_fee_rate,
+            fund_owner: self.fund_owner,
+            dynamic_fee_control: self.dynamic_fee_control,
+            min_trade_fee_rate: self.min_trade_fee_rate,
+            max_trade_fee_rate: self.max_trade_fee_rate,
+            volatility_filter_period: self.volatility_filter_period,
+            volatility_decay_period: self.volatility_decay_period,
+            status: self.status,
+        }
+    }
+
+    pub fn is_dynamic_fee_enabled(&self) -> bool {
+        self.dynamic_fee_control != 0
+    }
+
+    /// The trade fee rate of a swap step, the base trade fee plus the volatility surcharge
+    /// bounded by the min and max trade fee when the dynamic fee is enabled.
+    pub fn get_trade_fee_rate(&self, volatility_accumulator: u32) -> u32 {
+        if !self.is_dynamic_fee_enabled() {
+            return self.trade_fee_rate;
+        }
+        let surcharge = u64::from(volatility_accumulator) * u64::from(self.dynamic_fee_control);
+        let trade_fee_rate = (u64::from(self.trade_fee_rate) + surcharge)
+            .min(u64::from(self.max_trade_fee_rate))
+            .max(u64::from(self.min_trade_fee_rate));
+        // the bounds are checked to be below the denominator when they are set
+        trade_fee_rate.min(u64::from(FEE_RATE_DENOMINATOR_VALUE - 1)) as u32
+    }
 }
 ///This line says, "If the client feature is enabled, apply the derive(Debug) attribute to the struct."
 ///we enable this cfg_attr in the cargo.toml file like this :
@@ -65,3 +263,251 @@ pub struct ConfigChangeEvent {
     pub fund_fee_rate: u32,
     pub fund_owner: Pubkey,
 }
+
+/// Holds the owners proposed for an amm config until they sign to accept
+#[account]
+#[derive(Default, Debug)]
+pub struct AmmConfigPendingOwner {
+    /// Bump to identify PDA
+    pub bump: u8,
+    /// The amm config the owners are proposed for
+    pub amm_config: Pubkey,
+    /// The proposed protocol owner, default if none
+    pub pending_owner: Pubkey,
+    /// The proposed fund owner, default if none
+    pub pending_fund_owner: Pubkey,
+    // padding space for upgrade
+    pub padding: [u64; 4],
+}
+
+impl AmmConfigPendingOwner {
+    pub const LEN: usize = 8 + 1 + 32 * 3 + 8 * 4;
+
+    /// Record the proposed owners, none keeps the current proposal and the default key clears it
+    pub fn propose(&mut self, new_owner: Option<Pubkey>, new_fund_owner: Option<Pubkey>) {
+        if let Some(new_owner) = new_owner {
+            self.pending_owner = new_owner;
+        }
+        if let Some(new_fund_owner) = new_fund_owner {
+            self.pending_fund_owner = new_fund_owner;
+        }
+    }
+
+    /// Take every proposal made to the signer, returns the owner changes to apply to the config
+    pub fn accept(&mut self, signer: Pubkey) -> Result<Vec<AmmConfigUpdate>> {
+        require_keys_neq!(signer, Pubkey::default(), ErrorCode::NotPendingOwner);
+        let mut updates = Vec::new();
+        if self.pending_owner == signer {
+            updates.push(AmmConfigUpdate::Owner(signer));
+            self.pending_owner = Pubkey::default();
+        }
+        if self.pending_fund_owner == signer {
+            updates.push(AmmConfigUpdate::FundOwner(signer));
+            self.pending_fund_owner = Pubkey::default();
+        }
+        require!(!updates.is_empty(), ErrorCode::NotPendingOwner);
+        Ok(updates)
+    }
+}
+
+/// The updatable values of an amm config
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct AmmConfigValues {
+    pub owner: Pubkey,
+    pub protocol_fee_rate: u32,
+    pub trade_fee_rate: u32,
+    pub tick_spacing: u16,
+    pub fund_fee_rate: u32,
+    pub fund_owner: Pubkey,
+    pub dynamic_fee_control: u32,
+    pub min_trade_fee_rate: u32,
+    pub max_trade_fee_rate: u32,
+    pub volatility_filter_period: u32,
+    pub volatility_decay_period: u32,
+    pub status: u8,
+}
+
+/// Emitted when a config is updated, with the values of the config before and after the update
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct AmmConfigUpdateEvent {
+    #[index]
+    pub amm_config: Pubkey,
+    pub index: u16,
+    pub update: AmmConfigUpdate,
+    pub before: AmmConfigValues,
+    pub after: AmmConfigValues,
+}
+
+/// Emitted when the status of a config is changed, the status is inherited by every pool of the config
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct AmmConfigStatusChangeEvent {
+    #[index]
+    pub amm_config: Pubkey,
+    /// The `PoolStatusBitIndex` bits flipped to disabled
+    pub disabled_bits: u8,
+    /// The `PoolStatusBitIndex` bits flipped to enabled
+    pub enabled_bits: u8,
+    /// The status of the config after the change
+    pub status: u8,
+}
+
+#[cfg(test)]
+mod amm_config_test {
+    use super::*;
+    use crate::states::PoolStatusBitIndex;
+
+    #[test]
+    fn apply_status_update_test() {
+        let mut amm_config = AmmConfig::default();
+        let swap = 1 << PoolStatusBitIndex::Swap as u8;
+        let deposit = 1 << PoolStatusBitIndex::OpenPositionOrIncreaseLiquidity as u8;
+
+        assert!(!amm_config.is_status_enabling(AmmConfigUpdate::Status(swap)));
+        amm_config
+            .apply_update(AmmConfigUpdate::Status(swap))
+            .unwrap();
+        assert_eq!(amm_config.status, swap);
+        assert_eq!(amm_config.values().status, swap);
+
+        assert!(!amm_config.is_status_enabling(AmmConfigUpdate::Status(swap | deposit)));
+        assert!(amm_config.is_status_enabling(AmmConfigUpdate::Status(deposit)));
+        assert!(amm_config.is_status_enabling(AmmConfigUpdate::Status(0)));
+        assert!(!amm_config.is_status_enabling(AmmConfigUpdate::TradeFeeRate(0)));
+    }
+
+    #[test]
+    fn apply_fee_rate_update_test() {
+        let mut amm_config = AmmConfig {
+            trade_fee_rate: 1000,
+            protocol_fee_rate: 120000,
+            fund_fee_rate: 40000,
+            ..Default::default()
+        };
+        amm_config
+            .apply_update(AmmConfigUpdate::TradeFeeRate(2500))
+            .unwrap();
+        assert_eq!(amm_config.trade_fee_rate, 2500);
+        assert!(amm_config
+            .apply_update(AmmConfigUpdate::TradeFeeRate(FEE_RATE_DENOMINATOR_VALUE))
+            .is_err());
+
+        amm_config
+            .apply_update(AmmConfigUpdate::ProtocolFeeRate(960000))
+            .unwrap();
+        assert!(amm_config
+            .apply_update(AmmConfigUpdate::ProtocolFeeRate(960001))
+            .is_err());
+        assert!(amm_config
+            .apply_update(AmmConfigUpdate::FundFeeRate(40001))
+            .is_err());
+        assert!(amm_config
+            .apply_update(AmmConfigUpdate::FundFeeRate(u32::MAX))
+            .is_err());
+        assert_eq!(amm_config.protocol_fee_rate, 960000);
+        assert_eq!(amm_config.fund_fee_rate, 40000);
+    }
+
+    #[test]
+    fn apply_dynamic_fee_update_test() {
+        let mut amm_config = AmmConfig::default();
+        // the dynamic fee can't be enabled without an upper bound
+        assert!(amm_config
+            .apply_update(AmmConfigUpdate::DynamicFeeControl(10))
+            .is_err());
+        assert!(amm_config
+            .apply_update(AmmConfigUpdate::MinTradeFeeRate(500))
+            .is_err());
+        amm_config
+            .apply_update(AmmConfigUpdate::MaxTradeFeeRate(10000))
+            .unwrap();
+        amm_config
+            .apply_update(AmmConfigUpdate::MinTradeFeeRate(500))
+            .unwrap();
+        amm_config
+            .apply_update(AmmConfigUpdate::DynamicFeeControl(10))
+            .unwrap();
+        assert!(amm_config
+            .apply_update(AmmConfigUpdate::MaxTradeFeeRate(400))
+            .is_err());
+        assert!(amm_config
+            .apply_update(AmmConfigUpdate::MaxTradeFeeRate(FEE_RATE_DENOMINATOR_VALUE))
+            .is_err());
+        assert_eq!(amm_config.max_trade_fee_rate, 10000);
+        assert!(amm_config.is_dynamic_fee_enabled());
+    }
+
+    #[test]
+    fn accept_owner_with_wrong_signer_test() {
+        let mut pending_owner = AmmConfigPendingOwner::default();
+        let new_owner = Pubkey::new_unique();
+        pending_owner.propose(Some(new_owner), None);
+
+        assert_eq!(
+            pending_owner.accept(Pubkey::new_unique()).unwrap_err(),
+            ErrorCode::NotPendingOwner.into()
+        );
+        // nothing is proposed for the fund owner, the default key can't take it
+        assert_eq!(
+            pending_owner.accept(Pubkey::default()).unwrap_err(),
+            ErrorCode::NotPendingOwner.into()
+        );
+        assert_eq!(pending_owner.pending_owner, new_owner);
+
+        assert_eq!(
+            pending_owner.accept(new_owner).unwrap(),
+            vec![AmmConfigUpdate::Owner(new_owner)]
+        );
+        assert_eq!(pending_owner.pending_owner, Pubkey::default());
+        // a proposal can only be accepted once
+        assert!(pending_owner.accept(new_owner).is_err());
+    }
+
+    #[test]
+    fn accept_owner_and_fund_owner_with_same_key_test() {
+        let mut pending_owner = AmmConfigPendingOwner::default();
+        let new_owner = Pubkey::new_unique();
+        pending_owner.propose(Some(new_owner), Some(new_owner));
+
+        let updates = pending_owner.accept(new_owner).unwrap();
+        assert_eq!(
+            updates,
+            vec![
+                AmmConfigUpdate::Owner(new_owner),
+                AmmConfigUpdate::FundOwner(new_owner)
+            ]
+        );
+        assert_eq!(pending_owner.pending_owner, Pubkey::default());
+        assert_eq!(pending_owner.pending_fund_owner, Pubkey::default());
+
+        let mut amm_config = AmmConfig::default();
+        for update in updates {
+            amm_config.apply_update(update).unwrap();
+        }
+        assert_eq!(amm_config.owner, new_owner);
+        assert_eq!(amm_config.fund_owner, new_owner);
+    }
+
+    #[test]
+    fn clear_owner_proposal_test() {
+        let mut pending_owner = AmmConfigPendingOwner::default();
+        let new_owner = Pubkey::new_unique();
+        let new_fund_owner = Pubkey::new_unique();
+        pending_owner.propose(Some(new_owner), Some(new_fund_owner));
+
+        // none keeps the fund owner proposal, the default key clears the owner proposal
+        pending_owner.propose(Some(Pubkey::default()), None);
+        assert_eq!(pending_owner.pending_owner, Pubkey::default());
+        assert_eq!(pending_owner.pending_fund_owner, new_fund_owner);
+        assert_eq!(
+            pending_owner.accept(new_owner).unwrap_err(),
+            ErrorCode::NotPendingOwner.into()
+        );
+        assert_eq!(
+            pending_owner.accept(new_fund_owner).unwrap(),
+            vec![AmmConfigUpdate::FundOwner(new_fund_owner)]
+        );
+    }
+}
@@ -0,0 +1,217 @@
+use crate::error::ErrorCode;
+use anchor_lang::prelude::*;
+
+pub const FEE_DISTRIBUTION_SEED: &str = "fee_distribution";
+pub const FEE_RECIPIENT_SIZE_USIZE: usize = 8;
+pub const FEE_SHARE_DENOMINATOR_BPS: u16 = 10_000;
+
+/// A recipient of the protocol or fund fees with its share in basis points
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct FeeRecipient {
+    /// The owner of the token accounts receiving the fees
+    pub recipient: Pubkey,
+    pub share_bps: u16,
+}
+
+/// Holds how the protocol and fund fees of the pools of an amm config are split
+#[account]
+#[derive(Default, Debug)]
+pub struct FeeDistributionState {
+    /// Bump to identify PDA
+    pub bump: u8,
+    pub amm_config: Pubkey,
+    /// The recipients of the protocol fees, set by the admin.
+    /// The shares sum up to `FEE_SHARE_DENOMINATOR_BPS`, an empty list leaves the protocol fees in the pool
+    pub protocol_recipients: Vec<FeeRecipient>,
+    /// The recipients of the fund fees, set by the fund owner of the amm config.
+    /// The shares sum up to `FEE_SHARE_DENOMINATOR_BPS`, an empty list leaves the fund fees in the pool
+    pub fund_recipients: Vec<FeeRecipient>,
+    // padding space for upgrade
+    pub padding: [u64; 8],
+}
+
+impl FeeDistributionState {
+    pub const LEN: usize = 8 + 1 + 32 + (4 + (32 + 2) * FEE_RECIPIENT_SIZE_USIZE) * 2 + 8 * 8;
+
+    pub fn set_protocol_recipients(&mut self, recipients: Vec<FeeRecipient>) -> Result<()> {
+        Self::check_recipients(&recipients)?;
+        self.protocol_recipients = recipients;
+        Ok(())
+    }
+
+    pub fn set_fund_recipients(&mut self, recipients: Vec<FeeRecipient>) -> Result<()> {
+        Self::check_recipients(&recipients)?;
+        self.fund_recipients = recipients;
+        Ok(())
+    }
+
+    /// An empty list is valid, otherwise the shares of distinct recipients must sum up to 100%
+    fn check_recipients(recipients: &[FeeRecipient]) -> Result<()> {
+        if recipients.is_empty() {
+            return Ok(());
+        }
+        require!(
+            recipients.len() <= FEE_RECIPIENT_SIZE_USIZE,
+            ErrorCode::InvalidFeeDistribution
+        );
+        let mut total_share_bps: u32 = 0;
+        for (i, fee_recipient) in recipients.iter().enumerate() {
+            require!(
+                fee_recipient.recipient != Pubkey::default()
+                    && fee_recipient.share_bps > 0
+                    && !recipients[..i]
+                        .iter()
+                        .any(|other| other.recipient == fee_recipient.recipient),
+                ErrorCode::InvalidFeeDistribution
+            );
+            total_share_bps += u32::from(fee_recipient.share_bps);
+        }
+        require_eq!(
+            total_share_bps,
+            u32::from(FEE_SHARE_DENOMINATOR_BPS),
+            ErrorCode::InvalidFeeDistribution
+        );
+        Ok(())
+    }
+
+    /// Split the amount by the shares of the recipients, the rounding dust goes to the last recipient
+    pub fn split(recipients: &[FeeRecipient], amount: u64) -> Vec<u64> {
+        let mut remaining = amount;
+        let mut amounts = Vec::with_capacity(recipients.len());
+        for (i, fee_recipient) in recipients.iter().enumerate() {
+            let share = if i == recipients.len() - 1 {
+                remaining
+            } else {
+                u64::try_from(
+                    u128::from(amount) * u128::from(fee_recipient.share_bps)
+                        / u128::from(FEE_SHARE_DENOMINATOR_BPS),
+                )
+                .unwrap()
+            };
+            remaining -= share;
+            amounts.push(share);
+        }
+        amounts
+    }
+}
+
+/// Emitted when the fee distribution of an amm config is set
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct FeeDistributionChangeEvent {
+    #[index]
+    pub amm_config: Pubkey,
+    pub protocol_recipients: Vec<FeeRecipient>,
+    pub fund_recipients: Vec<FeeRecipient>,
+}
+
+/// Emitted for each recipient when the protocol or fund fees of a pool are distributed
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct FeeDistributedEvent {
+    #[index]
+    pub pool_state: Pubkey,
+    /// Whether the amounts come from the fund fees rather than the protocol fees
+    pub fund_fee: bool,
+    pub recipient: Pubkey,
+    pub recipient_token_account_0: Pubkey,
+    pub recipient_token_account_1: Pubkey,
+    pub amount_0: u64,
+    pub amount_1: u64,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn recipients(shares_bps: &[u16]) -> Vec<FeeRecipient> {
+        shares_bps
+            .iter()
+            .map(|share_bps| FeeRecipient {
+                recipient: Pubkey::new_unique(),
+                share_bps: *share_bps,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn set_recipients_test() {
+        let mut fee_distribution = FeeDistributionState::default();
+        fee_distribution
+            .set_protocol_recipients(recipients(&[5000, 3000, 2000]))
+            .unwrap();
+        assert_eq!(fee_distribution.protocol_recipients.len(), 3);
+
+        // the shares must sum up to 100%
+        assert!(fee_distribution
+            .set_protocol_recipients(recipients(&[5000, 3000]))
+            .is_err());
+        assert!(fee_distribution
+            .set_protocol_recipients(recipients(&[5000, 3000, 2001]))
+            .is_err());
+        // empty share
+        assert!(fee_distribution
+            .set_protocol_recipients(recipients(&[10000, 0]))
+            .is_err());
+        // too many recipients
+        assert!(fee_distribution
+            .set_protocol_recipients(recipients(&[1000; FEE_RECIPIENT_SIZE_USIZE + 2]))
+            .is_err());
+        // duplicated recipient
+        let mut duplicated = recipients(&[5000, 5000]);
+        duplicated[1].recipient = duplicated[0].recipient;
+        assert!(fee_distribution
+            .set_protocol_recipients(duplicated)
+            .is_err());
+
+        // the failed updates keep the recipients
+        assert_eq!(fee_distribution.protocol_recipients.len(), 3);
+
+        // the fund recipients are kept apart from the protocol recipients
+        fee_distribution
+            .set_fund_recipients(recipients(&[10000]))
+            .unwrap();
+        assert_eq!(fee_distribution.protocol_recipients.len(), 3);
+        assert_eq!(fee_distribution.fund_recipients.len(), 1);
+        assert!(fee_distribution
+            .set_fund_recipients(recipients(&[5000]))
+            .is_err());
+
+        // an empty list stops distributing the fees
+        fee_distribution.set_fund_recipients(Vec::new()).unwrap();
+        assert!(fee_distribution.fund_recipients.is_empty());
+        assert_eq!(fee_distribution.protocol_recipients.len(), 3);
+    }
+
+    #[test]
+    fn split_test() {
+        let recipients = recipients(&[3333, 3333, 3334]);
+        assert_eq!(FeeDistributionState::split(&recipients, 10), vec![3, 3, 4]);
+        assert_eq!(
+            FeeDistributionState::split(&recipients, 100),
+            vec![33, 33, 34]
+        );
+        assert_eq!(FeeDistributionState::split(&recipients, 0), vec![0, 0, 0]);
+        assert_eq!(
+            FeeDistributionState::split(&recipients, u64::MAX)
+                .iter()
+                .sum::<u64>(),
+            u64::MAX
+        );
+        assert!(FeeDistributionState::split(&[], 100).is_empty());
+    }
+
+    #[test]
+    fn fee_distribution_len_test() {
+        let mut fee_distribution = FeeDistributionState::default();
+        fee_distribution
+            .set_protocol_recipients(recipients(&[1250; FEE_RECIPIENT_SIZE_USIZE]))
+            .unwrap();
+        fee_distribution
+            .set_fund_recipients(recipients(&[1250; FEE_RECIPIENT_SIZE_USIZE]))
+            .unwrap();
+        let mut data = Vec::new();
+        fee_distribution.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), FeeDistributionState::LEN);
+    }
+}
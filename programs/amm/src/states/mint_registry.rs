@@ -0,0 +1,317 @@
+use crate::error::ErrorCode;
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    transfer_fee::MAX_FEE_BASIS_POINTS, ExtensionType,
+};
+use std::str::FromStr;
+
+pub const MINT_REGISTRY_SEED: &str = "mint_registry";
+pub const MINT_REGISTRY_SIZE_USIZE: usize = 100;
+
+/// The extensions a token-2022 mint could have before the registry existed
+pub const DEFAULT_ALLOWED_EXTENSIONS: [ExtensionType; 4] = [
+    ExtensionType::TransferFeeConfig,
+    ExtensionType::MetadataPointer,
+    ExtensionType::TokenMetadata,
+    ExtensionType::InterestBearingConfig,
+];
+
+/// The mints supported whatever their extensions before the registry existed
+pub const DEFAULT_ALLOWED_MINTS: [&str; 6] = [
+    "HVbpJAQGNpkgBaYBZQBR1t7yFdvaYVp2vCQQfKKEN4tM",
+    "Crn4x1Y2HUKko7ox2EZMT6N2t2ZyH7eKtwkBGVnhEq1g",
+    "FrBfWJ4qE5sCzKm3k3JaAtqZcXUh4LvJygDeketsrsH4",
+    "2b1kV6DkPAnxd5ixfnxCpjxmKwqjjaYmCZfHsFu24GXo",
+    "DAUcJBg4jSpVoEzASxYzdqHMUN8vuTpQyG2TvDcCHfZg",
+    "AUSD1jCcCyPLybk1YnvPWsHQSrZ46dxwoMniN4N2UEB9",
+];
+
+pub enum RegisteredMintRestrictionBitIndex {
+    MaxTransferFee,
+}
+
+/// A change of the mint registry
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MintRegistryUpdate {
+    /// Allow the token-2022 extension type for every mint
+    AllowExtension(u16),
+    /// Disallow the token-2022 extension type, the registered mints are not affected
+    DisallowExtension(u16),
+    /// Allow the mint whatever its extensions, or update the restrictions of a registered mint
+    AddMint {
+        mint: Pubkey,
+        /// The highest transfer fee the mint may charge, none for no limit
+        max_transfer_fee_bps: Option<u16>,
+    },
+    /// Remove the mint from the registry
+    RemoveMint(Pubkey),
+}
+
+#[zero_copy(unsafe)]
+#[repr(C, packed)]
+#[derive(Default, Debug, PartialEq, Eq)]
+pub struct RegisteredMint {
+    pub mint: Pubkey,
+    /// Bitwise representation of the restrictions of the mint
+    /// bit0, 1: the transfer fee is capped by max_transfer_fee_bps
+    pub restriction_flag: u8,
+    /// The highest transfer fee of the mint, in basis points
+    pub max_transfer_fee_bps: u16,
+    pub padding: [u8; 5],
+}
+
+impl RegisteredMint {
+    pub fn is_restricted(&self, bit: RegisteredMintRestrictionBitIndex) -> bool {
+        self.restriction_flag & (1 << (bit as u8)) != 0
+    }
+}
+
+/// Holds the token-2022 mints and extension types supported by pools and rewards
+#[account(zero_copy(unsafe))]
+#[repr(C, packed)]
+#[derive(Debug)]
+pub struct MintRegistryState {
+    /// Bump to identify PDA
+    pub bump: u8,
+    /// Bitwise representation of the allowed extension types, bit n allows the extension type n
+    pub allowed_extension_flag: u64,
+    /// The mints allowed whatever their extensions
+    pub mints: [RegisteredMint; MINT_REGISTRY_SIZE_USIZE],
+    // Unused bytes for future upgrades.
+    pub padding: [u64; 16],
+}
+
+impl MintRegistryState {
+    pub const LEN: usize = 8 + 1 + 8 + 40 * MINT_REGISTRY_SIZE_USIZE + 8 * 16;
+
+    pub fn key() -> Pubkey {
+        Pubkey::find_program_address(&[MINT_REGISTRY_SEED.as_bytes()], &crate::id()).0
+    }
+
+    pub fn initialize(&mut self, bump: u8) {
+        self.bump = bump;
+        self.allowed_extension_flag = DEFAULT_ALLOWED_EXTENSIONS
+            .iter()
+            .fold(0, |flag, extension| flag | 1 << (*extension as u16));
+        self.mints = [RegisteredMint::default(); MINT_REGISTRY_SIZE_USIZE];
+        for (registered_mint, mint) in self.mints.iter_mut().zip(DEFAULT_ALLOWED_MINTS) {
+            registered_mint.mint = Pubkey::from_str(mint).unwrap();
+        }
+        self.padding = [0; 16];
+    }
+
+    /// Before the registry is created, the mints and extensions supported before the registry existed are allowed
+    pub fn is_default_supported(mint: Pubkey, extensions: &[ExtensionType]) -> bool {
+        DEFAULT_ALLOWED_MINTS.contains(&mint.to_string().as_str())
+            || extensions
+                .iter()
+                .all(|extension| DEFAULT_ALLOWED_EXTENSIONS.contains(extension))
+    }
+
+    pub fn is_extension_allowed(&self, extension: ExtensionType) -> bool {
+        let bit = extension as u16;
+        bit < 64 && self.allowed_extension_flag & (1 << bit) != 0
+    }
+
+    pub fn get_mint(&self, mint: Pubkey) -> Option<RegisteredMint> {
+        if mint == Pubkey::default() {
+            return None;
+        }
+        self.mints.iter().find(|item| item.mint == mint).copied()
+    }
+
+    pub fn apply_update(&mut self, update: MintRegistryUpdate) -> Result<()> {
+        match update {
+            MintRegistryUpdate::AllowExtension(extension) => {
+                require_gt!(64, extension, ErrorCode::InvalidExtensionType);
+                self.allowed_extension_flag |= 1 << extension;
+            }
+            MintRegistryUpdate::DisallowExtension(extension) => {
+                require_gt!(64, extension, ErrorCode::InvalidExtensionType);
+                self.allowed_extension_flag &= !(1 << extension);
+            }
+            MintRegistryUpdate::AddMint {
+                mint,
+                max_transfer_fee_bps,
+            } => {
+                require_keys_neq!(mint, Pubkey::default());
+                let mut registered_mint = RegisteredMint {
+                    mint,
+                    ..Default::default()
+                };
+                if let Some(max_transfer_fee_bps) = max_transfer_fee_bps {
+                    require_gte!(
+                        MAX_FEE_BASIS_POINTS,
+                        max_transfer_fee_bps,
+                        ErrorCode::InvalidMaxTransferFee
+                    );
+                    registered_mint.restriction_flag |=
+                        1 << (RegisteredMintRestrictionBitIndex::MaxTransferFee as u8);
+                    registered_mint.max_transfer_fee_bps = max_transfer_fee_bps;
+                }
+                let index = self
+                    .mints
+                    .iter()
+                    .position(|item| item.mint == mint)
+                    .or_else(|| {
+                        self.mints
+                            .iter()
+                            .position(|item| item.mint == Pubkey::default())
+                    })
+                    .ok_or(ErrorCode::MintRegistryFull)?;
+                self.mints[index] = registered_mint;
+            }
+            MintRegistryUpdate::RemoveMint(mint) => {
+                let mut mints = self.mints.to_vec();
+                mints.retain(|item| item.mint != Pubkey::default() && item.mint != mint);
+                // clear
+                self.mints = [RegisteredMint::default(); MINT_REGISTRY_SIZE_USIZE];
+                // update
+                self.mints[0..mints.len()].copy_from_slice(mints.as_slice());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Emitted when the mint registry is changed
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct MintRegistryChangeEvent {
+    pub update: MintRegistryUpdate,
+    pub allowed_extension_flag: u64,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn new_mint_registry() -> MintRegistryState {
+        let mut mint_registry = MintRegistryState {
+            bump: 0,
+            allowed_extension_flag: 0,
+            mints: [RegisteredMint::default(); MINT_REGISTRY_SIZE_USIZE],
+            padding: [0; 16],
+        };
+        mint_registry.initialize(0);
+        mint_registry
+    }
+
+    #[test]
+    fn registry_len_test() {
+        assert_eq!(
+            MintRegistryState::LEN,
+            core::mem::size_of::<MintRegistryState>() + 8
+        );
+    }
+
+    #[test]
+    fn default_supported_test() {
+        let mint_registry = new_mint_registry();
+        for mint in DEFAULT_ALLOWED_MINTS {
+            let mint = Pubkey::from_str(mint).unwrap();
+            assert!(mint_registry.get_mint(mint).is_some());
+            assert!(MintRegistryState::is_default_supported(
+                mint,
+                &[ExtensionType::PermanentDelegate]
+            ));
+        }
+        assert_eq!(
+            mint_registry.mints[DEFAULT_ALLOWED_MINTS.len()],
+            RegisteredMint::default()
+        );
+
+        let mint = Pubkey::new_unique();
+        assert!(MintRegistryState::is_default_supported(
+            mint,
+            &DEFAULT_ALLOWED_EXTENSIONS
+        ));
+        assert!(MintRegistryState::is_default_supported(mint, &[]));
+        assert!(!MintRegistryState::is_default_supported(
+            mint,
+            &[
+                ExtensionType::TransferFeeConfig,
+                ExtensionType::PermanentDelegate
+            ]
+        ));
+    }
+
+    #[test]
+    fn extension_update_test() {
+        let mut mint_registry = new_mint_registry();
+        for extension in DEFAULT_ALLOWED_EXTENSIONS {
+            assert!(mint_registry.is_extension_allowed(extension));
+        }
+        assert!(!mint_registry.is_extension_allowed(ExtensionType::PermanentDelegate));
+
+        mint_registry
+            .apply_update(MintRegistryUpdate::AllowExtension(
+                ExtensionType::PermanentDelegate as u16,
+            ))
+            .unwrap();
+        mint_registry
+            .apply_update(MintRegistryUpdate::DisallowExtension(
+                ExtensionType::TransferFeeConfig as u16,
+            ))
+            .unwrap();
+        assert!(mint_registry.is_extension_allowed(ExtensionType::PermanentDelegate));
+        assert!(!mint_registry.is_extension_allowed(ExtensionType::TransferFeeConfig));
+        assert!(mint_registry
+            .apply_update(MintRegistryUpdate::AllowExtension(64))
+            .is_err());
+    }
+
+    #[test]
+    fn mint_update_test() {
+        let mut mint_registry = new_mint_registry();
+        let mint_0 = Pubkey::new_unique();
+        let mint_1 = Pubkey::new_unique();
+        mint_registry
+            .apply_update(MintRegistryUpdate::AddMint {
+                mint: mint_0,
+                max_transfer_fee_bps: None,
+            })
+            .unwrap();
+        mint_registry
+            .apply_update(MintRegistryUpdate::AddMint {
+                mint: mint_1,
+                max_transfer_fee_bps: Some(100),
+            })
+            .unwrap();
+        assert!(!mint_registry
+            .get_mint(mint_0)
+            .unwrap()
+            .is_restricted(RegisteredMintRestrictionBitIndex::MaxTransferFee));
+        let registered_mint = mint_registry.get_mint(mint_1).unwrap();
+        assert!(registered_mint.is_restricted(RegisteredMintRestrictionBitIndex::MaxTransferFee));
+        assert_eq!({ registered_mint.max_transfer_fee_bps }, 100);
+
+        // adding a registered mint updates its restrictions
+        mint_registry
+            .apply_update(MintRegistryUpdate::AddMint {
+                mint: mint_1,
+                max_transfer_fee_bps: None,
+            })
+            .unwrap();
+        assert!(!mint_registry
+            .get_mint(mint_1)
+            .unwrap()
+            .is_restricted(RegisteredMintRestrictionBitIndex::MaxTransferFee));
+        // the default mints are seeded first
+        let seeded = DEFAULT_ALLOWED_MINTS.len();
+        assert_eq!(mint_registry.mints[seeded + 2], RegisteredMint::default());
+
+        mint_registry
+            .apply_update(MintRegistryUpdate::RemoveMint(mint_0))
+            .unwrap();
+        assert!(mint_registry.get_mint(mint_0).is_none());
+        assert_eq!({ mint_registry.mints[seeded].mint }, mint_1);
+        assert!(mint_registry.get_mint(Pubkey::default()).is_none());
+        assert!(mint_registry
+            .apply_update(MintRegistryUpdate::AddMint {
+                mint: mint_0,
+                max_transfer_fee_bps: Some(MAX_FEE_BASIS_POINTS + 1),
+            })
+            .is_err());
+    }
+}
@@ -1,19 +1,35 @@
 pub mod config;
+pub mod fee_distribution;
+pub mod mint_registry;
 pub mod operation_account;
 pub mod oracle;
+pub mod oracle_metrics;
 pub mod personal_position;
 pub mod pool;
+pub mod pool_reward_extension;
+pub mod position_lock;
 pub mod protocol_position;
+pub mod reward_schedule;
+pub mod scheduled_change;
 pub mod support_mint_associated;
+pub mod swap_allowlist;
 pub mod tick_array;
 pub mod tickarray_bitmap_extension;
 
 pub use config::*;
+pub use fee_distribution::*;
+pub use mint_registry::*;
 pub use operation_account::*;
 pub use oracle::*;
+pub use oracle_metrics::*;
 pub use personal_position::*;
 pub use pool::*;
+pub use pool_reward_extension::*;
+pub use position_lock::*;
 pub use protocol_position::*;
+pub use reward_schedule::*;
+pub use scheduled_change::*;
 pub use support_mint_associated::*;
+pub use swap_allowlist::*;
 pub use tick_array::*;
 pub use tickarray_bitmap_extension::*;
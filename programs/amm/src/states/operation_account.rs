@@ -1,3 +1,4 @@
+use crate::error::ErrorCode;
 use anchor_lang::prelude::*;
 use std::collections::HashSet;
 
@@ -5,6 +6,30 @@ pub const OPERATION_SEED: &str = "operation";
 pub const OPERATION_SIZE_USIZE: usize = 10;
 pub const WHITE_MINT_SIZE_USIZE: usize = 100;
 
+pub enum OperatorRoleBitIndex {
+    /// Initialize the last reward and set reward params without the pool owner limits
+    RewardAdmin,
+    /// Update the whitelist mints
+    WhitelistManager,
+    /// Pause swaps of a pool
+    PoolPauser,
+    /// Collect protocol and fund fees to the accounts of the config owners
+    FeeCollector,
+}
+
+pub const OPERATOR_ROLE_MASK: u8 = 0b1111;
+
+/// A change of the operation account
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub enum OperationAccountUpdate {
+    /// Set the roles of the operator, the operator is removed when the roles are empty
+    SetOperatorRoles { operator: Pubkey, roles: u8 },
+    /// Add the mints to the whitelist
+    AddWhitelistMints(Vec<Pubkey>),
+    /// Remove the mints from the whitelist
+    RemoveWhitelistMints(Vec<Pubkey>),
+}
+
 /// Holds the current owner of the factory
 /*
 Normally, Anchor performs some safety checks to ensure correctness (like alignment and bounds).
@@ -23,15 +48,36 @@ pub struct OperationState {
     pub operation_owners: [Pubkey; OPERATION_SIZE_USIZE],
     /// The mint address of whitelist to emit reward
     pub whitelist_mints: [Pubkey; WHITE_MINT_SIZE_USIZE],
+    /// Bitwise representation of the roles of the operation owner at the same index
+    /// bit0, 1: reward admin
+    /// bit1, 1: whitelist manager
+    /// bit2, 1: pool pauser
+    /// bit3, 1: fee collector
+    pub operation_owner_roles: [u8; OPERATION_SIZE_USIZE],
+    pub padding: [u64; 8],
 }
 
 impl OperationState {
-    pub const LEN: usize = 8 + 1 + 32 * OPERATION_SIZE_USIZE + 32 * WHITE_MINT_SIZE_USIZE;
+    pub const LEN: usize = 8
+        + 1
+        + 32 * OPERATION_SIZE_USIZE
+        + 32 * WHITE_MINT_SIZE_USIZE
+        + OPERATION_SIZE_USIZE
+        + 8 * 8;
+    /// The size of the accounts created before the operator roles existed
+    pub const LEGACY_LEN: usize = 8 + 1 + 32 * OPERATION_SIZE_USIZE + 32 * WHITE_MINT_SIZE_USIZE;
+
+    pub fn key() -> Pubkey {
+        Pubkey::find_program_address(&[OPERATION_SEED.as_bytes()], &crate::id()).0
+    }
+
     pub fn initialize(&mut self, bump: u8) {
         self.bump = bump;
         //Pubkey::default(); -> placeholder value
         self.operation_owners = [Pubkey::default(); OPERATION_SIZE_USIZE];
         self.whitelist_mints = [Pubkey::default(); WHITE_MINT_SIZE_USIZE];
+        self.operation_owner_roles = [0; OPERATION_SIZE_USIZE];
+        self.padding = [0; 8];
     }
 
     pub fn validate_operation_owner(&self, owner: Pubkey) -> bool {
@@ -42,29 +88,119 @@ impl OperationState {
         mint != Pubkey::default() && self.whitelist_mints.contains(&mint)
     }
 
+    pub fn operator_roles(&self, operator: Pubkey) -> u8 {
+        if operator == Pubkey::default() {
+            return 0;
+        }
+        let operation_owner_roles = self.operation_owner_roles;
+        match self.operation_owners.iter().position(|x| *x == operator) {
+            Some(index) => operation_owner_roles[index],
+            None => 0,
+        }
+    }
+
+    pub fn validate_operator_role(&self, operator: Pubkey, role: OperatorRoleBitIndex) -> bool {
+        self.operator_roles(operator) & (1 << (role as u8)) != 0
+    }
+
+    /// Set the roles of the operator and return the roles before,
+    /// the operator is added when it is new and removed when the roles are empty
+    pub fn set_operator_roles(&mut self, operator: Pubkey, roles: u8) -> Result<u8> {
+        require!(
+            operator != Pubkey::default() && roles & !OPERATOR_ROLE_MASK == 0,
+            ErrorCode::InvalidOperatorRoles
+        );
+        let roles_before = self.operator_roles(operator);
+        if roles == 0 {
+            self.remove_operation_owner(vec![operator]);
+            return Ok(roles_before);
+        }
+        if !self.validate_operation_owner(operator) {
+            require!(
+                self.operation_owners.contains(&Pubkey::default()),
+                ErrorCode::OperationOwnerListFull
+            );
+            self.update_operation_owner(vec![operator]);
+        }
+        let index = self
+            .operation_owners
+            .iter()
+            .position(|x| *x == operator)
+            .unwrap();
+        self.operation_owner_roles[index] = roles;
+        Ok(roles_before)
+    }
+
+    /// Load a copy of the operation account, the accounts created before the operator roles existed
+    /// are shorter and their operators are reward admins until `update_operation_account` resizes them
+    pub fn load_with_roles(loader: &AccountLoader<OperationState>) -> Result<Box<OperationState>> {
+        let account_info = loader.to_account_info();
+        let data = account_info.try_borrow_data()?;
+        require_gte!(
+            data.len(),
+            Self::LEGACY_LEN,
+            anchor_lang::error::ErrorCode::AccountDidNotDeserialize
+        );
+        Ok(Self::from_account_data(&data))
+    }
+
+    fn from_account_data(data: &[u8]) -> Box<OperationState> {
+        let mut account_data = vec![0u8; Self::LEN];
+        let len = data.len().min(Self::LEN);
+        account_data[..len].copy_from_slice(&data[..len]);
+        let mut operation_state =
+            Box::new(*bytemuck::from_bytes::<OperationState>(&account_data[8..]));
+        if data.len() < Self::LEN {
+            operation_state.grant_legacy_roles_to_operators();
+        }
+        operation_state
+    }
+
+    /// The operators of the accounts created before the roles existed could only manage rewards
+    pub fn grant_legacy_roles_to_operators(&mut self) {
+        for i in 0..OPERATION_SIZE_USIZE {
+            if self.operation_owners[i] != Pubkey::default() {
+                self.operation_owner_roles[i] = 1 << (OperatorRoleBitIndex::RewardAdmin as u8);
+            }
+        }
+    }
+
+    /// Add the operation owners without roles, the roles of the existing owners are kept
     pub fn update_operation_owner(&mut self, keys: Vec<Pubkey>) {
-        let mut operation_owners = self.operation_owners.to_vec();
-        operation_owners.extend(keys.as_slice().iter());
+        let mut operation_owners: Vec<(Pubkey, u8)> = self
+            .operation_owners
+            .into_iter()
+            .zip(self.operation_owner_roles)
+            .collect();
+        operation_owners.extend(keys.iter().map(|key| (*key, 0)));
         //retain the operation_size_usize
-        operation_owners.retain(|&item| item != Pubkey::default());
-        let owners_set: HashSet<Pubkey> = HashSet::from_iter(operation_owners.iter().cloned());
-        let mut updated_owner: Vec<Pubkey> = owners_set.into_iter().collect();
-        updated_owner.sort_by(|a, b| a.cmp(b));
-        // clear
-        self.operation_owners = [Pubkey::default(); OPERATION_SIZE_USIZE];
-        // update
-        self.operation_owners[0..updated_owner.len()].copy_from_slice(updated_owner.as_slice());
+        operation_owners.retain(|&(item, _)| item != Pubkey::default());
+        let mut owners_set: HashSet<Pubkey> = HashSet::new();
+        operation_owners.retain(|&(item, _)| owners_set.insert(item));
+        operation_owners.sort_by(|a, b| a.0.cmp(&b.0));
+        self.set_operation_owners(operation_owners);
     }
 
     pub fn remove_operation_owner(&mut self, keys: Vec<Pubkey>) {
-        let mut operation_owners = self.operation_owners.to_vec();
+        let mut operation_owners: Vec<(Pubkey, u8)> = self
+            .operation_owners
+            .into_iter()
+            .zip(self.operation_owner_roles)
+            .collect();
         // remove keys from operation_owners
-        operation_owners.retain(|x| !keys.contains(&x));
+        operation_owners.retain(|(x, _)| *x != Pubkey::default() && !keys.contains(x));
+        self.set_operation_owners(operation_owners);
+    }
+
+    fn set_operation_owners(&mut self, operation_owners: Vec<(Pubkey, u8)>) {
         // clear
         self.operation_owners = [Pubkey::default(); OPERATION_SIZE_USIZE];
+        self.operation_owner_roles = [0; OPERATION_SIZE_USIZE];
         // update
-        self.operation_owners[0..operation_owners.len()]
-            .copy_from_slice(operation_owners.as_slice());
+        for (i, (owner, roles)) in operation_owners.into_iter().enumerate() {
+            self.operation_owners[i] = owner;
+            self.operation_owner_roles[i] = roles;
+        }
     }
 
     pub fn update_whitelist_mint(&mut self, keys: Vec<Pubkey>) {
@@ -90,6 +226,15 @@ impl OperationState {
     }
 }
 
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct OperatorRoleChangeEvent {
+    #[index]
+    pub operator: Pubkey,
+    pub roles_before: u8,
+    pub roles_after: u8,
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -100,6 +245,8 @@ mod test {
             bump: 0,
             operation_owners: [Pubkey::default(); OPERATION_SIZE_USIZE],
             whitelist_mints: [Pubkey::default(); WHITE_MINT_SIZE_USIZE],
+            operation_owner_roles: [0; OPERATION_SIZE_USIZE],
+            padding: [0; 8],
         };
         let mut keys = Vec::new();
         keys.push(Pubkey::new_unique());
@@ -123,6 +270,8 @@ mod test {
             bump: 0,
             operation_owners: [Pubkey::default(); OPERATION_SIZE_USIZE],
             whitelist_mints: [Pubkey::default(); WHITE_MINT_SIZE_USIZE],
+            operation_owner_roles: [0; OPERATION_SIZE_USIZE],
+            padding: [0; 8],
         };
         operation_state.operation_owners[0] = Pubkey::new_unique();
         operation_state.operation_owners[1] = Pubkey::new_unique();
@@ -145,6 +294,8 @@ mod test {
             bump: 0,
             operation_owners: [Pubkey::default(); OPERATION_SIZE_USIZE],
             whitelist_mints: [Pubkey::default(); WHITE_MINT_SIZE_USIZE],
+            operation_owner_roles: [0; OPERATION_SIZE_USIZE],
+            padding: [0; 8],
         };
         operation_state.operation_owners[0] = Pubkey::new_unique();
         operation_state.operation_owners[1] = Pubkey::new_unique();
@@ -167,6 +318,8 @@ mod test {
             bump: 0,
             operation_owners: [Pubkey::default(); OPERATION_SIZE_USIZE],
             whitelist_mints: [Pubkey::default(); WHITE_MINT_SIZE_USIZE],
+            operation_owner_roles: [0; OPERATION_SIZE_USIZE],
+            padding: [0; 8],
         };
         let mut keys = Vec::new();
         for _i in 0..10 {
@@ -190,6 +343,8 @@ mod test {
             bump: 0,
             operation_owners: [Pubkey::default(); OPERATION_SIZE_USIZE],
             whitelist_mints: [Pubkey::default(); WHITE_MINT_SIZE_USIZE],
+            operation_owner_roles: [0; OPERATION_SIZE_USIZE],
+            padding: [0; 8],
         };
         let mut keys = Vec::new();
         for _i in 0..11 {
@@ -207,6 +362,8 @@ mod test {
             bump: 0,
             operation_owners: [Pubkey::default(); OPERATION_SIZE_USIZE],
             whitelist_mints: [Pubkey::default(); WHITE_MINT_SIZE_USIZE],
+            operation_owner_roles: [0; OPERATION_SIZE_USIZE],
+            padding: [0; 8],
         };
         let mut keys = Vec::new();
         for _i in 0..3 {
@@ -222,6 +379,128 @@ mod test {
         println!("{:?}", operation_state.operation_owners);
     }
 
+    #[test]
+    fn test_set_operator_roles() {
+        let mut operation_state = OperationState {
+            bump: 0,
+            operation_owners: [Pubkey::default(); OPERATION_SIZE_USIZE],
+            whitelist_mints: [Pubkey::default(); WHITE_MINT_SIZE_USIZE],
+            operation_owner_roles: [0; OPERATION_SIZE_USIZE],
+            padding: [0; 8],
+        };
+        let reward_admin = Pubkey::new_unique();
+        let pool_pauser = Pubkey::new_unique();
+        let reward_admin_role = 1 << (OperatorRoleBitIndex::RewardAdmin as u8);
+        let pool_pauser_role = 1 << (OperatorRoleBitIndex::PoolPauser as u8);
+
+        assert_eq!(
+            operation_state
+                .set_operator_roles(reward_admin, reward_admin_role)
+                .unwrap(),
+            0
+        );
+        operation_state
+            .set_operator_roles(pool_pauser, pool_pauser_role)
+            .unwrap();
+        assert!(
+            operation_state.validate_operator_role(reward_admin, OperatorRoleBitIndex::RewardAdmin)
+        );
+        assert!(
+            !operation_state.validate_operator_role(reward_admin, OperatorRoleBitIndex::PoolPauser)
+        );
+        assert!(
+            operation_state.validate_operator_role(pool_pauser, OperatorRoleBitIndex::PoolPauser)
+        );
+        assert!(!operation_state
+            .validate_operator_role(pool_pauser, OperatorRoleBitIndex::FeeCollector));
+
+        // the roles follow their owner when the owners are sorted again
+        operation_state.update_operation_owner(vec![Pubkey::new_unique(), Pubkey::new_unique()]);
+        assert_eq!(
+            operation_state.operator_roles(reward_admin),
+            reward_admin_role
+        );
+        assert_eq!(
+            operation_state.operator_roles(pool_pauser),
+            pool_pauser_role
+        );
+
+        // empty roles remove the operator
+        assert_eq!(
+            operation_state.set_operator_roles(reward_admin, 0).unwrap(),
+            reward_admin_role
+        );
+        assert!(!operation_state.validate_operation_owner(reward_admin));
+        assert_eq!(
+            operation_state.operator_roles(pool_pauser),
+            pool_pauser_role
+        );
+
+        // unknown roles
+        assert!(operation_state
+            .set_operator_roles(reward_admin, OPERATOR_ROLE_MASK + 1)
+            .is_err());
+        // default operator
+        assert!(operation_state
+            .set_operator_roles(Pubkey::default(), OPERATOR_ROLE_MASK)
+            .is_err());
+    }
+
+    #[test]
+    fn test_set_operator_roles_with_full_array() {
+        let mut operation_state = OperationState {
+            bump: 0,
+            operation_owners: [Pubkey::default(); OPERATION_SIZE_USIZE],
+            whitelist_mints: [Pubkey::default(); WHITE_MINT_SIZE_USIZE],
+            operation_owner_roles: [0; OPERATION_SIZE_USIZE],
+            padding: [0; 8],
+        };
+        let operators: Vec<Pubkey> = (0..OPERATION_SIZE_USIZE)
+            .map(|_| Pubkey::new_unique())
+            .collect();
+        for operator in operators.iter() {
+            operation_state
+                .set_operator_roles(*operator, OPERATOR_ROLE_MASK)
+                .unwrap();
+        }
+        assert!(operation_state
+            .set_operator_roles(Pubkey::new_unique(), OPERATOR_ROLE_MASK)
+            .is_err());
+        // the existing operators could still be updated
+        operation_state
+            .set_operator_roles(
+                operators[0],
+                1 << (OperatorRoleBitIndex::FeeCollector as u8),
+            )
+            .unwrap();
+        assert!(operation_state
+            .validate_operator_role(operators[0], OperatorRoleBitIndex::FeeCollector));
+        assert!(!operation_state
+            .validate_operator_role(operators[0], OperatorRoleBitIndex::RewardAdmin));
+    }
+
+    #[test]
+    fn test_grant_legacy_roles_to_operators() {
+        let mut operation_state = OperationState {
+            bump: 0,
+            operation_owners: [Pubkey::default(); OPERATION_SIZE_USIZE],
+            whitelist_mints: [Pubkey::default(); WHITE_MINT_SIZE_USIZE],
+            operation_owner_roles: [0; OPERATION_SIZE_USIZE],
+            padding: [0; 8],
+        };
+        let operator = Pubkey::new_unique();
+        operation_state.update_operation_owner(vec![operator]);
+        assert_eq!(operation_state.operator_roles(operator), 0);
+
+        operation_state.grant_legacy_roles_to_operators();
+        assert!(operation_state.validate_operator_role(operator, OperatorRoleBitIndex::RewardAdmin));
+        assert!(!operation_state.validate_operator_role(operator, OperatorRoleBitIndex::PoolPauser));
+        assert!(
+            !operation_state.validate_operator_role(operator, OperatorRoleBitIndex::FeeCollector)
+        );
+        assert_eq!(operation_state.operator_roles(Pubkey::default()), 0);
+    }
+
     #[test]
     fn operation_layout_test() {
         use anchor_lang::Discriminator;
@@ -231,10 +510,17 @@ mod test {
             std::array::from_fn(|_| Pubkey::new_unique());
         let whitelist_mints: [Pubkey; WHITE_MINT_SIZE_USIZE] =
             std::array::from_fn(|_| Pubkey::new_unique());
+        let operation_owner_roles: [u8; OPERATION_SIZE_USIZE] =
+            std::array::from_fn(|i| i as u8 & OPERATOR_ROLE_MASK);
+        let padding: [u64; 8] = [0x123456789abcdef0; 8];
 
         // serialize original data
-        let mut operation_data =
-            [0u8; 8 + 1 + 32 * OPERATION_SIZE_USIZE + 32 * WHITE_MINT_SIZE_USIZE];
+        let mut operation_data = [0u8; 8
+            + 1
+            + 32 * OPERATION_SIZE_USIZE
+            + 32 * WHITE_MINT_SIZE_USIZE
+            + OPERATION_SIZE_USIZE
+            + 8 * 8];
         let mut offset = 0;
         operation_data[offset..offset + 8].copy_from_slice(&OperationState::discriminator());
         offset += 8;
@@ -248,6 +534,13 @@ mod test {
             operation_data[offset..offset + 32].copy_from_slice(&whitelist_mints[i].to_bytes());
             offset += 32;
         }
+        operation_data[offset..offset + OPERATION_SIZE_USIZE]
+            .copy_from_slice(&operation_owner_roles);
+        offset += OPERATION_SIZE_USIZE;
+        for i in 0..8 {
+            operation_data[offset..offset + 8].copy_from_slice(&padding[i].to_le_bytes());
+            offset += 8;
+        }
 
         // len check
         assert_eq!(offset, operation_data.len());
@@ -271,5 +564,44 @@ mod test {
             let unpack_whitelist_mints = unpack_data.whitelist_mints[i];
             assert_eq!(unpack_whitelist_mints, whitelist_mints[i]);
         }
+        let unpack_operation_owner_roles = unpack_data.operation_owner_roles;
+        assert_eq!(unpack_operation_owner_roles, operation_owner_roles);
+        let unpack_padding = unpack_data.padding;
+        assert_eq!(unpack_padding, padding);
+    }
+
+    #[test]
+    fn load_legacy_operation_account_test() {
+        use anchor_lang::Discriminator;
+
+        let operator = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let mut operation_state = OperationState {
+            bump: 0,
+            operation_owners: [Pubkey::default(); OPERATION_SIZE_USIZE],
+            whitelist_mints: [Pubkey::default(); WHITE_MINT_SIZE_USIZE],
+            operation_owner_roles: [0; OPERATION_SIZE_USIZE],
+            padding: [0; 8],
+        };
+        operation_state
+            .set_operator_roles(operator, 1 << OperatorRoleBitIndex::PoolPauser as u8)
+            .unwrap();
+        operation_state.update_whitelist_mint(vec![mint]);
+        let mut data = OperationState::discriminator().to_vec();
+        data.extend_from_slice(bytemuck::bytes_of(&operation_state));
+        assert_eq!(data.len(), OperationState::LEN);
+
+        // the roles of a resized account are read from the account
+        let loaded = OperationState::from_account_data(&data);
+        assert!(loaded.validate_operator_role(operator, OperatorRoleBitIndex::PoolPauser));
+        assert!(!loaded.validate_operator_role(operator, OperatorRoleBitIndex::RewardAdmin));
+
+        // the operators of a legacy account are reward admins only
+        let loaded = OperationState::from_account_data(&data[..OperationState::LEGACY_LEN]);
+        assert!(loaded.validate_operator_role(operator, OperatorRoleBitIndex::RewardAdmin));
+        assert!(!loaded.validate_operator_role(operator, OperatorRoleBitIndex::PoolPauser));
+        assert!(!loaded.validate_operator_role(operator, OperatorRoleBitIndex::FeeCollector));
+        assert!(loaded.validate_whitelist_mint(mint));
+        assert_eq!(loaded.operator_roles(Pubkey::new_unique()), 0);
     }
 }
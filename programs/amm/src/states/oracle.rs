@@ -9,14 +9,22 @@ Detect the discriminator mismatch
 Reject it with a runtime error instead of reading the wrong data
 */
 use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use std::cell::{Ref, RefMut};
 
+use crate::error::ErrorCode;
+use crate::libraries::{big_num::U128, full_math::MulDiv};
 use crate::util::get_recent_epoch;
 
 /// Seed to derive account address and signature
 pub const OBSERVATION_SEED: &str = "observation";
 // Number of ObservationState element
 pub const OBSERVATION_NUM: usize = 100;
+/// Max number of observations the account can be grown to
+pub const OBSERVATION_CARDINALITY_MAX: u16 = u16::MAX;
 pub const OBSERVATION_UPDATE_DURATION_DEFAULT: u32 = 15;
+/// Max seconds between two observations which can be configured for a pool
+pub const OBSERVATION_UPDATE_DURATION_MAX: u32 = 3600;
 
 /// The element of observations in ObservationState
 #[zero_copy(unsafe)]
@@ -27,12 +35,65 @@ pub struct Observation {
     pub block_timestamp: u32,
     /// the cumulative of tick during the duration time
     pub tick_cumulative: i64,
+    /// the cumulative of seconds elapsed divided by max(1, liquidity), as Q64.64
+    pub seconds_per_liquidity_cumulative_x64: u128,
     /// padding(reserved space) for feature update
-    pub padding: [u64; 4],
+    pub padding: [u64; 2],
 }
 
+// `zero_copy(unsafe)` only derives the bytemuck traits for accounts, they are needed to read the
+// observations stored beyond the fixed array as a slice. The struct is packed, so it has no padding bytes
+unsafe impl bytemuck::Zeroable for Observation {}
+unsafe impl bytemuck::Pod for Observation {}
+
 impl Observation {
-    pub const LEN: usize = 4 + 8 + 8 * 4;
+    pub const LEN: usize = 4 + 8 + 16 + 8 * 2;
+
+    /// Returns the observation which would be written at `block_timestamp`, given the tick in
+    /// effect since this observation and the seconds per liquidity accumulated by the pool
+    pub fn transform(
+        &self,
+        block_timestamp: u32,
+        tick: i32,
+        seconds_per_liquidity_cumulative_x64: u128,
+    ) -> Observation {
+        let delta_time = block_timestamp.saturating_sub(self.block_timestamp);
+        Observation {
+            block_timestamp,
+            tick_cumulative: self
+                .tick_cumulative
+                .wrapping_add(i64::from(tick) * i64::from(delta_time)),
+            seconds_per_liquidity_cumulative_x64,
+            padding: [0u64; 2],
+        }
+    }
+
+    /// Returns the observation at `target`, interpolated between `before` and `after`
+    pub fn interpolate(before: &Observation, after: &Observation, target: u32) -> Observation {
+        let observation_time_delta = after.block_timestamp - before.block_timestamp;
+        let target_delta = target - before.block_timestamp;
+        let tick_cumulative_delta =
+            i128::from(after.tick_cumulative.wrapping_sub(before.tick_cumulative));
+        let seconds_per_liquidity_delta = after
+            .seconds_per_liquidity_cumulative_x64
+            .wrapping_sub(before.seconds_per_liquidity_cumulative_x64);
+        Observation {
+            block_timestamp: target,
+            tick_cumulative: before.tick_cumulative.wrapping_add(
+                (tick_cumulative_delta * i128::from(target_delta)
+                    / i128::from(observation_time_delta)) as i64,
+            ),
+            seconds_per_liquidity_cumulative_x64: before
+                .seconds_per_liquidity_cumulative_x64
+                .wrapping_add(
+                    U128::from(seconds_per_liquidity_delta)
+                        .mul_div_floor(U128::from(target_delta), U128::from(observation_time_delta))
+                        .unwrap()
+                        .as_u128(),
+                ),
+            padding: [0u64; 2],
+        }
+    }
 }
 //It can hold upto observation's upto 100 elements and after that it will start to overwrite from the 0th index.
 #[account(zero_copy(unsafe))]
@@ -49,8 +110,14 @@ pub struct ObservationState {
     pub pool_id: Pubkey,
     /// observation array
     pub observations: [Observation; OBSERVATION_NUM],
+    /// the number of observations in use, the ones beyond OBSERVATION_NUM are stored after this struct
+    pub observation_cardinality: u16,
+    /// the number of observations the account has room for, takes effect once the ring buffer wraps
+    pub observation_cardinality_next: u16,
+    /// the min seconds between two observations, OBSERVATION_UPDATE_DURATION_DEFAULT if zero
+    pub observation_update_duration: u32,
     /// padding for feature update
-    pub padding: [u64; 4],
+    pub padding: [u64; 3],
 }
 
 impl Default for ObservationState {
@@ -62,14 +129,89 @@ impl Default for ObservationState {
             observation_index: 0,
             pool_id: Pubkey::default(),
             observations: [Observation::default(); OBSERVATION_NUM],
-            padding: [0u64; 4],
+            observation_cardinality: OBSERVATION_NUM as u16,
+            observation_cardinality_next: OBSERVATION_NUM as u16,
+            observation_update_duration: OBSERVATION_UPDATE_DURATION_DEFAULT,
+            padding: [0u64; 3],
         }
     }
 }
 
 impl ObservationState {
     //it is an  account so we add the Anchor_Discriminator_size
-    pub const LEN: usize = 8 + 1 + 8 + 2 + 32 + (Observation::LEN * OBSERVATION_NUM) + 8 * 4;
+    pub const LEN: usize =
+        8 + 1 + 8 + 2 + 32 + (Observation::LEN * OBSERVATION_NUM) + 2 + 2 + 4 + 8 * 3;
+
+    /// The account size required to hold `cardinality` observations
+    pub fn space(cardinality: u16) -> usize {
+        ObservationState::LEN
+            + Observation::LEN * (cardinality as usize).saturating_sub(OBSERVATION_NUM)
+    }
+
+    /// Loads the observation state together with the observations stored beyond the fixed array
+    pub fn load_with_extension<'a, 'info>(
+        loader: &'a AccountLoader<'info, ObservationState>,
+    ) -> Result<(Ref<'a, ObservationState>, Ref<'a, [Observation]>)> {
+        // check the discriminator
+        loader.load()?;
+        let data = loader.as_ref().try_borrow_data()?;
+        require_eq!(
+            (data.len() - ObservationState::LEN) % Observation::LEN,
+            0,
+            ErrorCode::InvalidObservationCardinality
+        );
+        Ok(Ref::map_split(data, |data| {
+            let (state, extension) = data.split_at(ObservationState::LEN);
+            (
+                bytemuck::from_bytes(&state[8..]),
+                bytemuck::cast_slice(extension),
+            )
+        }))
+    }
+
+    /// Unpacks the raw data of an observation account, for off-chain use
+    pub fn from_account_data(data: &[u8]) -> Result<(ObservationState, Vec<Observation>)> {
+        require_gte!(
+            data.len(),
+            ObservationState::LEN,
+            ErrorCode::InvalidObservationCardinality
+        );
+        require!(
+            data[..8] == ObservationState::discriminator(),
+            anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch
+        );
+        let (state, extension) = data.split_at(ObservationState::LEN);
+        require_eq!(
+            extension.len() % Observation::LEN,
+            0,
+            ErrorCode::InvalidObservationCardinality
+        );
+        Ok((
+            *bytemuck::from_bytes::<ObservationState>(&state[8..]),
+            bytemuck::cast_slice::<u8, Observation>(extension).to_vec(),
+        ))
+    }
+
+    /// Mutable version of `load_with_extension`
+    pub fn load_mut_with_extension<'a, 'info>(
+        loader: &'a AccountLoader<'info, ObservationState>,
+    ) -> Result<(RefMut<'a, ObservationState>, RefMut<'a, [Observation]>)> {
+        // check the discriminator and that the account is writable
+        loader.load_mut()?;
+        let data = loader.as_ref().try_borrow_mut_data()?;
+        require_eq!(
+            (data.len() - ObservationState::LEN) % Observation::LEN,
+            0,
+            ErrorCode::InvalidObservationCardinality
+        );
+        Ok(RefMut::map_split(data, |data| {
+            let (state, extension) = data.split_at_mut(ObservationState::LEN);
+            (
+                bytemuck::from_bytes_mut(&mut state[8..]),
+                bytemuck::cast_slice_mut(extension),
+            )
+        }))
+    }
 
     pub fn initialize(&mut self, pool_id: Pubkey) -> Result<()> {
         self.initialized = false;
@@ -77,17 +219,66 @@ impl ObservationState {
         self.observation_index = 0;
         self.pool_id = pool_id;
         self.observations = [Observation::default(); OBSERVATION_NUM];
-        self.padding = [0u64; 4];
+        self.observation_cardinality = OBSERVATION_NUM as u16;
+        self.observation_cardinality_next = OBSERVATION_NUM as u16;
+        self.observation_update_duration = OBSERVATION_UPDATE_DURATION_DEFAULT;
+        self.padding = [0u64; 3];
         Ok(())
     }
 
+    /// The number of observations in use, accounts created before the cardinality was stored use OBSERVATION_NUM
+    pub fn cardinality(&self) -> u16 {
+        if self.observation_cardinality == 0 {
+            OBSERVATION_NUM as u16
+        } else {
+            self.observation_cardinality
+        }
+    }
+
+    /// The number of observations the account has room for
+    pub fn cardinality_next(&self) -> u16 {
+        self.observation_cardinality_next.max(self.cardinality())
+    }
+
+    /// The min seconds between two observations, accounts created before the duration was stored use the default
+    pub fn update_duration(&self) -> u32 {
+        if self.observation_update_duration == 0 {
+            OBSERVATION_UPDATE_DURATION_DEFAULT
+        } else {
+            self.observation_update_duration
+        }
+    }
+
+    /// Returns the observation at `index` of the ring buffer
+    pub fn get_observation(&self, extension: &[Observation], index: usize) -> Observation {
+        if index < OBSERVATION_NUM {
+            self.observations[index]
+        } else {
+            extension[index - OBSERVATION_NUM]
+        }
+    }
+
+    fn get_observation_mut<'a>(
+        &'a mut self,
+        extension: &'a mut [Observation],
+        index: usize,
+    ) -> &'a mut Observation {
+        if index < OBSERVATION_NUM {
+            &mut self.observations[index]
+        } else {
+            &mut extension[index - OBSERVATION_NUM]
+        }
+    }
+
     /// Writes an oracle observation to the account
     ///
     /// # Arguments
     ///
     /// * `self` - The ObservationState account to write in
+    /// * `extension` - The observations stored beyond the fixed array
     /// * `block_timestamp` - The current timestamp of to update
     /// * `tick` - the tick for which we are writing observation
+    /// * `seconds_per_liquidity_cumulative_x64` - the seconds per liquidity accumulated by the pool up to `block_timestamp`
     ///
     /*
      timestamp: timestamp,
@@ -97,38 +288,236 @@ impl ObservationState {
             initialized: true
         });
     */
-    pub fn update(&mut self, block_timestamp: u32, tick: i32) {
-        let observation_index = self.observation_index;
+    pub fn update(
+        &mut self,
+        extension: &mut [Observation],
+        block_timestamp: u32,
+        tick: i32,
+        seconds_per_liquidity_cumulative_x64: u128,
+    ) {
+        let observation_index = self.observation_index as usize;
         if !self.initialized {
             self.initialized = true;
-            self.observations[observation_index as usize].block_timestamp = block_timestamp;
-            self.observations[observation_index as usize].tick_cumulative = 0;
+            self.observations[observation_index].block_timestamp = block_timestamp;
+            self.observations[observation_index].tick_cumulative = 0;
+            self.observations[observation_index].seconds_per_liquidity_cumulative_x64 =
+                seconds_per_liquidity_cumulative_x64;
         } else {
-            let last_observation = self.observations[observation_index as usize];
+            let last_observation = self.get_observation(extension, observation_index);
             //the gap between the present an last_observation's timestamp.
             let delta_time = block_timestamp.saturating_sub(last_observation.block_timestamp);
-            if delta_time < OBSERVATION_UPDATE_DURATION_DEFAULT {
+            if delta_time < self.update_duration() {
                 return;
             }
-            let delta_tick_cumulative = i64::from(tick).checked_mul(delta_time.into()).unwrap();
-            //here we derive the next observation index...
-            //if the current obs index is equal to 14(0..14) i.e. 15 elements, then we would overide the oldest obs, else return the next index
-            let next_observation_index = if observation_index as usize == OBSERVATION_NUM - 1 {
-                0
-            } else {
-                observation_index + 1
-            };
-            
-            self.observations[next_observation_index as usize].block_timestamp = block_timestamp;
+            // the grown cardinality only takes effect at the end of the ring buffer to keep the observations in order
+            let mut cardinality = self.cardinality() as usize;
+            let cardinality_next = self.cardinality_next() as usize;
+            if cardinality_next > cardinality
+                && observation_index == cardinality - 1
+                && cardinality_next <= OBSERVATION_NUM + extension.len()
+            {
+                self.observation_cardinality = cardinality_next as u16;
+                cardinality = cardinality_next;
+            }
+            //here we derive the next observation index, once the last slot is written we override the oldest one
+            let next_observation_index = (observation_index + 1) % cardinality;
+
             /*
             What we’re calculating here is the accumulated price: the current tick gets multiplied by the number of seconds since the last observation and gets added to the last accumulated price.
+            The seconds per liquidity is accumulated by the pool on every liquidity change, the observation records it.
             */
-            self.observations[next_observation_index as usize].tick_cumulative = last_observation
-                .tick_cumulative
-                .wrapping_add(delta_tick_cumulative);
-            self.observation_index = next_observation_index;
+            *self.get_observation_mut(extension, next_observation_index) = last_observation
+                .transform(block_timestamp, tick, seconds_per_liquidity_cumulative_x64);
+            self.observation_index = next_observation_index as u16;
         }
     }
+
+    /// Returns the tick cumulative and the seconds per liquidity cumulative as of each `seconds_ago` before `block_timestamp`
+    ///
+    /// # Arguments
+    ///
+    /// * `self` - The ObservationState account to read from
+    /// * `extension` - The observations stored beyond the fixed array
+    /// * `block_timestamp` - The current timestamp
+    /// * `seconds_agos` - How far back from `block_timestamp` each cumulative should be read
+    /// * `tick_current` - The current tick of the pool, used to extrapolate past the newest observation
+    /// * `seconds_per_liquidity_cumulative_x64` - The seconds per liquidity accumulated by the pool up to `block_timestamp`
+    ///
+    pub fn observe(
+        &self,
+        extension: &[Observation],
+        block_timestamp: u32,
+        seconds_agos: &[u32],
+        tick_current: i32,
+        seconds_per_liquidity_cumulative_x64: u128,
+    ) -> Result<(Vec<i64>, Vec<u128>)> {
+        require!(self.initialized, ErrorCode::ObservationNotInitialized);
+        let mut tick_cumulatives = Vec::with_capacity(seconds_agos.len());
+        let mut seconds_per_liquidity_cumulative_x64s = Vec::with_capacity(seconds_agos.len());
+        for seconds_ago in seconds_agos {
+            let observation = self.observe_single(
+                extension,
+                block_timestamp,
+                *seconds_ago,
+                tick_current,
+                seconds_per_liquidity_cumulative_x64,
+            )?;
+            tick_cumulatives.push(observation.tick_cumulative);
+            seconds_per_liquidity_cumulative_x64s
+                .push(observation.seconds_per_liquidity_cumulative_x64);
+        }
+        Ok((tick_cumulatives, seconds_per_liquidity_cumulative_x64s))
+    }
+
+    /// Returns the observation at `seconds_ago` before `block_timestamp`,
+    /// interpolating between the two surrounding observations when needed
+    pub fn observe_single(
+        &self,
+        extension: &[Observation],
+        block_timestamp: u32,
+        seconds_ago: u32,
+        tick_current: i32,
+        seconds_per_liquidity_cumulative_x64: u128,
+    ) -> Result<Observation> {
+        let target = block_timestamp
+            .checked_sub(seconds_ago)
+            .ok_or(ErrorCode::InvalidObserveParams)?;
+
+        let last_observation = self.get_observation(extension, self.observation_index as usize);
+        if target >= last_observation.block_timestamp {
+            // the newest observation is not new enough, extrapolate with the current tick up to the accumulator of the pool
+            let current_observation = last_observation.transform(
+                block_timestamp,
+                tick_current,
+                seconds_per_liquidity_cumulative_x64,
+            );
+            if target == block_timestamp {
+                return Ok(current_observation);
+            }
+            return Ok(Observation::interpolate(
+                &last_observation,
+                &current_observation,
+                target,
+            ));
+        }
+
+        let (before, after) = self.get_surrounding_observations(extension, target)?;
+        if target == before.block_timestamp {
+            return Ok(before);
+        }
+        if target == after.block_timestamp {
+            return Ok(after);
+        }
+        // interpolate between the two neighbours
+        Ok(Observation::interpolate(&before, &after, target))
+    }
+
+    /// The number of observations which have been written to the ring buffer
+    pub fn initialized_observation_count(&self, extension: &[Observation]) -> usize {
+        if !self.initialized {
+            return 0;
+        }
+        let cardinality = self.cardinality() as usize;
+        let next_observation_index = (self.observation_index as usize + 1) % cardinality;
+        if self
+            .get_observation(extension, next_observation_index)
+            .block_timestamp
+            != 0
+        {
+            cardinality
+        } else {
+            self.observation_index as usize + 1
+        }
+    }
+
+    /// The index of the oldest observation in the ring buffer
+    pub fn oldest_observation_index(&self, extension: &[Observation]) -> usize {
+        let cardinality = self.cardinality() as usize;
+        if self.initialized_observation_count(extension) == cardinality {
+            (self.observation_index as usize + 1) % cardinality
+        } else {
+            0
+        }
+    }
+
+    /// Returns the observations written at or after `since`, from the oldest to the newest
+    pub fn observations_since(&self, extension: &[Observation], since: u32) -> Vec<Observation> {
+        let cardinality = self.cardinality() as usize;
+        let oldest_index = self.oldest_observation_index(extension);
+        (0..self.initialized_observation_count(extension))
+            .map(|i| self.get_observation(extension, (oldest_index + i) % cardinality))
+            .filter(|observation| observation.block_timestamp >= since)
+            .collect()
+    }
+
+    /// Binary searches the ring buffer for the observations at or before and at or after `target`.
+    /// `target` must be older than the most recent observation.
+    fn get_surrounding_observations(
+        &self,
+        extension: &[Observation],
+        target: u32,
+    ) -> Result<(Observation, Observation)> {
+        let cardinality = self.cardinality() as usize;
+        let count = self.initialized_observation_count(extension);
+        let oldest_index = self.oldest_observation_index(extension);
+        let oldest_timestamp = self
+            .get_observation(extension, oldest_index)
+            .block_timestamp;
+        require_gte!(target, oldest_timestamp, ErrorCode::ObservationTooOld);
+
+        // search over the logical positions, oldest is 0 and newest is count - 1
+        let mut left = 0usize;
+        let mut right = count - 1;
+        while right - left > 1 {
+            let mid = (left + right) / 2;
+            let mid_observation =
+                self.get_observation(extension, (oldest_index + mid) % cardinality);
+            if mid_observation.block_timestamp <= target {
+                left = mid;
+            } else {
+                right = mid;
+            }
+        }
+        Ok((
+            self.get_observation(extension, (oldest_index + left) % cardinality),
+            self.get_observation(extension, (oldest_index + right) % cardinality),
+        ))
+    }
+}
+
+/// Emitted when the observation account of a pool is grown
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct IncreaseObservationCardinalityNextEvent {
+    /// The pool whose oracle is grown
+    #[index]
+    pub pool_state: Pubkey,
+    /// The previous number of observations the account had room for
+    pub observation_cardinality_next_old: u16,
+    /// The number of observations the account has room for now
+    pub observation_cardinality_next: u16,
+}
+
+/// Emitted when the observation update duration of a pool is changed
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct ObservationUpdateDurationChangeEvent {
+    /// The pool whose oracle is changed
+    #[index]
+    pub pool_state: Pubkey,
+    /// The previous min seconds between two observations
+    pub observation_update_duration_old: u32,
+    /// The new min seconds between two observations
+    pub observation_update_duration: u32,
+}
+
+/// Returns the mean tick of `tick_cumulative_delta` over `delta_time` seconds, always rounded to negative infinity
+pub fn get_mean_tick(tick_cumulative_delta: i64, delta_time: i64) -> i64 {
+    let mut mean_tick = tick_cumulative_delta / delta_time;
+    if tick_cumulative_delta < 0 && tick_cumulative_delta % delta_time != 0 {
+        mean_tick -= 1;
+    }
+    mean_tick
 }
 
 /// Returns the block timestamp truncated to 32 bits, i.e. mod 2**32
@@ -147,6 +536,210 @@ pub fn block_timestamp_mock() -> u64 {
         .as_secs()
 }
 
+#[cfg(test)]
+pub mod observe_test {
+    use super::*;
+
+    fn build_observation_state(
+        start: u32,
+        tick: i32,
+        count: usize,
+        cardinality_next: u16,
+    ) -> (ObservationState, Vec<Observation>) {
+        let mut observation_state = ObservationState::default();
+        let mut extension = vec![
+            Observation::default();
+            (cardinality_next as usize).saturating_sub(OBSERVATION_NUM)
+        ];
+        observation_state.observation_cardinality_next = cardinality_next;
+        for i in 0..count {
+            observation_state.update(
+                &mut extension,
+                start + i as u32 * OBSERVATION_UPDATE_DURATION_DEFAULT,
+                tick,
+                0,
+            );
+        }
+        (observation_state, extension)
+    }
+
+    #[test]
+    fn observe_not_initialized_test() {
+        let observation_state = ObservationState::default();
+        assert!(observation_state.observe(&[], 100, &[0], 10, 0).is_err());
+    }
+
+    #[test]
+    fn observe_exact_and_interpolate_test() {
+        let (observation_state, extension) =
+            build_observation_state(1000, 10, 5, OBSERVATION_NUM as u16);
+        // observations at 1000, 1015, 1030, 1045, 1060
+        let (tick_cumulatives, _) = observation_state
+            .observe(&extension, 1060, &[60, 45, 30, 23, 0], 10, 0)
+            .unwrap();
+        assert_eq!(tick_cumulatives, vec![0, 150, 300, 370, 600]);
+    }
+
+    #[test]
+    fn observe_extrapolate_test() {
+        let (observation_state, extension) =
+            build_observation_state(1000, 10, 2, OBSERVATION_NUM as u16);
+        let (tick_cumulatives, _) = observation_state
+            .observe(&extension, 1025, &[0, 5], -20, 0)
+            .unwrap();
+        assert_eq!(tick_cumulatives, vec![150 - 200, 150 - 100]);
+    }
+
+    #[test]
+    fn observe_too_old_test() {
+        let (observation_state, extension) =
+            build_observation_state(1000, 10, 3, OBSERVATION_NUM as u16);
+        assert!(observation_state
+            .observe(&extension, 1030, &[31], 10, 0)
+            .is_err());
+        assert!(observation_state
+            .observe(&extension, 1030, &[2000], 10, 0)
+            .is_err());
+        assert!(observation_state
+            .observe(&extension, 1030, &[30], 10, 0)
+            .is_ok());
+    }
+
+    #[test]
+    fn observe_after_wrap_test() {
+        let (observation_state, extension) =
+            build_observation_state(1000, 1, OBSERVATION_NUM + 10, OBSERVATION_NUM as u16);
+        assert_eq!(
+            observation_state.initialized_observation_count(&extension),
+            OBSERVATION_NUM
+        );
+        assert_eq!(observation_state.oldest_observation_index(&extension), 10);
+        let newest_timestamp =
+            1000 + (OBSERVATION_NUM as u32 + 9) * OBSERVATION_UPDATE_DURATION_DEFAULT;
+        let oldest_seconds_ago = (OBSERVATION_NUM as u32 - 1) * OBSERVATION_UPDATE_DURATION_DEFAULT;
+        let (tick_cumulatives, _) = observation_state
+            .observe(
+                &extension,
+                newest_timestamp,
+                &[oldest_seconds_ago, 7, 0],
+                1,
+                0,
+            )
+            .unwrap();
+        let newest_tick_cumulative = i64::from(newest_timestamp - 1000);
+        assert_eq!(
+            tick_cumulatives,
+            vec![
+                newest_tick_cumulative - i64::from(oldest_seconds_ago),
+                newest_tick_cumulative - 7,
+                newest_tick_cumulative
+            ]
+        );
+        assert!(observation_state
+            .observe(
+                &extension,
+                newest_timestamp,
+                &[oldest_seconds_ago + 1],
+                1,
+                0
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn observe_grown_cardinality_test() {
+        let cardinality_next = OBSERVATION_NUM as u16 + 50;
+        let (mut observation_state, mut extension) =
+            build_observation_state(1000, 1, OBSERVATION_NUM - 1, cardinality_next);
+        // the grown cardinality is not in use until the last slot is written
+        assert_eq!(observation_state.cardinality(), OBSERVATION_NUM as u16);
+
+        let mut block_timestamp =
+            1000 + (OBSERVATION_NUM as u32 - 2) * OBSERVATION_UPDATE_DURATION_DEFAULT;
+        for _ in 0..60 {
+            block_timestamp += OBSERVATION_UPDATE_DURATION_DEFAULT;
+            observation_state.update(&mut extension, block_timestamp, 1, 0);
+        }
+        assert_eq!(observation_state.cardinality(), cardinality_next);
+        let observation_index = observation_state.observation_index;
+        assert_eq!(observation_index, 8);
+        assert_eq!(
+            observation_state.initialized_observation_count(&extension),
+            cardinality_next as usize
+        );
+        assert_eq!(observation_state.oldest_observation_index(&extension), 9);
+
+        let oldest_seconds_ago =
+            (cardinality_next as u32 - 1) * OBSERVATION_UPDATE_DURATION_DEFAULT;
+        let (tick_cumulatives, _) = observation_state
+            .observe(
+                &extension,
+                block_timestamp,
+                &[oldest_seconds_ago, 1000, 0],
+                1,
+                0,
+            )
+            .unwrap();
+        let newest_tick_cumulative = i64::from(block_timestamp - 1000);
+        assert_eq!(
+            tick_cumulatives,
+            vec![
+                newest_tick_cumulative - i64::from(oldest_seconds_ago),
+                newest_tick_cumulative - 1000,
+                newest_tick_cumulative
+            ]
+        );
+        assert!(observation_state
+            .observe(&extension, block_timestamp, &[oldest_seconds_ago + 1], 1, 0)
+            .is_err());
+    }
+
+    #[test]
+    fn update_duration_test() {
+        let mut observation_state = ObservationState::default();
+        observation_state.observation_update_duration = 60;
+        observation_state.update(&mut [], 1000, 10, 0);
+        observation_state.update(&mut [], 1059, 10, 0);
+        let observation_index = observation_state.observation_index;
+        assert_eq!(observation_index, 0);
+        observation_state.update(&mut [], 1060, 10, 0);
+        let observation_index = observation_state.observation_index;
+        assert_eq!(observation_index, 1);
+
+        // accounts created before the duration was stored
+        observation_state.observation_update_duration = 0;
+        assert_eq!(
+            observation_state.update_duration(),
+            OBSERVATION_UPDATE_DURATION_DEFAULT
+        );
+    }
+
+    #[test]
+    fn observe_seconds_per_liquidity_test() {
+        let per_second = 1u128 << 32;
+        let mut observation_state = ObservationState::default();
+        // the observations record the seconds per liquidity accumulated by the pool
+        observation_state.update(&mut [], 1000, 10, 0);
+        observation_state.update(&mut [], 1020, 10, 20 * per_second);
+        observation_state.update(&mut [], 1040, 10, 60 * per_second);
+
+        // the liquidity of the pool changed since the newest observation
+        let (_, seconds_per_liquidity_cumulative_x64s) = observation_state
+            .observe(&[], 1050, &[50, 30, 25, 5, 0], 10, 90 * per_second)
+            .unwrap();
+        assert_eq!(
+            seconds_per_liquidity_cumulative_x64s,
+            vec![
+                0,
+                20 * per_second,
+                30 * per_second,
+                75 * per_second,
+                90 * per_second
+            ]
+        );
+    }
+}
+
 #[cfg(test)]
 pub mod oracle_layout_test {
     use super::*;
@@ -157,12 +750,10 @@ pub mod oracle_layout_test {
         let recent_epoch: u64 = 0x123456789abcdef0;
         let observation_index: u16 = 0x1122;
         let pool_id: Pubkey = Pubkey::new_unique();
-        let padding: [u64; 4] = [
-            0x123456789abcde0f,
-            0x123456789abcd0ef,
-            0x123456789abc0def,
-            0x123456789ab0cdef,
-        ];
+        let observation_cardinality: u16 = 0x3344;
+        let observation_cardinality_next: u16 = 0x5566;
+        let observation_update_duration: u32 = 0x778899aa;
+        let padding: [u64; 3] = [0x123456789abcde0f, 0x123456789abcd0ef, 0x123456789abc0def];
 
         let mut observation_datas = [0u8; Observation::LEN * OBSERVATION_NUM];
         let mut observations = [Observation::default(); OBSERVATION_NUM];
@@ -171,27 +762,24 @@ pub mod oracle_layout_test {
             let index = i + 1;
             let block_timestamp: u32 = u32::MAX - 3 * index as u32;
             let tick_cumulative: i64 = i64::MAX - 3 * index as i64;
-            let padding: [u64; 4] = [
-                u64::MAX - index as u64,
-                u64::MAX - 2 * index as u64,
-                u64::MAX - 3 * index as u64,
-                u64::MAX - 4 * index as u64,
-            ];
+            let seconds_per_liquidity_cumulative_x64: u128 = u128::MAX - 5 * index as u128;
+            let padding: [u64; 2] = [u64::MAX - index as u64, u64::MAX - 2 * index as u64];
             observations[i].block_timestamp = block_timestamp;
             observations[i].tick_cumulative = tick_cumulative;
+            observations[i].seconds_per_liquidity_cumulative_x64 =
+                seconds_per_liquidity_cumulative_x64;
             observations[i].padding = padding;
             observation_datas[offset..offset + 4].copy_from_slice(&block_timestamp.to_le_bytes());
             offset += 4;
             observation_datas[offset..offset + 8].copy_from_slice(&tick_cumulative.to_le_bytes());
             offset += 8;
+            observation_datas[offset..offset + 16]
+                .copy_from_slice(&seconds_per_liquidity_cumulative_x64.to_le_bytes());
+            offset += 16;
             observation_datas[offset..offset + 8].copy_from_slice(&padding[0].to_le_bytes());
             offset += 8;
             observation_datas[offset..offset + 8].copy_from_slice(&padding[1].to_le_bytes());
             offset += 8;
-            observation_datas[offset..offset + 8].copy_from_slice(&padding[2].to_le_bytes());
-            offset += 8;
-            observation_datas[offset..offset + 8].copy_from_slice(&padding[3].to_le_bytes());
-            offset += 8;
         }
 
         // serialize original data
@@ -213,14 +801,21 @@ pub mod oracle_layout_test {
         observation_state_data[offset..offset + Observation::LEN * OBSERVATION_NUM]
             .copy_from_slice(&observation_datas);
         offset += Observation::LEN * OBSERVATION_NUM;
+        observation_state_data[offset..offset + 2]
+            .copy_from_slice(&observation_cardinality.to_le_bytes());
+        offset += 2;
+        observation_state_data[offset..offset + 2]
+            .copy_from_slice(&observation_cardinality_next.to_le_bytes());
+        offset += 2;
+        observation_state_data[offset..offset + 4]
+            .copy_from_slice(&observation_update_duration.to_le_bytes());
+        offset += 4;
         observation_state_data[offset..offset + 8].copy_from_slice(&padding[0].to_le_bytes());
         offset += 8;
         observation_state_data[offset..offset + 8].copy_from_slice(&padding[1].to_le_bytes());
         offset += 8;
         observation_state_data[offset..offset + 8].copy_from_slice(&padding[2].to_le_bytes());
         offset += 8;
-        observation_state_data[offset..offset + 8].copy_from_slice(&padding[3].to_le_bytes());
-        offset += 8;
         // len check
         assert_eq!(offset, observation_state_data.len());
         assert_eq!(
@@ -242,6 +837,18 @@ pub mod oracle_layout_test {
         assert_eq!(unpack_observation_index, observation_index);
         let unpack_pool_id = unpack_data.pool_id;
         assert_eq!(unpack_pool_id, pool_id);
+        let unpack_observation_cardinality = unpack_data.observation_cardinality;
+        assert_eq!(unpack_observation_cardinality, observation_cardinality);
+        let unpack_observation_cardinality_next = unpack_data.observation_cardinality_next;
+        assert_eq!(
+            unpack_observation_cardinality_next,
+            observation_cardinality_next
+        );
+        let unpack_observation_update_duration = unpack_data.observation_update_duration;
+        assert_eq!(
+            unpack_observation_update_duration,
+            observation_update_duration
+        );
         let unpack_padding = unpack_data.padding;
         assert_eq!(unpack_padding, padding);
         for (observation, unpack_observation) in
@@ -249,13 +856,21 @@ pub mod oracle_layout_test {
         {
             let block_timestamp = observation.block_timestamp;
             let tick_cumulative = observation.tick_cumulative;
+            let seconds_per_liquidity_cumulative_x64 =
+                observation.seconds_per_liquidity_cumulative_x64;
             let padding = observation.padding;
 
             let unpack_block_timestamp = unpack_observation.block_timestamp;
             let unpack_tick_cumulative = unpack_observation.tick_cumulative;
+            let unpack_seconds_per_liquidity_cumulative_x64 =
+                unpack_observation.seconds_per_liquidity_cumulative_x64;
             let unpack_padding = unpack_observation.padding;
             assert_eq!(block_timestamp, unpack_block_timestamp);
             assert_eq!(tick_cumulative, unpack_tick_cumulative);
+            assert_eq!(
+                seconds_per_liquidity_cumulative_x64,
+                unpack_seconds_per_liquidity_cumulative_x64
+            );
             assert_eq!(padding, unpack_padding);
         }
     }
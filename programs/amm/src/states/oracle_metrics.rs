@@ -0,0 +1,280 @@
+/// Metrics derived from the oracle observations of a pool, usable on-chain and in the client
+use anchor_lang::prelude::*;
+
+use super::oracle::{get_mean_tick, Observation, ObservationState};
+use crate::error::ErrorCode;
+use crate::libraries::{big_num::U128, fixed_point_64, full_math::MulDiv};
+
+/// The realized volatility and the harmonic mean liquidity of a pool over a window
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct OracleMetrics {
+    /// The seconds the metrics are computed over
+    pub window: u32,
+    /// The number of observations found in the window
+    pub observation_count: u32,
+    /// The standard deviation of the tick deltas between observations, as Q64.64,
+    /// `None` when the window has less than three observations
+    pub volatility_x64: Option<u128>,
+    /// The harmonic mean of the in range liquidity over the window
+    pub harmonic_mean_liquidity: u128,
+}
+
+/// Calculates the realized volatility and the harmonic mean liquidity over the last `window` seconds.
+/// The volatility is left out when the window has less than three observations
+///
+/// # Arguments
+///
+/// * `observation_state` - The observation account of the pool
+/// * `extension` - The observations stored beyond the fixed array
+/// * `block_timestamp` - The current timestamp
+/// * `window` - How many seconds back from `block_timestamp` the metrics are computed over
+/// * `tick_current` - The current tick of the pool
+/// * `seconds_per_liquidity_cumulative_x64` - The seconds per liquidity accumulator of the pool at `block_timestamp`
+///
+pub fn get_oracle_metrics(
+    observation_state: &ObservationState,
+    extension: &[Observation],
+    block_timestamp: u32,
+    window: u32,
+    tick_current: i32,
+    seconds_per_liquidity_cumulative_x64: u128,
+) -> Result<OracleMetrics> {
+    let observations =
+        observation_state.observations_since(extension, block_timestamp.saturating_sub(window));
+    let volatility_x64 = if observations.len() < 3 {
+        None
+    } else {
+        Some(get_volatility_x64(&observations)?)
+    };
+    Ok(OracleMetrics {
+        window,
+        observation_count: observations.len() as u32,
+        volatility_x64,
+        harmonic_mean_liquidity: get_harmonic_mean_liquidity(
+            observation_state,
+            extension,
+            block_timestamp,
+            window,
+            tick_current,
+            seconds_per_liquidity_cumulative_x64,
+        )?,
+    })
+}
+
+/// Calculates the standard deviation of the tick deltas between observations, as Q64.64.
+/// The tick of each interval is the mean tick between two consecutive observations,
+/// the deltas are taken between consecutive intervals and are not weighted by the interval length.
+///
+/// # Arguments
+///
+/// * `observations` - The observations from the oldest to the newest, at least three are required
+///
+pub fn get_volatility_x64(observations: &[Observation]) -> Result<u128> {
+    require_gte!(observations.len(), 3, ErrorCode::InvalidObserveParams);
+
+    let mut mean_ticks = Vec::with_capacity(observations.len() - 1);
+    for pair in observations.windows(2) {
+        let delta_time = i64::from(pair[1].block_timestamp - pair[0].block_timestamp);
+        require_gt!(delta_time, 0, ErrorCode::InvalidObserveParams);
+        let tick_cumulative_delta = pair[1]
+            .tick_cumulative
+            .wrapping_sub(pair[0].tick_cumulative);
+        mean_ticks.push(get_mean_tick(tick_cumulative_delta, delta_time));
+    }
+
+    let count = (mean_ticks.len() - 1) as u128;
+    let mut sum: i128 = 0;
+    let mut sum_of_squares: u128 = 0;
+    for pair in mean_ticks.windows(2) {
+        let tick_delta = i128::from(pair[1] - pair[0]);
+        sum += tick_delta;
+        sum_of_squares += tick_delta.unsigned_abs() * tick_delta.unsigned_abs();
+    }
+
+    // variance = E[delta^2] - E[delta]^2
+    let mean_of_squares_x64 = U128::from(sum_of_squares)
+        .mul_div_floor(U128::from(fixed_point_64::Q64), U128::from(count))
+        .ok_or(ErrorCode::CalculateOverflow)?;
+    let mean_x64 = U128::from(sum.unsigned_abs())
+        .mul_div_floor(U128::from(fixed_point_64::Q64), U128::from(count))
+        .ok_or(ErrorCode::CalculateOverflow)?;
+    let square_of_mean_x64 = mean_x64
+        .mul_div_floor(mean_x64, U128::from(fixed_point_64::Q64))
+        .ok_or(ErrorCode::CalculateOverflow)?;
+    let variance_x64 = mean_of_squares_x64
+        .as_u128()
+        .saturating_sub(square_of_mean_x64.as_u128());
+
+    // sqrt(variance * 2^64) * 2^32 = sqrt(variance) * 2^64
+    Ok(sqrt_u128(variance_x64) << 32)
+}
+
+/// Calculates the harmonic mean of the in range liquidity over the last `window` seconds
+///
+/// # Arguments
+///
+/// * `observation_state` - The observation account of the pool
+/// * `extension` - The observations stored beyond the fixed array
+/// * `block_timestamp` - The current timestamp
+/// * `window` - How many seconds back from `block_timestamp` the mean is computed over
+/// * `tick_current` - The current tick of the pool
+/// * `seconds_per_liquidity_cumulative_x64` - The seconds per liquidity accumulator of the pool at `block_timestamp`
+///
+pub fn get_harmonic_mean_liquidity(
+    observation_state: &ObservationState,
+    extension: &[Observation],
+    block_timestamp: u32,
+    window: u32,
+    tick_current: i32,
+    seconds_per_liquidity_cumulative_x64: u128,
+) -> Result<u128> {
+    require_gt!(window, 0, ErrorCode::InvalidObserveParams);
+    let (_, seconds_per_liquidity_cumulative_x64s) = observation_state.observe(
+        extension,
+        block_timestamp,
+        &[window, 0],
+        tick_current,
+        seconds_per_liquidity_cumulative_x64,
+    )?;
+    let seconds_per_liquidity_delta_x64 = seconds_per_liquidity_cumulative_x64s[1]
+        .wrapping_sub(seconds_per_liquidity_cumulative_x64s[0]);
+    if seconds_per_liquidity_delta_x64 == 0 {
+        return Ok(u128::MAX);
+    }
+    // window / sum(seconds / liquidity)
+    Ok(U128::from(window)
+        .mul_div_floor(
+            U128::from(fixed_point_64::Q64),
+            U128::from(seconds_per_liquidity_delta_x64),
+        )
+        .ok_or(ErrorCode::CalculateOverflow)?
+        .as_u128())
+}
+
+/// Integer square root, rounded down
+fn sqrt_u128(value: u128) -> u128 {
+    if value < 2 {
+        return value;
+    }
+    let mut x = value;
+    let mut y = x / 2 + 1;
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+    x
+}
+
+#[cfg(test)]
+mod oracle_metrics_test {
+    use super::*;
+
+    fn build_observations(mean_ticks: &[i64], delta_time: u32) -> Vec<Observation> {
+        let mut observations = vec![Observation {
+            block_timestamp: 1000,
+            ..Default::default()
+        }];
+        for tick in mean_ticks {
+            let last = *observations.last().unwrap();
+            observations.push(Observation {
+                block_timestamp: last.block_timestamp + delta_time,
+                tick_cumulative: last.tick_cumulative + tick * i64::from(delta_time),
+                ..Default::default()
+            });
+        }
+        observations
+    }
+
+    #[test]
+    fn sqrt_u128_test() {
+        assert_eq!(sqrt_u128(0), 0);
+        assert_eq!(sqrt_u128(1), 1);
+        assert_eq!(sqrt_u128(15), 3);
+        assert_eq!(sqrt_u128(16), 4);
+        assert_eq!(sqrt_u128(u128::MAX), u64::MAX as u128);
+    }
+
+    #[test]
+    fn volatility_test() {
+        // constant price has no volatility
+        let observations = build_observations(&[100, 100, 100, 100], 15);
+        assert_eq!(get_volatility_x64(&observations).unwrap(), 0);
+
+        // tick deltas are 10, -10, 10, -10, stddev is 10
+        let observations = build_observations(&[0, 10, 0, 10, 0], 15);
+        assert_eq!(
+            get_volatility_x64(&observations).unwrap(),
+            10 * fixed_point_64::Q64
+        );
+
+        // tick deltas are 2, 4, 6, 8, stddev is sqrt(5)
+        let observations = build_observations(&[0, 2, 6, 12, 20], 30);
+        let volatility_x64 = get_volatility_x64(&observations).unwrap();
+        let expected = 5f64.sqrt();
+        let actual = volatility_x64 as f64 / fixed_point_64::Q64 as f64;
+        assert!((actual - expected).abs() < 1e-6);
+
+        // the mean ticks are rounded to negative infinity, -0.5 is -1 and the tick deltas are -1, 1
+        let mut observations = build_observations(&[0, 0, 0], 2);
+        observations[2].tick_cumulative = -1;
+        observations[3].tick_cumulative = -1;
+        assert_eq!(
+            get_volatility_x64(&observations).unwrap(),
+            fixed_point_64::Q64
+        );
+
+        // not enough observations
+        let observations = build_observations(&[100], 15);
+        assert!(get_volatility_x64(&observations).is_err());
+    }
+
+    #[test]
+    fn harmonic_mean_liquidity_test() {
+        let liquidity = 1u128 << 32;
+        let seconds_per_liquidity_x64 = fixed_point_64::Q64 / liquidity;
+        let mut observation_state = ObservationState::default();
+        observation_state.update(&mut [], 1000, 0, 0);
+        observation_state.update(&mut [], 1015, 10, 15 * seconds_per_liquidity_x64);
+        observation_state.update(&mut [], 1030, 0, 30 * seconds_per_liquidity_x64);
+        // the liquidity is 2^32 for 30 seconds and 2^30 for 30 seconds
+        let seconds_per_liquidity_cumulative_x64 =
+            30 * seconds_per_liquidity_x64 + 30 * 4 * seconds_per_liquidity_x64;
+        let harmonic_mean_liquidity = get_harmonic_mean_liquidity(
+            &observation_state,
+            &[],
+            1060,
+            60,
+            0,
+            seconds_per_liquidity_cumulative_x64,
+        )
+        .unwrap();
+        assert_eq!(harmonic_mean_liquidity, (2 * liquidity) / 5);
+
+        let metrics = get_oracle_metrics(
+            &observation_state,
+            &[],
+            1060,
+            60,
+            0,
+            seconds_per_liquidity_cumulative_x64,
+        )
+        .unwrap();
+        assert_eq!(metrics.observation_count, 3);
+        assert_eq!(metrics.volatility_x64, Some(0));
+        assert_eq!(metrics.harmonic_mean_liquidity, harmonic_mean_liquidity);
+
+        // the volatility needs three observations in the window
+        let metrics = get_oracle_metrics(
+            &observation_state,
+            &[],
+            1060,
+            40,
+            0,
+            seconds_per_liquidity_cumulative_x64,
+        )
+        .unwrap();
+        assert_eq!(metrics.observation_count, 1);
+        assert_eq!(metrics.volatility_x64, None);
+        assert!(metrics.harmonic_mean_liquidity > 0);
+    }
+}
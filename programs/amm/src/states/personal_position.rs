@@ -1,9 +1,10 @@
 use crate::libraries::{big_num::U256, fixed_point_64, full_math::MulDiv};
 use crate::pool::REWARD_NUM;
+use crate::pool_reward_extension::EXTENSION_REWARD_NUM;
 use crate::util::get_recent_epoch;
 use anchor_lang::prelude::*;
 
-use super::POSITION_SEED;
+use super::{boost_liquidity, POSITION_SEED};
 
 #[account]
 #[derive(Default, Debug)]
@@ -42,8 +43,15 @@ pub struct PersonalPositionState {
     pub reward_infos: [PositionRewardInfo; REWARD_NUM],
     // account update recent epoch
     pub recent_epoch: u64,
+    // Position reward info of the rewards of the pool reward extension
+    pub extension_reward_infos: [PositionRewardInfo; EXTENSION_REWARD_NUM],
+    // The generations of the reward slots when the reward infos were last updated
+    pub reward_generations: [u8; REWARD_NUM],
+    pub extension_reward_generations: [u8; EXTENSION_REWARD_NUM],
+    // The reward multiplier of the lock of the position in basis points, zero if the position is not locked
+    pub lock_reward_multiplier: u16,
     // Unused bytes for future upgrades.
-    pub padding: [u64; 7],
+    pub padding: [u8; 1],
 }
 
 /*
@@ -61,11 +69,21 @@ pub const LEN: usize =
     + 8 // token_fees_owed_0
     + 8 // token_fees_owed_1
     + (PositionRewardInfo::LEN * REWARD_NUM) // reward_infos array
-    + 64; // padding
+    + 8 // recent_epoch
+    + (PositionRewardInfo::LEN * EXTENSION_REWARD_NUM) // extension_reward_infos array
+    + REWARD_NUM // reward_generations
+    + EXTENSION_REWARD_NUM // extension_reward_generations
+    + 2 // lock_reward_multiplier
+    + 1; // padding
 */
 impl PersonalPositionState {
     pub const LEN: usize =
-        8+ 1 + 32 + 32 + 4 + 4 + 16 + 16 + 16 + 8 + 8 + PositionRewardInfo::LEN * REWARD_NUM + 64;
+        8+ 1 + 32 + 32 + 4 + 4 + 16 + 16 + 16 + 8 + 8 + PositionRewardInfo::LEN * REWARD_NUM + 8
+            + PositionRewardInfo::LEN * EXTENSION_REWARD_NUM
+            + REWARD_NUM
+            + EXTENSION_REWARD_NUM
+            + 2
+            + 1;
 
     pub fn seeds(&self) -> [&[u8]; 3] {
         [
@@ -76,37 +94,52 @@ impl PersonalPositionState {
         ]
     }
 
+    pub fn is_locked(&self) -> bool {
+        self.lock_reward_multiplier != 0
+    }
+
+    /// The liquidity earning the rewards, boosted by the reward multiplier of the lock
+    pub fn reward_liquidity(&self) -> u128 {
+        if self.is_locked() {
+            boost_liquidity(self.liquidity, self.lock_reward_multiplier)
+        } else {
+            self.liquidity
+        }
+    }
+
+    /// The extra liquidity the lock of the position adds to the boosted liquidity of the pool
+    pub fn boosted_liquidity(&self) -> u128 {
+        self.reward_liquidity() - self.liquidity
+    }
+
     pub fn update_rewards(
         &mut self,
         reward_growths_inside: [u128; REWARD_NUM],
+        extension_reward_growths_inside: [u128; EXTENSION_REWARD_NUM],
+        reward_generations: [u8; REWARD_NUM],
+        extension_reward_generations: [u8; EXTENSION_REWARD_NUM],
         add_delta: bool,
     ) -> Result<()> {
+        let reward_liquidity = self.reward_liquidity();
         for i in 0..REWARD_NUM {
-            let reward_growth_inside = reward_growths_inside[i];
-            let curr_reward_info = self.reward_infos[i];
-
-            if add_delta {
-                // Calculate reward delta.
-                // If reward delta overflows, default to a zero value. This means the position loses all
-                // rewards earned since the last time the position was modified or rewards were collected.
-                let reward_growth_delta =
-                    reward_growth_inside.wrapping_sub(curr_reward_info.growth_inside_last_x64);
-
-                let amount_owed_delta = U256::from(reward_growth_delta)
-                    .mul_div_floor(U256::from(self.liquidity), U256::from(fixed_point_64::Q64))
-                    .unwrap()
-                    .to_underflow_u64();
-
-                // Overflows not allowed. Must collect rewards owed before overflow.
-                self.reward_infos[i].reward_amount_owed = curr_reward_info
-                    .reward_amount_owed
-                    .checked_add(amount_owed_delta)
-                    .unwrap();
-
-                #[cfg(feature = "enable-log")]
-                msg!("update personal reward, index:{}, owed_before:{:?}, amount_owed_delta:{}, owed_after:{}, reward_growth_delta:{}, self.liquidity:{}", i, curr_reward_info.reward_amount_owed,amount_owed_delta, self.reward_infos[i].reward_amount_owed,reward_growth_delta,self.liquidity );
+            if self.reward_generations[i] != reward_generations[i] {
+                self.reward_infos[i].reset(reward_growths_inside[i]);
+                self.reward_generations[i] = reward_generations[i];
+            }
+            self.reward_infos[i].update(reward_growths_inside[i], reward_liquidity, add_delta);
+            #[cfg(feature = "enable-log")]
+            msg!("update personal reward, index:{}, owed_after:{}, reward_growth_inside:{}, reward_liquidity:{}", i, self.reward_infos[i].reward_amount_owed, reward_growths_inside[i], reward_liquidity);
+        }
+        for i in 0..EXTENSION_REWARD_NUM {
+            if self.extension_reward_generations[i] != extension_reward_generations[i] {
+                self.extension_reward_infos[i].reset(extension_reward_growths_inside[i]);
+                self.extension_reward_generations[i] = extension_reward_generations[i];
             }
-            self.reward_infos[i].growth_inside_last_x64 = reward_growth_inside;
+            self.extension_reward_infos[i].update(
+                extension_reward_growths_inside[i],
+                reward_liquidity,
+                add_delta,
+            );
         }
         self.recent_epoch = get_recent_epoch()?;
         Ok(())
@@ -124,6 +157,36 @@ impl PositionRewardInfo {
     // imp - 8 bits -> 1 byte os 128 bits -> 16 bytest
     // the next 8bytes is the anchor discriminator size 
     pub const LEN: usize = 16 + 8;
+
+    /// Accrue the reward earned by the liquidity since the last growth inside, then checkpoint the growth inside
+    pub fn update(&mut self, reward_growth_inside: u128, liquidity: u128, add_delta: bool) {
+        if add_delta {
+            // Calculate reward delta.
+            // If reward delta overflows, default to a zero value. This means the position loses all
+            // rewards earned since the last time the position was modified or rewards were collected.
+            let reward_growth_delta =
+                reward_growth_inside.wrapping_sub(self.growth_inside_last_x64);
+
+            let amount_owed_delta = U256::from(reward_growth_delta)
+                .mul_div_floor(U256::from(liquidity), U256::from(fixed_point_64::Q64))
+                .unwrap()
+                .to_underflow_u64();
+
+            // Overflows not allowed. Must collect rewards owed before overflow.
+            self.reward_amount_owed = self
+                .reward_amount_owed
+                .checked_add(amount_owed_delta)
+                .unwrap();
+        }
+        self.growth_inside_last_x64 = reward_growth_inside;
+    }
+
+    /// Drop the checkpoint of a retired reward, its amount owed was claimed before the retirement.
+    /// The reward reusing the slot is earned from the given growth inside
+    pub fn reset(&mut self, reward_growth_inside: u128) {
+        self.growth_inside_last_x64 = reward_growth_inside;
+        self.reward_amount_owed = 0;
+    }
 }
 
 /// Emitted when create a new position
@@ -210,6 +273,8 @@ pub struct DecreaseLiquidityEvent {
     pub transfer_fee_0: u64,
     /// The amount of token_1 transfer fee
     pub transfer_fee_1: u64,
+    /// The amount of rewards of the pool reward extension
+    pub extension_reward_amounts: [u64; EXTENSION_REWARD_NUM],
 }
 
 /// Emitted when liquidity decreased or increase.
@@ -263,4 +328,79 @@ pub struct CollectPersonalFeeEvent {
 pub struct UpdateRewardInfosEvent {
     /// Reward info
     pub reward_growth_global_x64: [u128; REWARD_NUM],
+    /// Reward info of the rewards of the pool reward extension
+    pub extension_reward_growth_global_x64: [u128; EXTENSION_REWARD_NUM],
+    /// The current emission rates of the rewards, following their schedules
+    pub emissions_per_second_x64: [u128; REWARD_NUM],
+    /// The current emission rates of the rewards of the pool reward extension
+    pub extension_emissions_per_second_x64: [u128; EXTENSION_REWARD_NUM],
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn personal_position_len_test() {
+        let position = PersonalPositionState::default();
+        assert_eq!(
+            PersonalPositionState::LEN,
+            position.try_to_vec().unwrap().len() + 8
+        );
+    }
+
+    #[test]
+    fn update_rewards_of_retired_slot_test() {
+        let mut position = PersonalPositionState {
+            liquidity: fixed_point_64::Q64,
+            ..Default::default()
+        };
+        position
+            .update_rewards([100, 0, 0], [0; 2], [0; 3], [0; 2], true)
+            .unwrap();
+        position
+            .update_rewards([150, 0, 0], [0; 2], [0; 3], [0; 2], true)
+            .unwrap();
+        assert_eq!(position.reward_infos[0].reward_amount_owed, 150);
+        // the reward can only be retired once every emitted reward is claimed
+        position.reward_infos[0].reward_amount_owed = 0;
+
+        // the slot was retired with its growth frozen, the checkpoint is reset
+        position
+            .update_rewards([150, 0, 0], [0; 2], [1, 0, 0], [0; 2], true)
+            .unwrap();
+        assert_eq!(position.reward_infos[0].reward_amount_owed, 0);
+        assert_eq!(position.reward_infos[0].growth_inside_last_x64, 150);
+        assert_eq!(position.reward_generations, [1, 0, 0]);
+
+        // the new reward of the slot continues the growth
+        position
+            .update_rewards([170, 0, 0], [0; 2], [1, 0, 0], [0; 2], true)
+            .unwrap();
+        assert_eq!(position.reward_infos[0].reward_amount_owed, 20);
+    }
+
+    #[test]
+    fn update_rewards_of_locked_position_test() {
+        let mut position = PersonalPositionState {
+            liquidity: fixed_point_64::Q64,
+            lock_reward_multiplier: 15_000,
+            ..Default::default()
+        };
+        assert!(position.is_locked());
+        assert_eq!(position.boosted_liquidity(), fixed_point_64::Q64 / 2);
+        position
+            .update_rewards([100, 0, 0], [0, 40], [0; 3], [0; 2], true)
+            .unwrap();
+        assert_eq!(position.reward_infos[0].reward_amount_owed, 150);
+        assert_eq!(position.extension_reward_infos[1].reward_amount_owed, 60);
+
+        // the unlocked position earns with its liquidity
+        position.lock_reward_multiplier = 0;
+        assert_eq!(position.boosted_liquidity(), 0);
+        position
+            .update_rewards([200, 0, 0], [0, 40], [0; 3], [0; 2], true)
+            .unwrap();
+        assert_eq!(position.reward_infos[0].reward_amount_owed, 250);
+    }
 }
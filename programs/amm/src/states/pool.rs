@@ -1,9 +1,7 @@
 use crate::error::ErrorCode;
 use crate::libraries::{
-    big_num::{U1024, U128, U256},
-    check_current_tick_array_is_initialized, fixed_point_64,
-    full_math::MulDiv,
-    tick_array_bit_map, tick_math,
+    big_num::{U1024, U256},
+    check_current_tick_array_is_initialized, fixed_point_64, tick_array_bit_map, tick_math,
 };
 use crate::states::*;
 use crate::util::get_recent_epoch;
@@ -25,12 +23,14 @@ pub mod reward_period_limit {
     pub const MIN_REWARD_PERIOD: u64 = 1 * 60 * 60;
     pub const MAX_REWARD_PERIOD: u64 = 2 * 60 * 60;
     pub const INCREASE_EMISSIONES_PERIOD: u64 = 30 * 60;
+    pub const RETIRE_GRACE_PERIOD: u64 = 60 * 60;
 }
 #[cfg(not(feature = "paramset"))]
 pub mod reward_period_limit {
     pub const MIN_REWARD_PERIOD: u64 = 7 * 24 * 60 * 60;
     pub const MAX_REWARD_PERIOD: u64 = 90 * 24 * 60 * 60;
     pub const INCREASE_EMISSIONES_PERIOD: u64 = 72 * 60 * 60;
+    pub const RETIRE_GRACE_PERIOD: u64 = 30 * 24 * 60 * 60;
 }
 
 pub enum PoolStatusBitIndex {
@@ -52,6 +52,12 @@ pub enum PoolStatusBitFlag {
     Disable,
 }
 
+pub enum PoolFeeOverrideBitIndex {
+    TradeFee,
+    ProtocolFee,
+    FundFee,
+}
+
 /// The pool state
 ///
 /// PDA of `[POOL_SEED, config, token_mint_0, token_mint_1]`
@@ -123,8 +129,18 @@ pub struct PoolState {
     /// bit3, 1: disable collect reward, 0: normal
     /// bit4, 1: disable swap, 0: normal
     pub status: u8,
+    /// 1 if the pool has a reward extension, which every instruction changing the liquidity of a position must pass,
+    /// swaps only need it to cross initialized ticks
+    pub reward_extension: u8,
+    /// Incremented each time a reward slot is retired, positions with an older generation
+    /// drop their checkpoint of the retired reward
+    pub reward_generations: [u8; REWARD_NUM],
+    /// Bump of the swap allowlist PDA, set when the pool gets a launch phase
+    pub swap_allowlist_bump: u8,
+    /// Bump of the reward extension PDA, set with `reward_extension`
+    pub reward_extension_bump: u8,
     /// Leave blank for future use
-    pub padding: [u8; 7],
+    pub padding: [u8; 1],
 
     pub reward_infos: [RewardInfo; REWARD_NUM],
 
@@ -143,14 +159,47 @@ pub struct PoolState {
     pub fund_fees_token_1: u64,
 
     // The timestamp allowed for swap in the pool.
-    // Note: The open_time is disabled for now.
     pub open_time: u64,
     // account recent update epoch
     pub recent_epoch: u64,
+    // The seconds per unit of in range liquidity for the entire life of the pool, as Q64.64
+    pub seconds_per_liquidity_global_x64: u128,
+    // The block timestamp the seconds per liquidity was last accumulated at
+    pub seconds_per_liquidity_update_time: u64,
+    // Until this timestamp only the addresses of the swap allowlist can swap, zero if the pool has no launch phase
+    pub launch_end_time: u64,
+    // The tick the volatility of the dynamic fee is measured from
+    pub volatility_reference_tick: i32,
+    // The decayed volatility carried over from the previous swaps, in tick spacings
+    pub volatility_reference: u32,
+    // The volatility the last swap step was charged with, in tick spacings
+    pub volatility_accumulator: u32,
+    // The block timestamp of the last volatility update
+    pub volatility_update_time: u32,
+    // Bitwise representation of the fee rates overridden by the pool
+    // bit0, 1: trade fee overridden, 0: trade fee of the amm config
+    // bit1, 1: protocol fee overridden, 0: protocol fee of the amm config
+    // bit2, 1: fund fee overridden, 0: fund fee of the amm config
+    pub fee_override_flag: u32,
+    // The fee rates replacing the ones of the amm config when overridden, denominated in hundredths of a bip (10^-6)
+    pub trade_fee_rate_override: u32,
+    pub protocol_fee_rate_override: u32,
+    pub fund_fee_rate_override: u32,
+    // The owner proposed by the admin, it becomes the pool and reward owner once it accepts
+    pub pending_owner: Pubkey,
+    // The emission schedules of the reward infos, a constant emission by default
+    pub reward_schedules: [RewardSchedule; REWARD_NUM],
+    // The extra liquidity of the locked positions in range, the rewards are shared by the liquidity and the boosted liquidity
+    pub boosted_liquidity: u128,
+    // The minimum seconds a position can be locked for
+    pub min_lock_duration: u32,
+    // The reward multiplier of the new position locks in basis points, zero if the pool disables the locks
+    pub lock_reward_multiplier: u16,
+    pub padding5: u16,
 
     // Unused bytes for future upgrades.
-    pub padding1: [u64; 24],
-    pub padding2: [u64; 32],
+    pub padding1: [u64; 3],
+    pub padding2: [u64; 29],
 }
 
 impl PoolState {
@@ -177,8 +226,19 @@ impl PoolState {
         + RewardInfo::LEN * REWARD_NUM
         + 8 * 16 //    pub tick_array_bitmap: [u64; 16] this means there 16 elements each 8bytes
         + 8 * 8 
-        + 8 * 24
-        + 8 * 32;
+        + 16
+        + 8
+        + 8
+        + 4 * 4
+        + 4 * 4
+        + 32
+        + RewardSchedule::LEN * REWARD_NUM
+        + 16
+        + 4
+        + 2
+        + 2
+        + 8 * 3
+        + 8 * 29;
     /*
     The data type &[u8]; 5 represents an array of byte slices with 5 elements, where each element is a reference to a slice of bytes (u8).
 
@@ -253,7 +313,11 @@ Breakdown:
         self.swap_in_amount_token_1 = 0;
         self.swap_out_amount_token_0 = 0;
         self.status = 0;
-        self.padding = [0; 7];
+        self.reward_extension = 0;
+        self.reward_generations = [0; REWARD_NUM];
+        self.swap_allowlist_bump = 0;
+        self.reward_extension_bump = 0;
+        self.padding = [0; 1];
         self.tick_array_bitmap = [0; 16];
         self.total_fees_token_0 = 0;
         self.total_fees_claimed_token_0 = 0;
@@ -264,8 +328,25 @@ Breakdown:
         self.open_time = open_time;
         //“If this function returns an Err, then return that error from the current function immediately. Otherwise, unwrap the Ok value.”
         self.recent_epoch = get_recent_epoch()?;
-        self.padding1 = [0; 24];
-        self.padding2 = [0; 32];
+        self.seconds_per_liquidity_global_x64 = 0;
+        self.seconds_per_liquidity_update_time = 0;
+        self.launch_end_time = 0;
+        self.volatility_reference_tick = tick;
+        self.volatility_reference = 0;
+        self.volatility_accumulator = 0;
+        self.volatility_update_time = 0;
+        self.fee_override_flag = 0;
+        self.trade_fee_rate_override = 0;
+        self.protocol_fee_rate_override = 0;
+        self.fund_fee_rate_override = 0;
+        self.pending_owner = Pubkey::default();
+        self.reward_schedules = [RewardSchedule::default(); REWARD_NUM];
+        self.boosted_liquidity = 0;
+        self.min_lock_duration = 0;
+        self.lock_reward_multiplier = 0;
+        self.padding5 = 0;
+        self.padding1 = [0; 3];
+        self.padding2 = [0; 29];
         self.observation_key = observation_state_key;
 
         Ok(())
@@ -343,7 +424,8 @@ Breakdown:
             // the last reward token must be controled by the admin
             require!(
                 *authority == crate::admin::id()
-                    || operation_state.validate_operation_owner(*authority),
+                    || operation_state
+                        .validate_operator_role(*authority, OperatorRoleBitIndex::RewardAdmin),
                 ErrorCode::NotApproved
             );
         }
@@ -368,75 +450,44 @@ Breakdown:
 
     // Calculates the next global reward growth variables based on the given timestamp.
     // The provided timestamp must be greater than or equal to the last updated timestamp.
+    /// Move the volatility reference before a swap if the last swap is older than the filter period,
+    /// the volatility carried over decays linearly to zero over the decay period.
+    pub fn update_volatility_reference(&mut self, amm_config: &AmmConfig, block_timestamp: u32) {
+        let elapsed = block_timestamp.saturating_sub(self.volatility_update_time);
+        if elapsed >= amm_config.volatility_filter_period {
+            self.volatility_reference_tick = self.tick_current;
+            self.volatility_reference = if elapsed < amm_config.volatility_decay_period {
+                (u64::from(self.volatility_accumulator)
+                    * u64::from(amm_config.volatility_decay_period - elapsed)
+                    / u64::from(amm_config.volatility_decay_period)) as u32
+            } else {
+                0
+            };
+        }
+        self.volatility_update_time = block_timestamp;
+    }
+
+    /// Accumulate the tick spacings crossed from the volatility reference tick up to `tick`
+    pub fn update_volatility_accumulator(&mut self, tick: i32) {
+        let tick_spacing_delta = (i64::from(tick) - i64::from(self.volatility_reference_tick))
+            .unsigned_abs()
+            / u64::from(self.tick_spacing);
+        self.volatility_accumulator = (u64::from(self.volatility_reference) + tick_spacing_delta)
+            .min(u64::from(u32::MAX)) as u32;
+    }
+
     pub fn update_reward_infos(&mut self, curr_timestamp: u64) -> Result<[RewardInfo; REWARD_NUM]> {
         #[cfg(feature = "enable-log")]
         msg!("current block timestamp:{}", curr_timestamp);
 
         let mut next_reward_infos = self.reward_infos;
-
-        for i in 0..REWARD_NUM {
-            let reward_info = &mut next_reward_infos[i];
-            if !reward_info.initialized() {
-                continue;
-            }
-            if curr_timestamp <= reward_info.open_time {
-                continue;
-            }
-            let latest_update_timestamp = curr_timestamp.min(reward_info.end_time);
-
-            if self.liquidity != 0 {
-                require_gte!(latest_update_timestamp, reward_info.last_update_time);
-                let time_delta = latest_update_timestamp
-                    .checked_sub(reward_info.last_update_time)
-                    .unwrap();
-
-                let reward_growth_delta = U256::from(time_delta)
-                    .mul_div_floor(
-                        U256::from(reward_info.emissions_per_second_x64),
-                        U256::from(self.liquidity),
-                    )
-                    .unwrap();
-
-                reward_info.reward_growth_global_x64 = reward_info
-                    .reward_growth_global_x64
-                    .checked_add(reward_growth_delta.as_u128())
-                    .unwrap();
-
-                reward_info.reward_total_emissioned = reward_info
-                    .reward_total_emissioned
-                    .checked_add(
-                        U128::from(time_delta)
-                            .mul_div_ceil(
-                                U128::from(reward_info.emissions_per_second_x64),
-                                U128::from(fixed_point_64::Q64),
-                            )
-                            .unwrap()
-                            .as_u64(),
-                    )
-                    .unwrap();
-                #[cfg(feature = "enable-log")]
-                msg!(
-                    "reward_index:{},latest_update_timestamp:{},reward_info.reward_last_update_time:{},time_delta:{},reward_emission_per_second_x64:{},reward_growth_delta:{},reward_info.reward_growth_global_x64:{}, reward_info.reward_claim:{}",
-                    i,
-                    latest_update_timestamp,
-                    identity(reward_info.last_update_time),
-                    time_delta,
-                    identity(reward_info.emissions_per_second_x64),
-                    reward_growth_delta,
-                    identity(reward_info.reward_growth_global_x64),
-                    identity(reward_info.reward_claimed)
-                );
-            }
-            reward_info.last_update_time = latest_update_timestamp;
-            // update reward state
-            if latest_update_timestamp >= reward_info.open_time
-                && latest_update_timestamp < reward_info.end_time
-            {
-                reward_info.reward_state = RewardState::Opening as u8;
-            } else if latest_update_timestamp == next_reward_infos[i].end_time {
-                next_reward_infos[i].reward_state = RewardState::Ended as u8;
-            }
-        }
+        let reward_schedules = self.reward_schedules;
+        update_reward_growths(
+            &mut next_reward_infos,
+            &reward_schedules,
+            self.reward_liquidity(),
+            curr_timestamp,
+        )?;
         self.reward_infos = next_reward_infos;
         #[cfg(feature = "enable-log")]
         msg!("update pool reward info, reward_0_total_emissioned:{}, reward_1_total_emissioned:{}, reward_2_total_emissioned:{}, pool.liquidity:{}",
@@ -445,6 +496,31 @@ Breakdown:
         Ok(next_reward_infos)
     }
 
+    /// The seconds per unit of in range liquidity accumulated up to `block_timestamp`, as Q64.64
+    pub fn seconds_per_liquidity_cumulative_x64(&self, block_timestamp: u64) -> u128 {
+        // the pools created before the accumulator start it with their next liquidity change
+        if self.seconds_per_liquidity_update_time == 0 {
+            return self.seconds_per_liquidity_global_x64;
+        }
+        let delta_time = block_timestamp.saturating_sub(self.seconds_per_liquidity_update_time);
+        self.seconds_per_liquidity_global_x64.wrapping_add(
+            (u128::from(delta_time) << fixed_point_64::RESOLUTION) / self.liquidity.max(1),
+        )
+    }
+
+    /// Accumulates the seconds per liquidity up to `block_timestamp` with the current in range
+    /// liquidity, must be called before the in range liquidity changes
+    pub fn update_seconds_per_liquidity(&mut self, block_timestamp: u64) {
+        self.seconds_per_liquidity_global_x64 =
+            self.seconds_per_liquidity_cumulative_x64(block_timestamp);
+        self.seconds_per_liquidity_update_time = block_timestamp;
+    }
+
+    /// The liquidity sharing the reward emissions, the in range liquidity boosted by the locked positions
+    pub fn reward_liquidity(&self) -> u128 {
+        self.liquidity.checked_add(self.boosted_liquidity).unwrap()
+    }
+
     pub fn check_unclaimed_reward(&self, index: usize, reward_amount_owed: u64) -> Result<()> {
         assert!(index < REWARD_NUM);
         let unclaimed_reward = self.reward_infos[index]
@@ -464,6 +540,14 @@ Breakdown:
         Ok(())
     }
 
+    /// Retire the reward of the slot and move the slot to the next generation
+    pub fn retire_reward(&mut self, index: usize) {
+        assert!(index < REWARD_NUM);
+        self.reward_infos[index].retire();
+        self.reward_schedules[index] = RewardSchedule::default();
+        self.reward_generations[index] = self.reward_generations[index].wrapping_add(1);
+    }
+
     pub fn get_tick_array_offset(&self, tick_array_start_index: i32) -> Result<usize> {
         require!(
             TickArrayState::check_is_valid_start_index(tick_array_start_index, self.tick_spacing),
@@ -605,6 +689,81 @@ Breakdown:
         self.status.bitand(status) == 0
     }
 
+    /// Get status by bit with the status of the amm config inherited when the config is passed,
+    /// if it is `normal` status both in the pool and in the amm config, return true
+    pub fn get_status_by_bit_with_config(
+        &self,
+        bit: PoolStatusBitIndex,
+        amm_config: Option<&AmmConfig>,
+    ) -> bool {
+        let status = u8::from(1) << (bit as u8);
+        let config_status = amm_config.map_or(0, |amm_config| amm_config.status);
+        (self.status | config_status).bitand(status) == 0
+    }
+
+    /// Set the fee rate overrides of the pool, `None` falls back to the fee rate of the amm config
+    pub fn set_fee_overrides(
+        &mut self,
+        trade_fee_rate: Option<u32>,
+        protocol_fee_rate: Option<u32>,
+        fund_fee_rate: Option<u32>,
+    ) {
+        let mut fee_override_flag = 0;
+        if let Some(trade_fee_rate) = trade_fee_rate {
+            fee_override_flag |= 1 << (PoolFeeOverrideBitIndex::TradeFee as u32);
+            self.trade_fee_rate_override = trade_fee_rate;
+        } else {
+            self.trade_fee_rate_override = 0;
+        }
+        if let Some(protocol_fee_rate) = protocol_fee_rate {
+            fee_override_flag |= 1 << (PoolFeeOverrideBitIndex::ProtocolFee as u32);
+            self.protocol_fee_rate_override = protocol_fee_rate;
+        } else {
+            self.protocol_fee_rate_override = 0;
+        }
+        if let Some(fund_fee_rate) = fund_fee_rate {
+            fee_override_flag |= 1 << (PoolFeeOverrideBitIndex::FundFee as u32);
+            self.fund_fee_rate_override = fund_fee_rate;
+        } else {
+            self.fund_fee_rate_override = 0;
+        }
+        self.fee_override_flag = fee_override_flag;
+    }
+
+    /// Set the fee rate overrides and check the fee rates the pool ends up with
+    pub fn update_fee_overrides(
+        &mut self,
+        amm_config: &AmmConfig,
+        trade_fee_rate: Option<u32>,
+        protocol_fee_rate: Option<u32>,
+        fund_fee_rate: Option<u32>,
+    ) -> Result<AmmConfig> {
+        self.set_fee_overrides(trade_fee_rate, protocol_fee_rate, fund_fee_rate);
+        let effective_amm_config = self.effective_amm_config(amm_config);
+        effective_amm_config.check_fee_rates()?;
+        Ok(effective_amm_config)
+    }
+
+    /// Whether the fee rate is overridden by the pool
+    pub fn is_fee_overridden(&self, bit: PoolFeeOverrideBitIndex) -> bool {
+        self.fee_override_flag.bitand(1 << (bit as u32)) != 0
+    }
+
+    /// The amm config with its fee rates replaced by the fee overrides of the pool
+    pub fn effective_amm_config(&self, amm_config: &AmmConfig) -> AmmConfig {
+        let mut effective_amm_config = amm_config.clone();
+        if self.is_fee_overridden(PoolFeeOverrideBitIndex::TradeFee) {
+            effective_amm_config.trade_fee_rate = self.trade_fee_rate_override;
+        }
+        if self.is_fee_overridden(PoolFeeOverrideBitIndex::ProtocolFee) {
+            effective_amm_config.protocol_fee_rate = self.protocol_fee_rate_override;
+        }
+        if self.is_fee_overridden(PoolFeeOverrideBitIndex::FundFee) {
+            effective_amm_config.fund_fee_rate = self.fund_fee_rate_override;
+        }
+        effective_amm_config
+    }
+
     pub fn is_overflow_default_tickarray_bitmap(&self, tick_indexs: Vec<i32>) -> bool {
         let (min_tick_array_start_index_boundary, max_tick_array_index_boundary) =
             self.tick_array_start_index_range();
@@ -641,6 +800,69 @@ Breakdown:
     }
 }
 
+/// Accumulates the reward growths of the initialized rewards up to the timestamp,
+/// the emissions follow the schedules of the rewards and are shared by the in range liquidity
+pub fn update_reward_growths<const N: usize>(
+    reward_infos: &mut [RewardInfo; N],
+    reward_schedules: &[RewardSchedule; N],
+    liquidity: u128,
+    curr_timestamp: u64,
+) -> Result<()> {
+    for i in 0..N {
+        let reward_info = &mut reward_infos[i];
+        if !reward_info.initialized() {
+            continue;
+        }
+        if curr_timestamp <= reward_info.open_time {
+            continue;
+        }
+        let latest_update_timestamp = curr_timestamp.min(reward_info.end_time);
+
+        if liquidity != 0 {
+            require_gte!(latest_update_timestamp, reward_info.last_update_time);
+            let reward_emissions_x64 = reward_schedules[i].emissions_between_x64(
+                reward_info,
+                reward_info.last_update_time,
+                latest_update_timestamp,
+            );
+
+            let reward_growth_delta = reward_emissions_x64 / U256::from(liquidity);
+
+            reward_info.reward_growth_global_x64 = reward_info
+                .reward_growth_global_x64
+                .checked_add(reward_growth_delta.as_u128())
+                .unwrap();
+
+            reward_info.reward_total_emissioned = reward_info
+                .reward_total_emissioned
+                .checked_add(emissions_amount(reward_emissions_x64))
+                .unwrap();
+            #[cfg(feature = "enable-log")]
+            msg!(
+                "reward_index:{},latest_update_timestamp:{},reward_info.reward_last_update_time:{},reward_emissions_x64:{},reward_emission_per_second_x64:{},reward_growth_delta:{},reward_info.reward_growth_global_x64:{}, reward_info.reward_claim:{}",
+                i,
+                latest_update_timestamp,
+                identity(reward_info.last_update_time),
+                reward_emissions_x64,
+                identity(reward_info.emissions_per_second_x64),
+                reward_growth_delta,
+                identity(reward_info.reward_growth_global_x64),
+                identity(reward_info.reward_claimed)
+            );
+        }
+        reward_info.last_update_time = latest_update_timestamp;
+        // update reward state
+        if latest_update_timestamp >= reward_info.open_time
+            && latest_update_timestamp < reward_info.end_time
+        {
+            reward_info.reward_state = RewardState::Opening as u8;
+        } else if latest_update_timestamp == reward_infos[i].end_time {
+            reward_infos[i].reward_state = RewardState::Ended as u8;
+        }
+    }
+    Ok(())
+}
+
 #[derive(Copy, Clone, AnchorSerialize, AnchorDeserialize, Debug, PartialEq)]
 /// State of reward
 pub enum RewardState {
@@ -695,14 +917,51 @@ impl RewardInfo {
     }
 
     /// Returns true if this reward is initialized.
-    /// Once initialized, a reward cannot transition back to uninitialized.
+    /// Once initialized, a reward only transitions back to uninitialized when it is retired.
     pub fn initialized(&self) -> bool {
         self.token_mint.ne(&Pubkey::default())
     }
 
-    pub fn get_reward_growths(reward_infos: &[RewardInfo; REWARD_NUM]) -> [u128; REWARD_NUM] {
-        let mut reward_growths = [0u128; REWARD_NUM];
-        for i in 0..REWARD_NUM {
+    /// Returns true if the ticks track the growth of this reward,
+    /// a retired reward keeps its growth until its slot is reused.
+    pub fn has_growth(&self) -> bool {
+        self.initialized() || self.reward_growth_global_x64 != 0
+    }
+
+    /// Check the reward has ended and every emitted reward has been claimed by the positions,
+    /// or the grace period to claim has passed since the end time.
+    /// A position can't collect a reward any more once its slot is retired
+    pub fn check_settled(&self, current_timestamp: u64) -> Result<()> {
+        require!(
+            self.initialized()
+                && self.last_update_time == self.end_time
+                && (self.unclaimed_reward() == 0
+                    || current_timestamp
+                        >= self.end_time + reward_period_limit::RETIRE_GRACE_PERIOD),
+            ErrorCode::RewardNotSettled
+        );
+        Ok(())
+    }
+
+    /// The emitted reward not claimed by the positions yet
+    pub fn unclaimed_reward(&self) -> u64 {
+        self.reward_total_emissioned
+            .saturating_sub(self.reward_claimed)
+    }
+
+    /// Clear the reward so its slot can be initialized with a new mint,
+    /// the growth is kept so that the growths outside of the ticks stay consistent with it.
+    pub fn retire(&mut self) {
+        *self = RewardInfo {
+            authority: self.authority,
+            reward_growth_global_x64: self.reward_growth_global_x64,
+            ..Default::default()
+        };
+    }
+
+    pub fn get_reward_growths<const N: usize>(reward_infos: &[RewardInfo; N]) -> [u128; N] {
+        let mut reward_growths = [0u128; N];
+        for i in 0..N {
             reward_growths[i] = reward_infos[i].reward_growth_global_x64;
         }
         reward_growths
@@ -738,6 +997,54 @@ pub struct PoolCreatedEvent {
     pub token_vault_0: Pubkey,
     /// Vault of token_1
     pub token_vault_1: Pubkey,
+
+    /// The trade fee rate of the pool, denominated in hundredths of a bip (10^-6)
+    pub trade_fee_rate: u32,
+    /// The protocol fee rate of the pool
+    pub protocol_fee_rate: u32,
+    /// The fund fee rate of the pool
+    pub fund_fee_rate: u32,
+}
+
+/// Emitted when the fee overrides of a pool are updated
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct PoolFeeChangeEvent {
+    /// The pool of which the fees are changed
+    #[index]
+    pub pool_state: Pubkey,
+    /// The effective trade fee rate of the pool, denominated in hundredths of a bip (10^-6)
+    pub trade_fee_rate: u32,
+    /// The effective protocol fee rate of the pool
+    pub protocol_fee_rate: u32,
+    /// The effective fund fee rate of the pool
+    pub fund_fee_rate: u32,
+}
+
+/// Emitted when the status of a pool is changed
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct PoolStatusChangeEvent {
+    /// The pool of which the status is changed
+    #[index]
+    pub pool_state: Pubkey,
+    /// The `PoolStatusBitIndex` bits flipped to disabled
+    pub disabled_bits: u8,
+    /// The `PoolStatusBitIndex` bits flipped to enabled
+    pub enabled_bits: u8,
+    /// The status of the pool after the change
+    pub status: u8,
+}
+
+impl PoolStatusChangeEvent {
+    pub fn new(pool_state: Pubkey, status_before: u8, status_after: u8) -> Self {
+        Self {
+            pool_state,
+            disabled_bits: status_after & !status_before,
+            enabled_bits: status_before & !status_after,
+            status: status_after,
+        }
+    }
 }
 
 /// Emitted when the collected protocol fees are withdrawn by the factory owner
@@ -806,6 +1113,44 @@ pub struct SwapEvent {
 
     /// The log base 1.0001 of price of the pool after the swap
     pub tick: i32,
+
+    /// The trade fee rate charged by the last step of the swap, denominated in hundredths of a bip (10^-6)
+    pub trade_fee_rate: u32,
+}
+
+/// A hop of a routed swap
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct RouteSwapHop {
+    /// The pool of the hop
+    pub pool_state: Pubkey,
+
+    /// The amount paid from the input token account of the hop, transfer fee included
+    pub amount_in: u64,
+
+    /// The amount received by the output token account of the hop
+    pub amount_out: u64,
+
+    /// The sqrt(price) of the pool after the hop, as a Q64.64
+    pub sqrt_price_x64: u128,
+}
+
+/// Emitted once by a routed swap with every hop of the route
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct RouteSwapEvent {
+    /// The address that initiated the swap call
+    #[index]
+    pub sender: Pubkey,
+
+    /// The amount paid from the input token account of the route
+    pub amount_in: u64,
+
+    /// The amount received by the output token account of the route
+    pub amount_out: u64,
+
+    /// The hops of the route, in the swap order
+    pub hops: Vec<RouteSwapHop>,
 }
 
 /// Emitted pool liquidity change when increase and decrease liquidity
@@ -832,6 +1177,48 @@ pub struct LiquidityChangeEvent {
     pub liquidity_after: u128,
 }
 
+/// Emitted when a reward is funded
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct FundRewardEvent {
+    #[index]
+    pub pool_state: Pubkey,
+    pub funder: Pubkey,
+    pub reward_index: u8,
+    /// The amount received by the reward vault
+    pub amount: u64,
+    pub end_time: u64,
+    pub emissions_per_second_x64: u128,
+}
+
+/// Emitted when a settled reward is retired and its slot can be reused
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct RetireRewardEvent {
+    #[index]
+    pub pool_state: Pubkey,
+    pub reward_index: u8,
+    pub token_mint: Pubkey,
+    /// The generation of the slot after the retirement
+    pub generation: u8,
+    /// The balance of the vault beyond the unclaimed reward, swept to the pool owner
+    pub amount_remaining: u64,
+}
+
+/// Emitted when the emission schedule of a reward is set
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct SetRewardScheduleEvent {
+    #[index]
+    pub pool_state: Pubkey,
+    pub reward_index: u8,
+    /// RewardScheduleKind of the schedule
+    pub kind: u8,
+    pub step_period: u32,
+    pub start_emissions_per_second_x64: u128,
+    pub end_emissions_per_second_x64: u128,
+}
+
 // /// Emitted when price move in a swap step
 // #[event]
 // #[cfg_attr(feature = "client", derive(Debug))]
@@ -1058,10 +1445,198 @@ pub mod pool_test {
                 false
             );
         }
+
+        #[test]
+        fn get_status_by_bit_with_config() {
+            let mut pool_state = PoolState::default();
+            let mut amm_config = AmmConfig::default();
+            pool_state.set_status(1 << PoolStatusBitIndex::Swap as u8);
+            amm_config.status = 1 << PoolStatusBitIndex::DecreaseLiquidity as u8;
+
+            assert!(!pool_state
+                .get_status_by_bit_with_config(PoolStatusBitIndex::Swap, Some(&amm_config)));
+            assert!(!pool_state.get_status_by_bit_with_config(
+                PoolStatusBitIndex::DecreaseLiquidity,
+                Some(&amm_config)
+            ));
+            assert!(pool_state
+                .get_status_by_bit_with_config(PoolStatusBitIndex::CollectFee, Some(&amm_config)));
+            // the pool status is unchanged
+            assert!(pool_state.get_status_by_bit(PoolStatusBitIndex::DecreaseLiquidity));
+            // without the config only the pool status applies
+            assert!(pool_state
+                .get_status_by_bit_with_config(PoolStatusBitIndex::DecreaseLiquidity, None));
+        }
+
+        #[test]
+        fn status_change_event_flipped_bits() {
+            let swap = 1 << PoolStatusBitIndex::Swap as u8;
+            let deposit = 1 << PoolStatusBitIndex::OpenPositionOrIncreaseLiquidity as u8;
+            let collect_fee = 1 << PoolStatusBitIndex::CollectFee as u8;
+
+            let event = PoolStatusChangeEvent::new(
+                Pubkey::default(),
+                collect_fee,
+                collect_fee | swap | deposit,
+            );
+            assert_eq!(event.disabled_bits, swap | deposit);
+            assert_eq!(event.enabled_bits, 0);
+            assert_eq!(event.status, collect_fee | swap | deposit);
+
+            let event = PoolStatusChangeEvent::new(Pubkey::default(), collect_fee | swap, deposit);
+            assert_eq!(event.disabled_bits, deposit);
+            assert_eq!(event.enabled_bits, collect_fee | swap);
+            assert_eq!(event.status, deposit);
+        }
+    }
+
+    mod volatility_test {
+        use super::*;
+
+        fn dynamic_fee_config() -> AmmConfig {
+            AmmConfig {
+                trade_fee_rate: 1000,
+                dynamic_fee_control: 100,
+                min_trade_fee_rate: 500,
+                max_trade_fee_rate: 10000,
+                volatility_filter_period: 10,
+                volatility_decay_period: 100,
+                ..Default::default()
+            }
+        }
+
+        #[test]
+        fn update_volatility_accumulator_test() {
+            let pool_state = &mut PoolState::default();
+            pool_state.tick_spacing = 10;
+            pool_state.volatility_reference_tick = 100;
+            pool_state.volatility_reference = 5;
+
+            pool_state.update_volatility_accumulator(100);
+            assert_eq!({ pool_state.volatility_accumulator }, 5);
+            pool_state.update_volatility_accumulator(-35);
+            assert_eq!({ pool_state.volatility_accumulator }, 5 + 13);
+            pool_state.update_volatility_accumulator(230);
+            assert_eq!({ pool_state.volatility_accumulator }, 5 + 13);
+        }
+
+        #[test]
+        fn update_volatility_reference_test() {
+            let amm_config = dynamic_fee_config();
+            let pool_state = &mut PoolState::default();
+            pool_state.tick_spacing = 10;
+            pool_state.tick_current = 200;
+            pool_state.volatility_reference_tick = 100;
+            pool_state.volatility_accumulator = 40;
+            pool_state.volatility_update_time = 1000;
+
+            // within the filter period the reference is kept
+            pool_state.update_volatility_reference(&amm_config, 1005);
+            assert_eq!({ pool_state.volatility_reference_tick }, 100);
+            assert_eq!({ pool_state.volatility_reference }, 0);
+            assert_eq!({ pool_state.volatility_update_time }, 1005);
+
+            // after the filter period the reference moves and the volatility decays
+            pool_state.update_volatility_reference(&amm_config, 1030);
+            assert_eq!({ pool_state.volatility_reference_tick }, 200);
+            assert_eq!({ pool_state.volatility_reference }, 40 * 75 / 100);
+
+            // after the decay period nothing is carried over
+            pool_state.update_volatility_reference(&amm_config, 1130);
+            assert_eq!({ pool_state.volatility_reference }, 0);
+        }
+
+        #[test]
+        fn get_trade_fee_rate_test() {
+            let mut amm_config = dynamic_fee_config();
+            assert_eq!(amm_config.get_trade_fee_rate(0), 1000);
+            assert_eq!(amm_config.get_trade_fee_rate(20), 1000 + 20 * 100);
+            assert_eq!(amm_config.get_trade_fee_rate(1000), 10000);
+
+            amm_config.trade_fee_rate = 100;
+            assert_eq!(amm_config.get_trade_fee_rate(0), 500);
+
+            // the static fee ignores the volatility and the bounds
+            amm_config.dynamic_fee_control = 0;
+            assert_eq!(amm_config.get_trade_fee_rate(1000), 100);
+        }
+    }
+
+    mod fee_override_test {
+        use super::*;
+
+        #[test]
+        fn effective_amm_config_test() {
+            let amm_config = AmmConfig {
+                trade_fee_rate: 1000,
+                protocol_fee_rate: 120000,
+                fund_fee_rate: 40000,
+                ..Default::default()
+            };
+            let pool_state = &mut PoolState::default();
+            let effective_amm_config = pool_state.effective_amm_config(&amm_config);
+            assert_eq!(effective_amm_config.trade_fee_rate, 1000);
+            assert_eq!(effective_amm_config.protocol_fee_rate, 120000);
+            assert_eq!(effective_amm_config.fund_fee_rate, 40000);
+
+            // a zero override is still an override
+            pool_state.set_fee_overrides(Some(2500), None, Some(0));
+            assert!(pool_state.is_fee_overridden(PoolFeeOverrideBitIndex::TradeFee));
+            assert!(!pool_state.is_fee_overridden(PoolFeeOverrideBitIndex::ProtocolFee));
+            assert!(pool_state.is_fee_overridden(PoolFeeOverrideBitIndex::FundFee));
+            let effective_amm_config = pool_state.effective_amm_config(&amm_config);
+            assert_eq!(effective_amm_config.trade_fee_rate, 2500);
+            assert_eq!(effective_amm_config.protocol_fee_rate, 120000);
+            assert_eq!(effective_amm_config.fund_fee_rate, 0);
+
+            // clear the overrides
+            pool_state.set_fee_overrides(None, None, None);
+            assert_eq!({ pool_state.fee_override_flag }, 0);
+            let effective_amm_config = pool_state.effective_amm_config(&amm_config);
+            assert_eq!(effective_amm_config.trade_fee_rate, 1000);
+            assert_eq!(effective_amm_config.fund_fee_rate, 40000);
+        }
+
+        #[test]
+        fn update_fee_overrides_test() {
+            let amm_config = AmmConfig {
+                trade_fee_rate: 1000,
+                protocol_fee_rate: 120000,
+                fund_fee_rate: 40000,
+                ..Default::default()
+            };
+            let pool_state = &mut PoolState::default();
+            let effective_amm_config = pool_state
+                .update_fee_overrides(&amm_config, Some(3000), None, None)
+                .unwrap();
+            assert_eq!(effective_amm_config.trade_fee_rate, 3000);
+
+            // the protocol fee override plus the fund fee of the config exceeds the trade fee
+            assert!(pool_state
+                .update_fee_overrides(&amm_config, None, Some(960001), None)
+                .is_err());
+            assert!(pool_state
+                .update_fee_overrides(&amm_config, Some(FEE_RATE_DENOMINATOR_VALUE), None, None)
+                .is_err());
+
+            // the trade fee override can't be above the upper bound of the dynamic fee
+            let amm_config = AmmConfig {
+                dynamic_fee_control: 100,
+                max_trade_fee_rate: 10000,
+                ..amm_config
+            };
+            assert!(pool_state
+                .update_fee_overrides(&amm_config, Some(10000), None, None)
+                .is_ok());
+            assert!(pool_state
+                .update_fee_overrides(&amm_config, Some(10001), None, None)
+                .is_err());
+        }
     }
 
     mod update_reward_infos_test {
         use super::*;
+        use crate::libraries::fixed_point_64;
         use anchor_lang::prelude::Pubkey;
         use std::convert::identity;
         use std::str::FromStr;
@@ -1073,6 +1648,8 @@ pub mod pool_test {
                 bump: 0,
                 operation_owners: [Pubkey::default(); OPERATION_SIZE_USIZE],
                 whitelist_mints: [Pubkey::default(); WHITE_MINT_SIZE_USIZE],
+                operation_owner_roles: [0; OPERATION_SIZE_USIZE],
+                padding: [0; 8],
             };
             pool_state
                 .initialize_reward(
@@ -1116,6 +1693,194 @@ pub mod pool_test {
                 1666069200
             );
         }
+
+        #[test]
+        fn retire_reward_test() {
+            let pool_state = &mut PoolState::default();
+            let operation_state = OperationState {
+                bump: 0,
+                operation_owners: [Pubkey::default(); OPERATION_SIZE_USIZE],
+                whitelist_mints: [Pubkey::default(); WHITE_MINT_SIZE_USIZE],
+                operation_owner_roles: [0; OPERATION_SIZE_USIZE],
+                padding: [0; 8],
+            };
+            let reward_mint = Pubkey::new_unique();
+            pool_state
+                .initialize_reward(
+                    1665982800,
+                    1666069200,
+                    10 << fixed_point_64::RESOLUTION,
+                    &reward_mint,
+                    COption::None,
+                    &Pubkey::default(),
+                    &Pubkey::default(),
+                    &operation_state,
+                )
+                .unwrap();
+            pool_state.liquidity = 100;
+
+            // the reward has not ended yet
+            pool_state.update_reward_infos(1665983000).unwrap();
+            assert!(pool_state.reward_infos[0]
+                .check_settled(1665983000)
+                .is_err());
+
+            // the positions have not claimed every emitted reward
+            pool_state.update_reward_infos(1666069300).unwrap();
+            pool_state.add_reward_clamed(0, 1000).unwrap();
+            let reward_info = pool_state.reward_infos[0];
+            assert!(reward_info.check_settled(1666069300).is_err());
+            // until the grace period to claim has passed
+            let grace_end = 1666069200 + reward_period_limit::RETIRE_GRACE_PERIOD;
+            assert!(reward_info.check_settled(grace_end - 1).is_err());
+            assert!(reward_info.check_settled(grace_end).is_ok());
+
+            let unclaimed_reward = reward_info.unclaimed_reward();
+            assert_eq!(
+                unclaimed_reward,
+                reward_info.reward_total_emissioned - reward_info.reward_claimed
+            );
+            pool_state
+                .add_reward_clamed(0, unclaimed_reward - 1)
+                .unwrap();
+            assert!(pool_state.reward_infos[0]
+                .check_settled(1666069300)
+                .is_err());
+            pool_state.add_reward_clamed(0, 1).unwrap();
+            let reward_info = pool_state.reward_infos[0];
+            assert!(reward_info.check_settled(1666069300).is_ok());
+
+            let growth_before = reward_info.reward_growth_global_x64;
+            pool_state.retire_reward(0);
+            let reward_info = pool_state.reward_infos[0];
+            assert!(!reward_info.initialized());
+            assert!(reward_info.has_growth());
+            assert_eq!(
+                identity(reward_info.reward_growth_global_x64),
+                growth_before
+            );
+            assert_eq!(identity(reward_info.reward_total_emissioned), 0);
+            assert_eq!(identity(reward_info.reward_claimed), 0);
+            assert_eq!(identity(pool_state.reward_generations), [1, 0, 0]);
+            assert!(reward_info.check_settled(grace_end).is_err());
+
+            // the slot is reused by a new mint, the growth continues from the retired reward
+            pool_state
+                .initialize_reward(
+                    1666070000,
+                    1666156400,
+                    10 << fixed_point_64::RESOLUTION,
+                    &Pubkey::new_unique(),
+                    COption::None,
+                    &Pubkey::default(),
+                    &Pubkey::default(),
+                    &operation_state,
+                )
+                .unwrap();
+            pool_state.update_reward_infos(1666070100).unwrap();
+            assert!(identity(pool_state.reward_infos[0].reward_growth_global_x64) > growth_before);
+        }
+
+        #[test]
+        fn scheduled_reward_test() {
+            let pool_state = &mut PoolState::default();
+            let operation_state = OperationState {
+                bump: 0,
+                operation_owners: [Pubkey::default(); OPERATION_SIZE_USIZE],
+                whitelist_mints: [Pubkey::default(); WHITE_MINT_SIZE_USIZE],
+                operation_owner_roles: [0; OPERATION_SIZE_USIZE],
+                padding: [0; 8],
+            };
+            pool_state
+                .initialize_reward(
+                    1665982800,
+                    1666069200,
+                    10 << fixed_point_64::RESOLUTION,
+                    &Pubkey::new_unique(),
+                    COption::None,
+                    &Pubkey::default(),
+                    &Pubkey::default(),
+                    &operation_state,
+                )
+                .unwrap();
+            pool_state.reward_schedules[0] = RewardSchedule::new(
+                &pool_state.reward_infos[0],
+                RewardScheduleKind::LinearDecay,
+                0,
+                0,
+            )
+            .unwrap();
+            pool_state.liquidity = 100;
+
+            // the emission decays linearly from 10 to 0 tokens per second over 86400 seconds
+            let updated_reward_infos = pool_state.update_reward_infos(1666026000).unwrap();
+            assert_eq!(
+                identity(updated_reward_infos[0].reward_total_emissioned),
+                324000
+            );
+            let reward_schedules = pool_state.reward_schedules;
+            assert_eq!(
+                get_emissions_per_second_x64(&updated_reward_infos, &reward_schedules, 1666026000)
+                    [0],
+                5 << fixed_point_64::RESOLUTION
+            );
+
+            let updated_reward_infos = pool_state.update_reward_infos(1666069300).unwrap();
+            assert_eq!(
+                identity(updated_reward_infos[0].reward_total_emissioned),
+                432000
+            );
+            assert_eq!(
+                identity(updated_reward_infos[0].reward_growth_global_x64),
+                4320 << fixed_point_64::RESOLUTION
+            );
+            assert_eq!(
+                get_emissions_per_second_x64(&updated_reward_infos, &reward_schedules, 1666069300)
+                    [0],
+                0
+            );
+        }
+    }
+
+    mod seconds_per_liquidity_test {
+        use super::*;
+        use crate::libraries::fixed_point_64;
+
+        #[test]
+        fn accumulate_with_liquidity_changes_test() {
+            let mut pool_state = PoolState::default();
+            // the accumulator starts with the first liquidity change
+            pool_state.update_seconds_per_liquidity(1000);
+            assert_eq!({ pool_state.seconds_per_liquidity_global_x64 }, 0);
+            pool_state.liquidity = 10;
+
+            pool_state.update_seconds_per_liquidity(1100);
+            assert_eq!(
+                { pool_state.seconds_per_liquidity_global_x64 },
+                (100u128 << fixed_point_64::RESOLUTION) / 10
+            );
+            // the liquidity changes between two observations
+            pool_state.liquidity = 40;
+
+            assert_eq!(
+                pool_state.seconds_per_liquidity_cumulative_x64(1180),
+                (100u128 << fixed_point_64::RESOLUTION) / 10
+                    + (80u128 << fixed_point_64::RESOLUTION) / 40
+            );
+            // reading the accumulator doesn't move it
+            assert_eq!({ pool_state.seconds_per_liquidity_update_time }, 1100);
+        }
+
+        #[test]
+        fn accumulate_without_liquidity_test() {
+            let mut pool_state = PoolState::default();
+            pool_state.update_seconds_per_liquidity(1000);
+            pool_state.update_seconds_per_liquidity(1001);
+            assert_eq!(
+                { pool_state.seconds_per_liquidity_global_x64 },
+                1u128 << fixed_point_64::RESOLUTION
+            );
+        }
     }
 
     mod use_tickarray_bitmap_extension_test {
@@ -1652,7 +2417,11 @@ pub mod pool_test {
             let swap_in_amount_token_1: u128 = 0x11223344556677008899aabbccddeeff;
             let swap_out_amount_token_0: u128 = 0x11223344556677880099aabbccddeeff;
             let status: u8 = 0x1b;
-            let padding: [u8; 7] = [0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18];
+            let reward_extension: u8 = 0x1c;
+            let reward_generations: [u8; REWARD_NUM] = [0x13, 0x14, 0x15];
+            let swap_allowlist_bump: u8 = 0x16;
+            let reward_extension_bump: u8 = 0x17;
+            let padding: [u8; 1] = [0x18];
             // RewardInfo
             let reward_state: u8 = 0x1c;
             let open_time: u64 = 0x123456789abc0def;
@@ -1723,20 +2492,54 @@ pub mod pool_test {
             let fund_fees_token_1: u64 = 0x1230456789abcdef;
             let pool_open_time: u64 = 0x1203456789abcdef;
             let recent_epoch: u64 = 0x1023456789abcdef;
-            let mut padding1: [u64; 24] = [0u64; 24];
-            let mut padding1_data = [0u8; 8 * 24];
+            let seconds_per_liquidity_global_x64: u128 = 0x11002233445566778899aabbccddeeff;
+            let seconds_per_liquidity_update_time: u64 = 0x0123456789abcdef;
+            let launch_end_time: u64 = 0x0123456789abcdef;
+            let volatility_reference_tick: i32 = 0x12345670;
+            let volatility_reference: u32 = 0x12345607;
+            let volatility_accumulator: u32 = 0x12345067;
+            let volatility_update_time: u32 = 0x12340567;
+            let fee_override_flag: u32 = 0x12304567;
+            let trade_fee_rate_override: u32 = 0x12034567;
+            let protocol_fee_rate_override: u32 = 0x10234567;
+            let fund_fee_rate_override: u32 = 0x01234567;
+            let pending_owner = Pubkey::new_unique();
+            // RewardSchedule
+            let schedule_kind: u8 = RewardScheduleKind::Step as u8;
+            let schedule_padding: [u8; 3] = [0x31, 0x32, 0x33];
+            let step_period: u32 = 0x12345678;
+            let end_emissions_per_second_x64: u128 = 0x123456789abcdef0fedcba9876543210;
+            let mut reward_schedule_data = [0u8; RewardSchedule::LEN];
             let mut offset = 0;
-            for i in 0..24 {
+            reward_schedule_data[offset..offset + 1].copy_from_slice(&schedule_kind.to_le_bytes());
+            offset += 1;
+            reward_schedule_data[offset..offset + 3].copy_from_slice(&schedule_padding);
+            offset += 3;
+            reward_schedule_data[offset..offset + 4].copy_from_slice(&step_period.to_le_bytes());
+            offset += 4;
+            reward_schedule_data[offset..offset + 16]
+                .copy_from_slice(&end_emissions_per_second_x64.to_le_bytes());
+            offset += 16;
+            assert_eq!(offset, RewardSchedule::LEN);
+            assert_eq!(RewardSchedule::LEN, core::mem::size_of::<RewardSchedule>());
+            let boosted_liquidity: u128 = 0x1234567890abcdeffedcba0987654321;
+            let min_lock_duration: u32 = 0x12345068;
+            let lock_reward_multiplier: u16 = 0x1235;
+            let padding5: u16 = 0x1325;
+            let mut padding1: [u64; 3] = [0u64; 3];
+            let mut padding1_data = [0u8; 8 * 3];
+            let mut offset = 0;
+            for i in 0..3 {
                 padding1[i] = u64::MAX - i as u64;
                 padding1_data[offset..offset + 8].copy_from_slice(&padding1[i].to_le_bytes());
                 offset += 8;
             }
-            let mut padding2: [u64; 32] = [0u64; 32];
-            let mut padding2_data = [0u8; 8 * 32];
+            let mut padding2: [u64; 29] = [0u64; 29];
+            let mut padding2_data = [0u8; 8 * 29];
             let mut offset = 0;
-            for i in 24..(24 + 32) {
-                padding2[i - 24] = u64::MAX - i as u64;
-                padding2_data[offset..offset + 8].copy_from_slice(&padding2[i - 24].to_le_bytes());
+            for i in 3..(3 + 29) {
+                padding2[i - 3] = u64::MAX - i as u64;
+                padding2_data[offset..offset + 8].copy_from_slice(&padding2[i - 3].to_le_bytes());
                 offset += 8;
             }
             // serialize original data
@@ -1794,8 +2597,16 @@ pub mod pool_test {
             offset += 16;
             pool_data[offset..offset + 1].copy_from_slice(&status.to_le_bytes());
             offset += 1;
-            pool_data[offset..offset + 7].copy_from_slice(&padding);
-            offset += 7;
+            pool_data[offset..offset + 1].copy_from_slice(&reward_extension.to_le_bytes());
+            offset += 1;
+            pool_data[offset..offset + REWARD_NUM].copy_from_slice(&reward_generations);
+            offset += REWARD_NUM;
+            pool_data[offset..offset + 1].copy_from_slice(&swap_allowlist_bump.to_le_bytes());
+            offset += 1;
+            pool_data[offset..offset + 1].copy_from_slice(&reward_extension_bump.to_le_bytes());
+            offset += 1;
+            pool_data[offset..offset + 1].copy_from_slice(&padding);
+            offset += 1;
             pool_data[offset..offset + RewardInfo::LEN * REWARD_NUM]
                 .copy_from_slice(&reward_info_datas);
             offset += RewardInfo::LEN * REWARD_NUM;
@@ -1819,10 +2630,50 @@ pub mod pool_test {
             offset += 8;
             pool_data[offset..offset + 8].copy_from_slice(&recent_epoch.to_le_bytes());
             offset += 8;
-            pool_data[offset..offset + 8 * 24].copy_from_slice(&padding1_data);
-            offset += 8 * 24;
-            pool_data[offset..offset + 8 * 32].copy_from_slice(&padding2_data);
-            offset += 8 * 32;
+            pool_data[offset..offset + 16]
+                .copy_from_slice(&seconds_per_liquidity_global_x64.to_le_bytes());
+            offset += 16;
+            pool_data[offset..offset + 8]
+                .copy_from_slice(&seconds_per_liquidity_update_time.to_le_bytes());
+            offset += 8;
+            pool_data[offset..offset + 8].copy_from_slice(&launch_end_time.to_le_bytes());
+            offset += 8;
+            pool_data[offset..offset + 4].copy_from_slice(&volatility_reference_tick.to_le_bytes());
+            offset += 4;
+            pool_data[offset..offset + 4].copy_from_slice(&volatility_reference.to_le_bytes());
+            offset += 4;
+            pool_data[offset..offset + 4].copy_from_slice(&volatility_accumulator.to_le_bytes());
+            offset += 4;
+            pool_data[offset..offset + 4].copy_from_slice(&volatility_update_time.to_le_bytes());
+            offset += 4;
+            pool_data[offset..offset + 4].copy_from_slice(&fee_override_flag.to_le_bytes());
+            offset += 4;
+            pool_data[offset..offset + 4].copy_from_slice(&trade_fee_rate_override.to_le_bytes());
+            offset += 4;
+            pool_data[offset..offset + 4]
+                .copy_from_slice(&protocol_fee_rate_override.to_le_bytes());
+            offset += 4;
+            pool_data[offset..offset + 4].copy_from_slice(&fund_fee_rate_override.to_le_bytes());
+            offset += 4;
+            pool_data[offset..offset + 32].copy_from_slice(&pending_owner.to_bytes());
+            offset += 32;
+            for _ in 0..REWARD_NUM {
+                pool_data[offset..offset + RewardSchedule::LEN]
+                    .copy_from_slice(&reward_schedule_data);
+                offset += RewardSchedule::LEN;
+            }
+            pool_data[offset..offset + 16].copy_from_slice(&boosted_liquidity.to_le_bytes());
+            offset += 16;
+            pool_data[offset..offset + 4].copy_from_slice(&min_lock_duration.to_le_bytes());
+            offset += 4;
+            pool_data[offset..offset + 2].copy_from_slice(&lock_reward_multiplier.to_le_bytes());
+            offset += 2;
+            pool_data[offset..offset + 2].copy_from_slice(&padding5.to_le_bytes());
+            offset += 2;
+            pool_data[offset..offset + 8 * 3].copy_from_slice(&padding1_data);
+            offset += 8 * 3;
+            pool_data[offset..offset + 8 * 29].copy_from_slice(&padding2_data);
+            offset += 8 * 29;
 
             // len check
             assert_eq!(offset, pool_data.len());
@@ -1883,6 +2734,14 @@ pub mod pool_test {
             assert_eq!(unpack_swap_out_amount_token_0, swap_out_amount_token_0);
             let unpack_status = unpack_data.status;
             assert_eq!(unpack_status, status);
+            let unpack_reward_extension = unpack_data.reward_extension;
+            assert_eq!(unpack_reward_extension, reward_extension);
+            let unpack_reward_generations = unpack_data.reward_generations;
+            assert_eq!(unpack_reward_generations, reward_generations);
+            let unpack_swap_allowlist_bump = unpack_data.swap_allowlist_bump;
+            assert_eq!(unpack_swap_allowlist_bump, swap_allowlist_bump);
+            let unpack_reward_extension_bump = unpack_data.reward_extension_bump;
+            assert_eq!(unpack_reward_extension_bump, reward_extension_bump);
             let unpack_padding = unpack_data.padding;
             assert_eq!(unpack_padding, padding);
 
@@ -1933,6 +2792,62 @@ pub mod pool_test {
             assert_eq!(unpack_open_time, pool_open_time);
             let unpack_recent_epoch = unpack_data.recent_epoch;
             assert_eq!(unpack_recent_epoch, recent_epoch);
+            let unpack_seconds_per_liquidity_global_x64 =
+                unpack_data.seconds_per_liquidity_global_x64;
+            assert_eq!(
+                unpack_seconds_per_liquidity_global_x64,
+                seconds_per_liquidity_global_x64
+            );
+            let unpack_seconds_per_liquidity_update_time =
+                unpack_data.seconds_per_liquidity_update_time;
+            assert_eq!(
+                unpack_seconds_per_liquidity_update_time,
+                seconds_per_liquidity_update_time
+            );
+            let unpack_launch_end_time = unpack_data.launch_end_time;
+            assert_eq!(unpack_launch_end_time, launch_end_time);
+            let unpack_volatility_reference_tick = unpack_data.volatility_reference_tick;
+            assert_eq!(unpack_volatility_reference_tick, volatility_reference_tick);
+            let unpack_volatility_reference = unpack_data.volatility_reference;
+            assert_eq!(unpack_volatility_reference, volatility_reference);
+            let unpack_volatility_accumulator = unpack_data.volatility_accumulator;
+            assert_eq!(unpack_volatility_accumulator, volatility_accumulator);
+            let unpack_volatility_update_time = unpack_data.volatility_update_time;
+            assert_eq!(unpack_volatility_update_time, volatility_update_time);
+            let unpack_fee_override_flag = unpack_data.fee_override_flag;
+            assert_eq!(unpack_fee_override_flag, fee_override_flag);
+            let unpack_trade_fee_rate_override = unpack_data.trade_fee_rate_override;
+            assert_eq!(unpack_trade_fee_rate_override, trade_fee_rate_override);
+            let unpack_protocol_fee_rate_override = unpack_data.protocol_fee_rate_override;
+            assert_eq!(
+                unpack_protocol_fee_rate_override,
+                protocol_fee_rate_override
+            );
+            let unpack_fund_fee_rate_override = unpack_data.fund_fee_rate_override;
+            assert_eq!(unpack_fund_fee_rate_override, fund_fee_rate_override);
+            let unpack_pending_owner = unpack_data.pending_owner;
+            assert_eq!(unpack_pending_owner, pending_owner);
+            for schedule in unpack_data.reward_schedules {
+                let unpack_schedule_kind = schedule.kind;
+                assert_eq!(unpack_schedule_kind, schedule_kind);
+                let unpack_schedule_padding = schedule.padding;
+                assert_eq!(unpack_schedule_padding, schedule_padding);
+                let unpack_step_period = schedule.step_period;
+                assert_eq!(unpack_step_period, step_period);
+                let unpack_end_emissions_per_second_x64 = schedule.end_emissions_per_second_x64;
+                assert_eq!(
+                    unpack_end_emissions_per_second_x64,
+                    end_emissions_per_second_x64
+                );
+            }
+            let unpack_boosted_liquidity = unpack_data.boosted_liquidity;
+            assert_eq!(unpack_boosted_liquidity, boosted_liquidity);
+            let unpack_min_lock_duration = unpack_data.min_lock_duration;
+            assert_eq!(unpack_min_lock_duration, min_lock_duration);
+            let unpack_lock_reward_multiplier = unpack_data.lock_reward_multiplier;
+            assert_eq!(unpack_lock_reward_multiplier, lock_reward_multiplier);
+            let unpack_padding5 = unpack_data.padding5;
+            assert_eq!(unpack_padding5, padding5);
             let unpack_padding1 = unpack_data.padding1;
             assert_eq!(unpack_padding1, padding1);
             let unpack_padding2 = unpack_data.padding2;
@@ -0,0 +1,343 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+use solana_program::program_option::COption;
+
+pub const POOL_REWARD_EXTENSION_SEED: &str = "pool_reward_extension";
+/// The number of reward streams of the reward extension, in addition to the `REWARD_NUM` ones of the pool,
+/// so a pool has at most five rewards. Every tick stores the growths outside of each extension reward
+/// and the tick arrays already take the whole 10KiB an account created by the program can have
+pub const EXTENSION_REWARD_NUM: usize = 2;
+
+/// Holds the reward streams of a pool beyond its `REWARD_NUM` reward infos,
+/// the growths of the ticks and positions are tracked next to the ones of the pool rewards
+///
+/// PDA of `[POOL_REWARD_EXTENSION_SEED, pool_id]`
+#[account(zero_copy(unsafe))]
+#[repr(C, packed)]
+#[derive(Default, Debug)]
+pub struct PoolRewardExtension {
+    pub pool_id: Pubkey,
+    pub reward_infos: [RewardInfo; EXTENSION_REWARD_NUM],
+    /// Incremented each time a reward slot is retired, like the pool reward generations
+    pub reward_generations: [u8; EXTENSION_REWARD_NUM],
+    pub padding0: [u8; 6],
+    /// The emission schedules of the reward infos, a constant emission by default
+    pub reward_schedules: [RewardSchedule; EXTENSION_REWARD_NUM],
+    // Unused bytes for future upgrades.
+    pub padding: [u64; 25],
+}
+
+impl PoolRewardExtension {
+    pub const LEN: usize = 8
+        + 32
+        + RewardInfo::LEN * EXTENSION_REWARD_NUM
+        + EXTENSION_REWARD_NUM
+        + 6
+        + RewardSchedule::LEN * EXTENSION_REWARD_NUM
+        + 8 * 25;
+
+    pub fn initialize(&mut self, pool_id: Pubkey, pool_owner: Pubkey) {
+        self.pool_id = pool_id;
+        self.reward_infos = [RewardInfo::new(pool_owner); EXTENSION_REWARD_NUM];
+        self.reward_generations = [0; EXTENSION_REWARD_NUM];
+        self.padding0 = [0; 6];
+        self.reward_schedules = [RewardSchedule::default(); EXTENSION_REWARD_NUM];
+        self.padding = [0; 25];
+    }
+
+    /// The reward extension address from the bump stored on the pool
+    pub fn key(pool_id: Pubkey, bump: u8) -> Result<Pubkey> {
+        Pubkey::create_program_address(
+            &[
+                POOL_REWARD_EXTENSION_SEED.as_bytes(),
+                pool_id.as_ref(),
+                &[bump],
+            ],
+            &crate::id(),
+        )
+        .map_err(|_| ErrorCode::MissingRewardExtensionAccount.into())
+    }
+
+    /// Accumulates the reward growths with the in range liquidity of the pool,
+    /// it must be called with the pool reward infos before the liquidity changes
+    pub fn update_reward_infos(
+        &mut self,
+        liquidity: u128,
+        curr_timestamp: u64,
+    ) -> Result<[RewardInfo; EXTENSION_REWARD_NUM]> {
+        let mut next_reward_infos = self.reward_infos;
+        let reward_schedules = self.reward_schedules;
+        update_reward_growths(
+            &mut next_reward_infos,
+            &reward_schedules,
+            liquidity,
+            curr_timestamp,
+        )?;
+        self.reward_infos = next_reward_infos;
+        Ok(next_reward_infos)
+    }
+
+    /// Initialize the lowest free reward of the extension, returns its index in the extension
+    pub fn initialize_reward(
+        &mut self,
+        pool_state: &PoolState,
+        open_time: u64,
+        end_time: u64,
+        reward_per_second_x64: u128,
+        token_mint: &Pubkey,
+        token_mint_freeze_authority: COption<Pubkey>,
+        token_vault: &Pubkey,
+        authority: &Pubkey,
+        operation_state: &OperationState,
+    ) -> Result<usize> {
+        let reward_infos = self.reward_infos;
+        let lowest_index = match reward_infos.iter().position(|r| !r.initialized()) {
+            Some(lowest_index) => lowest_index,
+            None => return err!(ErrorCode::FullRewardInfo),
+        };
+        // the mint can't be used by a reward of the pool or of the extension
+        let pool_reward_infos = pool_state.reward_infos;
+        require!(
+            !pool_reward_infos
+                .iter()
+                .chain(reward_infos.iter())
+                .any(|r| r.token_mint == *token_mint),
+            ErrorCode::RewardTokenAlreadyInUse
+        );
+        // the extension rewards can't have a freeze authority unless they are a pool token or whitelisted
+        if *token_mint != pool_state.token_mint_0
+            && *token_mint != pool_state.token_mint_1
+            && !operation_state.whitelist_mints.contains(token_mint)
+        {
+            require!(
+                token_mint_freeze_authority.is_none(),
+                ErrorCode::ExceptRewardMint
+            );
+        }
+
+        let reward_info = &mut self.reward_infos[lowest_index];
+        reward_info.last_update_time = open_time;
+        reward_info.open_time = open_time;
+        reward_info.end_time = end_time;
+        reward_info.emissions_per_second_x64 = reward_per_second_x64;
+        reward_info.token_mint = *token_mint;
+        reward_info.token_vault = *token_vault;
+        reward_info.authority = *authority;
+        Ok(lowest_index)
+    }
+
+    pub fn check_unclaimed_reward(&self, index: usize, reward_amount_owed: u64) -> Result<()> {
+        assert!(index < EXTENSION_REWARD_NUM);
+        let unclaimed_reward = self.reward_infos[index]
+            .reward_total_emissioned
+            .checked_sub(self.reward_infos[index].reward_claimed)
+            .unwrap();
+        require_gte!(unclaimed_reward, reward_amount_owed);
+        Ok(())
+    }
+
+    pub fn add_reward_clamed(&mut self, index: usize, amount: u64) -> Result<()> {
+        assert!(index < EXTENSION_REWARD_NUM);
+        self.reward_infos[index].reward_claimed = self.reward_infos[index]
+            .reward_claimed
+            .checked_add(amount)
+            .unwrap();
+        Ok(())
+    }
+
+    /// The reward generations of the extension of a pool, a pool without extension never retired one
+    pub fn generations_of(
+        reward_extension: Option<&PoolRewardExtension>,
+    ) -> [u8; EXTENSION_REWARD_NUM] {
+        match reward_extension {
+            Some(reward_extension) => reward_extension.reward_generations,
+            None => [0; EXTENSION_REWARD_NUM],
+        }
+    }
+
+    /// Retire the reward of the slot and move the slot to the next generation
+    pub fn retire_reward(&mut self, index: usize) {
+        assert!(index < EXTENSION_REWARD_NUM);
+        self.reward_infos[index].retire();
+        self.reward_schedules[index] = RewardSchedule::default();
+        self.reward_generations[index] = self.reward_generations[index].wrapping_add(1);
+    }
+}
+
+/// Emitted when the reward extension of a pool is created
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct CreateRewardExtensionEvent {
+    #[index]
+    pub pool_state: Pubkey,
+    pub reward_extension: Pubkey,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::libraries::fixed_point_64;
+
+    fn empty_operation_state() -> OperationState {
+        OperationState {
+            bump: 0,
+            operation_owners: [Pubkey::default(); OPERATION_SIZE_USIZE],
+            whitelist_mints: [Pubkey::default(); WHITE_MINT_SIZE_USIZE],
+            operation_owner_roles: [0; OPERATION_SIZE_USIZE],
+            padding: [0; 8],
+        }
+    }
+
+    #[test]
+    fn reward_extension_len_test() {
+        assert_eq!(
+            PoolRewardExtension::LEN,
+            core::mem::size_of::<PoolRewardExtension>() + 8
+        );
+    }
+
+    #[test]
+    fn initialize_reward_test() {
+        let pool_state = PoolState {
+            token_mint_0: Pubkey::new_unique(),
+            token_mint_1: Pubkey::new_unique(),
+            ..Default::default()
+        };
+        let operation_state = empty_operation_state();
+        let mut reward_extension = PoolRewardExtension::default();
+        let authority = Pubkey::new_unique();
+        for i in 0..EXTENSION_REWARD_NUM {
+            let index = reward_extension
+                .initialize_reward(
+                    &pool_state,
+                    100,
+                    200,
+                    fixed_point_64::Q64,
+                    &Pubkey::new_unique(),
+                    COption::None,
+                    &Pubkey::new_unique(),
+                    &authority,
+                    &operation_state,
+                )
+                .unwrap();
+            assert_eq!(index, i);
+        }
+        // every reward of the extension is initialized
+        let result = reward_extension.initialize_reward(
+            &pool_state,
+            100,
+            200,
+            fixed_point_64::Q64,
+            &Pubkey::new_unique(),
+            COption::None,
+            &Pubkey::new_unique(),
+            &authority,
+            &operation_state,
+        );
+        assert_eq!(result.unwrap_err(), ErrorCode::FullRewardInfo.into());
+    }
+
+    #[test]
+    fn initialize_reward_mint_check_test() {
+        let pool_reward_mint = Pubkey::new_unique();
+        let mut pool_state = PoolState {
+            token_mint_0: Pubkey::new_unique(),
+            token_mint_1: Pubkey::new_unique(),
+            ..Default::default()
+        };
+        pool_state.reward_infos[0].token_mint = pool_reward_mint;
+        let operation_state = empty_operation_state();
+        let mut reward_extension = PoolRewardExtension::default();
+        let authority = Pubkey::new_unique();
+
+        // the mint of a pool reward
+        let result = reward_extension.initialize_reward(
+            &pool_state,
+            100,
+            200,
+            fixed_point_64::Q64,
+            &pool_reward_mint,
+            COption::None,
+            &Pubkey::new_unique(),
+            &authority,
+            &operation_state,
+        );
+        assert_eq!(
+            result.unwrap_err(),
+            ErrorCode::RewardTokenAlreadyInUse.into()
+        );
+
+        // a mint with a freeze authority
+        let result = reward_extension.initialize_reward(
+            &pool_state,
+            100,
+            200,
+            fixed_point_64::Q64,
+            &Pubkey::new_unique(),
+            COption::Some(Pubkey::new_unique()),
+            &Pubkey::new_unique(),
+            &authority,
+            &operation_state,
+        );
+        assert_eq!(result.unwrap_err(), ErrorCode::ExceptRewardMint.into());
+
+        // the pool tokens can have a freeze authority
+        let token_mint_0 = pool_state.token_mint_0;
+        reward_extension
+            .initialize_reward(
+                &pool_state,
+                100,
+                200,
+                fixed_point_64::Q64,
+                &token_mint_0,
+                COption::Some(Pubkey::new_unique()),
+                &Pubkey::new_unique(),
+                &authority,
+                &operation_state,
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn update_reward_infos_test() {
+        let pool_state = PoolState::default();
+        let mut reward_extension = PoolRewardExtension::default();
+        reward_extension
+            .initialize_reward(
+                &pool_state,
+                100,
+                200,
+                fixed_point_64::Q64,
+                &Pubkey::new_unique(),
+                COption::None,
+                &Pubkey::new_unique(),
+                &Pubkey::new_unique(),
+                &empty_operation_state(),
+            )
+            .unwrap();
+
+        // not opened yet
+        let reward_infos = reward_extension.update_reward_infos(10, 50).unwrap();
+        let reward_growth_global_x64 = reward_infos[0].reward_growth_global_x64;
+        assert_eq!(reward_growth_global_x64, 0);
+
+        // one token per second shared by 10 liquidity
+        let reward_infos = reward_extension.update_reward_infos(10, 150).unwrap();
+        let reward_growth_global_x64 = reward_infos[0].reward_growth_global_x64;
+        assert_eq!(reward_growth_global_x64, 50 * fixed_point_64::Q64 / 10);
+        let reward_total_emissioned = reward_infos[0].reward_total_emissioned;
+        assert_eq!(reward_total_emissioned, 50);
+
+        // the emissions stop at the end time
+        let reward_infos = reward_extension.update_reward_infos(10, 300).unwrap();
+        let reward_total_emissioned = reward_infos[0].reward_total_emissioned;
+        assert_eq!(reward_total_emissioned, 100);
+        let reward_state = reward_infos[0].reward_state;
+        assert_eq!(reward_state, RewardState::Ended as u8);
+
+        // the uninitialized reward stays untouched
+        let last_update_time = reward_infos[1].last_update_time;
+        assert_eq!(last_update_time, 0);
+    }
+}
@@ -0,0 +1,105 @@
+use crate::libraries::{big_num::U256, full_math::MulDiv};
+use anchor_lang::prelude::*;
+
+pub const POSITION_LOCK_SEED: &str = "position_lock";
+/// The denominator of the reward multipliers, in basis points
+pub const LOCK_REWARD_MULTIPLIER_DENOMINATOR: u16 = 10_000;
+pub const MAX_LOCK_REWARD_MULTIPLIER: u16 = 50_000;
+
+/// The lock of a position, its liquidity can't be decreased until the lock is removed
+/// and it earns the rewards with its liquidity boosted by the reward multiplier of the lock
+///
+/// PDA of `[POSITION_LOCK_SEED, position_nft_mint]`
+#[account]
+#[derive(Default, Debug)]
+pub struct PositionLockState {
+    /// Bump to identify PDA
+    pub bump: [u8; 1],
+    /// Mint address of the tokenized position
+    pub nft_mint: Pubkey,
+    /// The position can be unlocked from this timestamp
+    pub lock_until: u64,
+    /// The extra liquidity the lock adds to the boosted liquidity of the pool
+    pub boosted_liquidity: u128,
+    /// The account that paid for the lock, receives its rent when the position is unlocked
+    pub rent_payer: Pubkey,
+    // Unused bytes for future upgrades.
+    pub padding: [u64; 4],
+}
+
+impl PositionLockState {
+    pub const LEN: usize = 8 + 1 + 32 + 8 + 16 + 32 + 8 * 4;
+
+    pub fn key(nft_mint: Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[POSITION_LOCK_SEED.as_bytes(), nft_mint.as_ref()],
+            &crate::id(),
+        )
+        .0
+    }
+}
+
+/// The liquidity boosted by the reward multiplier, rounded down
+pub fn boost_liquidity(liquidity: u128, lock_reward_multiplier: u16) -> u128 {
+    U256::from(liquidity)
+        .mul_div_floor(
+            U256::from(lock_reward_multiplier),
+            U256::from(LOCK_REWARD_MULTIPLIER_DENOMINATOR),
+        )
+        .unwrap()
+        .as_u128()
+}
+
+/// Emitted when a position is locked
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct LockPositionEvent {
+    /// The ID of the token of the locked position
+    #[index]
+    pub position_nft_mint: Pubkey,
+    pub pool_state: Pubkey,
+    pub lock_until: u64,
+    /// The reward multiplier of the lock in basis points
+    pub lock_reward_multiplier: u16,
+    /// The extra liquidity added to the boosted liquidity of the pool
+    pub boosted_liquidity: u128,
+}
+
+/// Emitted when a position is unlocked
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct UnlockPositionEvent {
+    /// The ID of the token of the unlocked position
+    #[index]
+    pub position_nft_mint: Pubkey,
+    pub pool_state: Pubkey,
+    /// The extra liquidity removed from the boosted liquidity of the pool
+    pub boosted_liquidity: u128,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn position_lock_len_test() {
+        assert_eq!(
+            PositionLockState::LEN,
+            PositionLockState::default().try_to_vec().unwrap().len() + 8
+        );
+    }
+
+    #[test]
+    fn boost_liquidity_test() {
+        assert_eq!(
+            boost_liquidity(1000, LOCK_REWARD_MULTIPLIER_DENOMINATOR),
+            1000
+        );
+        assert_eq!(boost_liquidity(1000, 15_000), 1500);
+        assert_eq!(boost_liquidity(999, 15_000), 1498);
+        assert_eq!(
+            boost_liquidity(u64::MAX as u128, MAX_LOCK_REWARD_MULTIPLIER),
+            u64::MAX as u128 * 5
+        );
+    }
+}
@@ -1,6 +1,7 @@
 use crate::libraries::tick_math;
 use crate::libraries::{big_num::U128, full_math::MulDiv};
 use crate::pool::REWARD_NUM;
+use crate::pool_reward_extension::EXTENSION_REWARD_NUM;
 use crate::util::get_recent_epoch;
 use crate::{
     error::ErrorCode,
@@ -46,12 +47,27 @@ pub struct ProtocolPositionState {
     pub reward_growth_inside: [u128; REWARD_NUM], // 24
     // account update recent epoch
     pub recent_epoch: u64,
+    /// The reward growth per unit of liquidity of the rewards of the pool reward extension
+    pub reward_growth_inside_extension: [u128; EXTENSION_REWARD_NUM],
     // Unused bytes for future upgrades.
-    pub padding: [u64; 7],
+    pub padding: [u64; 3],
 }
 
 impl ProtocolPositionState {
-    pub const LEN: usize = 8 + 1 + 32 + 4 + 4 + 16 + 16 + 16 + 8 + 8 + 16 * REWARD_NUM + 64;
+    pub const LEN: usize = 8
+        + 1
+        + 32
+        + 4
+        + 4
+        + 16
+        + 16
+        + 16
+        + 8
+        + 8
+        + 16 * REWARD_NUM
+        + 8
+        + 16 * EXTENSION_REWARD_NUM
+        + 8 * 3;
 
     pub fn update(
         &mut self,
@@ -61,6 +77,7 @@ impl ProtocolPositionState {
         fee_growth_inside_0_x64: u128,
         fee_growth_inside_1_x64: u128,
         reward_growths_inside: [u128; REWARD_NUM],
+        extension_reward_growths_inside: [u128; EXTENSION_REWARD_NUM],
     ) -> Result<()> {
         if self.liquidity == 0 && liquidity_delta == 0 {
             return Ok(());
@@ -100,13 +117,18 @@ impl ProtocolPositionState {
             "protocol position reward_growths_inside:{:?}",
             reward_growths_inside
         );
-        self.update_reward_growths_inside(reward_growths_inside);
+        self.update_reward_growths_inside(reward_growths_inside, extension_reward_growths_inside);
         self.recent_epoch = get_recent_epoch()?;
         Ok(())
     }
 
-    pub fn update_reward_growths_inside(&mut self, reward_growths_inside: [u128; REWARD_NUM]) {
+    pub fn update_reward_growths_inside(
+        &mut self,
+        reward_growths_inside: [u128; REWARD_NUM],
+        extension_reward_growths_inside: [u128; EXTENSION_REWARD_NUM],
+    ) {
         // just record, calculate reward owed in personal position
         self.reward_growth_inside = reward_growths_inside;
+        self.reward_growth_inside_extension = extension_reward_growths_inside;
     }
 }
@@ -0,0 +1,315 @@
+use crate::error::ErrorCode;
+use crate::libraries::{big_num::U256, fixed_point_64};
+use crate::states::pool::{reward_period_limit, RewardInfo};
+use anchor_lang::prelude::*;
+
+#[derive(Copy, Clone, AnchorSerialize, AnchorDeserialize, Debug, PartialEq, Eq)]
+/// Shape of the reward emission between open time and end time
+pub enum RewardScheduleKind {
+    /// Emit `emissions_per_second_x64` of the reward info for the whole period
+    Constant,
+    /// Decrease the emission linearly from `emissions_per_second_x64` of the reward info at open time
+    /// to `end_emissions_per_second_x64` at end time
+    LinearDecay,
+    /// Decrease the emission by equal steps every `step_period` seconds, from `emissions_per_second_x64`
+    /// of the reward info for the first step to `end_emissions_per_second_x64` for the last step
+    Step,
+}
+
+/// The emission schedule of a reward, stored next to its reward info
+#[zero_copy(unsafe)]
+#[repr(C, packed)]
+#[derive(Default, Debug, PartialEq, Eq)]
+pub struct RewardSchedule {
+    /// RewardScheduleKind of the schedule
+    pub kind: u8,
+    pub padding: [u8; 3],
+    /// Seconds of every step of a step schedule
+    pub step_period: u32,
+    /// Q64.64 number of the tokens per second emitted at the end of the schedule
+    pub end_emissions_per_second_x64: u128,
+}
+
+impl RewardSchedule {
+    pub const LEN: usize = 1 + 3 + 4 + 16;
+
+    /// Creates a schedule of the reward, the schedule can only decrease the emission so that the
+    /// reward funded for the constant emission always covers it
+    pub fn new(
+        reward_info: &RewardInfo,
+        kind: RewardScheduleKind,
+        step_period: u32,
+        end_emissions_per_second_x64: u128,
+    ) -> Result<Self> {
+        if kind == RewardScheduleKind::Constant {
+            return Ok(Self::default());
+        }
+        let reward_period = reward_info.end_time.saturating_sub(reward_info.open_time);
+        if reward_period == 0
+            || reward_period > reward_period_limit::MAX_REWARD_PERIOD
+            || end_emissions_per_second_x64 > reward_info.emissions_per_second_x64
+        {
+            return err!(ErrorCode::InvalidRewardSchedule);
+        }
+        if kind == RewardScheduleKind::Step
+            && (step_period == 0 || u64::from(step_period) >= reward_period)
+        {
+            return err!(ErrorCode::InvalidRewardSchedule);
+        }
+        Ok(Self {
+            kind: kind as u8,
+            padding: [0; 3],
+            step_period: if kind == RewardScheduleKind::Step {
+                step_period
+            } else {
+                0
+            },
+            end_emissions_per_second_x64,
+        })
+    }
+
+    pub fn is_constant(&self) -> bool {
+        self.kind == RewardScheduleKind::Constant as u8
+    }
+
+    /// Q64.64 number of the tokens emitted between the two timestamps of the reward period
+    pub fn emissions_between_x64(&self, reward_info: &RewardInfo, from: u64, to: u64) -> U256 {
+        if self.is_constant() {
+            return U256::from(to - from) * U256::from(reward_info.emissions_per_second_x64);
+        }
+        self.cumulative_emissions_x64(reward_info, to)
+            - self.cumulative_emissions_x64(reward_info, from)
+    }
+
+    /// Q64.64 number of the tokens emitted per second at the timestamp, zero out of the reward period
+    pub fn emissions_per_second_x64(&self, reward_info: &RewardInfo, timestamp: u64) -> u128 {
+        if !reward_info.initialized()
+            || timestamp < reward_info.open_time
+            || timestamp >= reward_info.end_time
+        {
+            return 0;
+        }
+        let start = U256::from(reward_info.emissions_per_second_x64);
+        let decrease = start - U256::from(self.end_emissions_per_second_x64);
+        let elapsed = U256::from(timestamp - reward_info.open_time);
+        let reward_period = U256::from(reward_info.end_time - reward_info.open_time);
+        let rate = if self.kind == RewardScheduleKind::LinearDecay as u8 {
+            start - decrease * elapsed / reward_period
+        } else if self.kind == RewardScheduleKind::Step as u8 {
+            let step_period = U256::from(self.step_period);
+            let last_step = (reward_period - 1) / step_period;
+            if last_step.is_zero() {
+                start
+            } else {
+                let step = std::cmp::min(elapsed / step_period, last_step);
+                start - decrease * step / last_step
+            }
+        } else {
+            start
+        };
+        rate.as_u128()
+    }
+
+    /// Q64.64 number of the tokens emitted from the open time to the timestamp, rounded down
+    fn cumulative_emissions_x64(&self, reward_info: &RewardInfo, timestamp: u64) -> U256 {
+        let timestamp = timestamp.clamp(reward_info.open_time, reward_info.end_time);
+        let elapsed = U256::from(timestamp - reward_info.open_time);
+        let reward_period = U256::from(reward_info.end_time - reward_info.open_time);
+        let start = U256::from(reward_info.emissions_per_second_x64);
+        let decrease = start - U256::from(self.end_emissions_per_second_x64);
+
+        if self.kind == RewardScheduleKind::LinearDecay as u8 {
+            // e0 * x - (e0 - e1) * x^2 / 2T
+            let double_period = reward_period * 2;
+            return (start * elapsed * double_period - decrease * elapsed * elapsed)
+                / double_period;
+        }
+        // the rate of step k is e0 - (e0 - e1) * k / K, K is the index of the last step
+        let step_period = U256::from(self.step_period);
+        let last_step = (reward_period - 1) / step_period;
+        if last_step.is_zero() {
+            return start * elapsed;
+        }
+        let double_last_step = last_step * 2;
+        let full_steps = elapsed / step_period;
+        let mut emissions = double_last_step * step_period * full_steps * start;
+        if !full_steps.is_zero() {
+            emissions = emissions - step_period * decrease * full_steps * (full_steps - 1);
+        }
+        let partial = elapsed - full_steps * step_period;
+        if !partial.is_zero() {
+            emissions =
+                emissions + (double_last_step * start - decrease * full_steps * 2) * partial;
+        }
+        emissions / double_last_step
+    }
+}
+
+/// Q64.64 numbers of the tokens emitted per second of the rewards at the timestamp
+pub fn get_emissions_per_second_x64<const N: usize>(
+    reward_infos: &[RewardInfo; N],
+    reward_schedules: &[RewardSchedule; N],
+    timestamp: u64,
+) -> [u128; N] {
+    let mut emissions = [0; N];
+    for (i, emission) in emissions.iter_mut().enumerate() {
+        *emission = reward_schedules[i].emissions_per_second_x64(&reward_infos[i], timestamp);
+    }
+    emissions
+}
+
+/// Ceils the Q64.64 emission to the amount of tokens
+pub fn emissions_amount(emissions_x64: U256) -> u64 {
+    let q64 = U256::from(fixed_point_64::Q64);
+    ((emissions_x64 + q64 - 1) / q64).as_u64()
+}
+
+#[cfg(test)]
+mod reward_schedule_test {
+    use super::*;
+    use crate::states::pool::RewardState;
+
+    fn reward_info(open_time: u64, end_time: u64, emissions_per_second: u128) -> RewardInfo {
+        RewardInfo {
+            reward_state: RewardState::Initialized as u8,
+            token_mint: Pubkey::new_unique(),
+            open_time,
+            end_time,
+            last_update_time: open_time,
+            emissions_per_second_x64: emissions_per_second << fixed_point_64::RESOLUTION,
+            ..Default::default()
+        }
+    }
+
+    fn tokens(emissions_x64: U256) -> u128 {
+        (emissions_x64 >> fixed_point_64::RESOLUTION).as_u128()
+    }
+
+    #[test]
+    fn constant_schedule_test() {
+        let reward_info = reward_info(1000, 2000, 10);
+        let schedule = RewardSchedule::default();
+        assert_eq!(
+            tokens(schedule.emissions_between_x64(&reward_info, 1000, 1500)),
+            5000
+        );
+        assert_eq!(
+            schedule.emissions_per_second_x64(&reward_info, 1999),
+            10 << fixed_point_64::RESOLUTION
+        );
+        assert_eq!(schedule.emissions_per_second_x64(&reward_info, 2000), 0);
+    }
+
+    #[test]
+    fn linear_decay_schedule_test() {
+        let reward_info = reward_info(1000, 2000, 10);
+        let schedule =
+            RewardSchedule::new(&reward_info, RewardScheduleKind::LinearDecay, 0, 0).unwrap();
+        // half of the constant emission for the whole period
+        assert_eq!(
+            tokens(schedule.emissions_between_x64(&reward_info, 1000, 2000)),
+            5000
+        );
+        assert_eq!(
+            tokens(schedule.emissions_between_x64(&reward_info, 1000, 1500)),
+            3750
+        );
+        assert_eq!(
+            tokens(schedule.emissions_between_x64(&reward_info, 1500, 3000)),
+            1250
+        );
+        assert_eq!(
+            schedule.emissions_per_second_x64(&reward_info, 1500),
+            5 << fixed_point_64::RESOLUTION
+        );
+    }
+
+    #[test]
+    fn step_schedule_test() {
+        let reward_info = reward_info(1000, 2000, 10);
+        // 4 steps emitting 10, 8, 6, 4 tokens per second
+        let schedule = RewardSchedule::new(
+            &reward_info,
+            RewardScheduleKind::Step,
+            250,
+            4 << fixed_point_64::RESOLUTION,
+        )
+        .unwrap();
+        assert_eq!(
+            tokens(schedule.emissions_between_x64(&reward_info, 1000, 2000)),
+            7000
+        );
+        assert_eq!(
+            tokens(schedule.emissions_between_x64(&reward_info, 1000, 1250)),
+            2500
+        );
+        assert_eq!(
+            tokens(schedule.emissions_between_x64(&reward_info, 1100, 1600)),
+            1500 + 2000 + 600
+        );
+        assert_eq!(
+            schedule.emissions_per_second_x64(&reward_info, 1750),
+            4 << fixed_point_64::RESOLUTION
+        );
+    }
+
+    #[test]
+    fn step_schedule_with_partial_last_step_test() {
+        let reward_info = reward_info(1000, 2000, 10);
+        // the last step only lasts 100 seconds: 10, 7, 4, 1 tokens per second
+        let schedule = RewardSchedule::new(
+            &reward_info,
+            RewardScheduleKind::Step,
+            300,
+            1 << fixed_point_64::RESOLUTION,
+        )
+        .unwrap();
+        assert_eq!(
+            tokens(schedule.emissions_between_x64(&reward_info, 1000, 2000)),
+            3000 + 2100 + 1200 + 100
+        );
+        assert_eq!(
+            schedule.emissions_per_second_x64(&reward_info, 1950),
+            1 << fixed_point_64::RESOLUTION
+        );
+    }
+
+    #[test]
+    fn schedule_integrates_across_gaps_test() {
+        let reward_info = reward_info(1000, 2000, 1_000_000);
+        for schedule in [
+            RewardSchedule::new(
+                &reward_info,
+                RewardScheduleKind::LinearDecay,
+                0,
+                1 << fixed_point_64::RESOLUTION,
+            )
+            .unwrap(),
+            RewardSchedule::new(&reward_info, RewardScheduleKind::Step, 70, 3).unwrap(),
+        ] {
+            let total = schedule.emissions_between_x64(&reward_info, 1000, 2000);
+            let mut sum = U256::default();
+            let mut last = 900;
+            for timestamp in [1000, 1001, 1069, 1070, 1071, 1333, 1999, 2000, 2500] {
+                sum = sum + schedule.emissions_between_x64(&reward_info, last, timestamp);
+                last = timestamp;
+            }
+            assert_eq!(sum, total);
+        }
+    }
+
+    #[test]
+    fn invalid_schedule_test() {
+        let reward_info = reward_info(1000, 2000, 10);
+        // the schedule can't increase the emission
+        assert!(RewardSchedule::new(
+            &reward_info,
+            RewardScheduleKind::LinearDecay,
+            0,
+            11 << fixed_point_64::RESOLUTION
+        )
+        .is_err());
+        assert!(RewardSchedule::new(&reward_info, RewardScheduleKind::Step, 0, 0).is_err());
+        assert!(RewardSchedule::new(&reward_info, RewardScheduleKind::Step, 1000, 0).is_err());
+    }
+}
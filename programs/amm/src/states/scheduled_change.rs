@@ -0,0 +1,136 @@
+use crate::states::AmmConfigUpdate;
+use anchor_lang::prelude::*;
+
+pub const SCHEDULED_CHANGE_SEED: &str = "scheduled_change";
+/// The seconds between scheduling an admin change and the earliest time it can be executed
+#[cfg(feature = "devnet")]
+pub const ADMIN_CHANGE_DELAY: u64 = 60;
+#[cfg(not(feature = "devnet"))]
+pub const ADMIN_CHANGE_DELAY: u64 = 2 * 24 * 3600;
+
+/// An admin change which only applies after the admin change delay
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AdminChange {
+    /// A fee rate change of the target amm config, status changes are scheduled as `EnableAmmConfigStatus`
+    AmmConfig(AmmConfigUpdate),
+    /// The `PoolStatusBitIndex` bits to enable on the target pool
+    EnablePoolStatus(u8),
+    /// The `PoolStatusBitIndex` bits to enable on the target amm config
+    EnableAmmConfigStatus(u8),
+}
+
+impl AdminChange {
+    /// The space reserved for the largest change
+    pub const MAX_LEN: usize = 64;
+
+    /// Only the scheduled bits are enabled, the bits paused after the change was scheduled stay disabled
+    pub fn enable_status_bits(status: u8, enable_bits: u8) -> u8 {
+        status & !enable_bits
+    }
+}
+
+/// Holds an admin change until it is executed or cancelled
+#[account]
+#[derive(Debug)]
+pub struct ScheduledChangeState {
+    /// Bump to identify PDA
+    pub bump: u8,
+    /// The admin who scheduled the change, receives the rent back
+    pub authority: Pubkey,
+    /// The amm config or pool the change applies to
+    pub target: Pubkey,
+    /// Distinguishes the changes scheduled for the same target
+    pub nonce: u64,
+    /// The timestamp from which anyone can execute the change
+    pub effective_at: u64,
+    pub change: AdminChange,
+}
+
+impl ScheduledChangeState {
+    pub const LEN: usize = 8 + 1 + 32 + 32 + 8 + 8 + AdminChange::MAX_LEN;
+}
+
+/// Emitted when an admin change is scheduled
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct AdminChangeScheduledEvent {
+    #[index]
+    pub scheduled_change: Pubkey,
+    #[index]
+    pub target: Pubkey,
+    pub change: AdminChange,
+    pub effective_at: u64,
+}
+
+/// Emitted when a scheduled admin change is applied
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct AdminChangeExecutedEvent {
+    #[index]
+    pub scheduled_change: Pubkey,
+    #[index]
+    pub target: Pubkey,
+    pub change: AdminChange,
+}
+
+/// Emitted when a scheduled admin change is dropped by the admin
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct AdminChangeCancelledEvent {
+    #[index]
+    pub scheduled_change: Pubkey,
+    #[index]
+    pub target: Pubkey,
+    pub change: AdminChange,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::states::{AmmConfig, PoolState, PoolStatusBitIndex};
+
+    #[test]
+    fn admin_change_max_len_test() {
+        let changes = [
+            AdminChange::AmmConfig(AmmConfigUpdate::Owner(Pubkey::new_unique())),
+            AdminChange::EnablePoolStatus(u8::MAX),
+            AdminChange::EnableAmmConfigStatus(u8::MAX),
+        ];
+        for change in changes {
+            assert!(change.try_to_vec().unwrap().len() <= AdminChange::MAX_LEN);
+        }
+    }
+
+    #[test]
+    fn pause_after_schedule_then_execute_test() {
+        let swap = 1 << PoolStatusBitIndex::Swap as u8;
+        let deposit = 1 << PoolStatusBitIndex::OpenPositionOrIncreaseLiquidity as u8;
+        let withdraw = 1 << PoolStatusBitIndex::DecreaseLiquidity as u8;
+
+        // the admin schedules enabling swaps while swaps and deposits are paused
+        let mut pool_state = PoolState::default();
+        pool_state.set_status(swap | deposit);
+        let enable_bits = swap;
+        // the guardian pauses withdrawals before the change is executed
+        pool_state.set_status(pool_state.status | withdraw);
+        pool_state.set_status(AdminChange::enable_status_bits(
+            pool_state.status,
+            enable_bits,
+        ));
+        assert_eq!(pool_state.status, deposit | withdraw);
+
+        let mut amm_config = AmmConfig::default();
+        amm_config
+            .apply_update(AmmConfigUpdate::Status(swap))
+            .unwrap();
+        amm_config
+            .apply_update(AmmConfigUpdate::Status(amm_config.status | withdraw))
+            .unwrap();
+        let status = AdminChange::enable_status_bits(amm_config.status, swap);
+        assert!(amm_config.is_status_enabling(AmmConfigUpdate::Status(status)));
+        amm_config
+            .apply_update(AmmConfigUpdate::Status(status))
+            .unwrap();
+        assert_eq!(amm_config.status, withdraw);
+    }
+}
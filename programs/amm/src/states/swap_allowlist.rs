@@ -0,0 +1,149 @@
+use crate::error::ErrorCode;
+use anchor_lang::prelude::*;
+use std::collections::HashSet;
+
+pub const SWAP_ALLOWLIST_SEED: &str = "swap_allowlist";
+pub const SWAP_ALLOWLIST_SIZE_USIZE: usize = 100;
+/// The longest launch phase a pool can have, one week
+pub const MAX_LAUNCH_DURATION: u64 = 7 * 24 * 3600;
+/// The furthest in the future the open time of a new pool can be, thirty days
+pub const MAX_LAUNCH_DELAY: u64 = 30 * 24 * 3600;
+
+/// A change of the addresses allowed to swap during the launch phase
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub enum SwapAllowlistUpdate {
+    /// Add the addresses to the allowlist
+    AddSwappers(Vec<Pubkey>),
+    /// Remove the addresses from the allowlist
+    RemoveSwappers(Vec<Pubkey>),
+}
+
+/// Holds the addresses allowed to swap in a pool during its launch phase
+#[account(zero_copy(unsafe))]
+#[repr(C, packed)]
+#[derive(Debug)]
+pub struct SwapAllowlistState {
+    /// Bump to identify PDA
+    pub bump: u8,
+    /// The pool the allowlist belongs to
+    pub pool_id: Pubkey,
+    /// The addresses allowed to swap before the launch phase ends
+    pub swappers: [Pubkey; SWAP_ALLOWLIST_SIZE_USIZE],
+}
+
+impl SwapAllowlistState {
+    pub const LEN: usize = 8 + 1 + 32 + 32 * SWAP_ALLOWLIST_SIZE_USIZE;
+
+    /// The allowlist address from the bump stored on the pool, cheaper than searching for the bump
+    pub fn key(pool_id: Pubkey, bump: u8) -> Result<Pubkey> {
+        Pubkey::create_program_address(
+            &[SWAP_ALLOWLIST_SEED.as_bytes(), pool_id.as_ref(), &[bump]],
+            &crate::id(),
+        )
+        .map_err(|_| ErrorCode::NotInSwapAllowlist.into())
+    }
+
+    pub fn initialize(&mut self, bump: u8, pool_id: Pubkey) {
+        self.bump = bump;
+        self.pool_id = pool_id;
+        self.swappers = [Pubkey::default(); SWAP_ALLOWLIST_SIZE_USIZE];
+    }
+
+    pub fn validate_swapper(&self, swapper: Pubkey) -> bool {
+        swapper != Pubkey::default() && self.swappers.contains(&swapper)
+    }
+
+    pub fn update_swappers(&mut self, keys: Vec<Pubkey>) -> Result<()> {
+        let mut swappers = self.swappers.to_vec();
+        swappers.extend(keys.as_slice().iter());
+        swappers.retain(|&item| item != Pubkey::default());
+        let swappers_set: HashSet<Pubkey> = HashSet::from_iter(swappers.iter().cloned());
+        let mut updated_swappers: Vec<Pubkey> = swappers_set.into_iter().collect();
+        require_gte!(
+            SWAP_ALLOWLIST_SIZE_USIZE,
+            updated_swappers.len(),
+            ErrorCode::SwapAllowlistFull
+        );
+        updated_swappers.sort_by(|a, b| a.cmp(b));
+        // clear
+        self.swappers = [Pubkey::default(); SWAP_ALLOWLIST_SIZE_USIZE];
+        // update
+        self.swappers[0..updated_swappers.len()].copy_from_slice(updated_swappers.as_slice());
+        Ok(())
+    }
+
+    pub fn remove_swappers(&mut self, keys: Vec<Pubkey>) {
+        let mut swappers = self.swappers.to_vec();
+        // remove keys from swappers
+        swappers.retain(|x| *x != Pubkey::default() && !keys.contains(&x));
+        // clear
+        self.swappers = [Pubkey::default(); SWAP_ALLOWLIST_SIZE_USIZE];
+        // update
+        self.swappers[0..swappers.len()].copy_from_slice(swappers.as_slice());
+    }
+
+    /// Check the swapper is on the allowlist of the pool, the allowlist account is searched in the remaining accounts of the swap
+    pub fn check_swapper<'info>(
+        swap_allowlist_key: Pubkey,
+        swapper: Pubkey,
+        remaining_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        let account_info = remaining_accounts
+            .iter()
+            .find(|account_info| account_info.key() == swap_allowlist_key)
+            .ok_or(ErrorCode::NotInSwapAllowlist)?;
+        let swap_allowlist = AccountLoader::<SwapAllowlistState>::try_from(account_info)?;
+        require!(
+            swap_allowlist.load()?.validate_swapper(swapper),
+            ErrorCode::NotInSwapAllowlist
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn new_swap_allowlist() -> SwapAllowlistState {
+        SwapAllowlistState {
+            bump: 0,
+            pool_id: Pubkey::new_unique(),
+            swappers: [Pubkey::default(); SWAP_ALLOWLIST_SIZE_USIZE],
+        }
+    }
+
+    #[test]
+    fn update_and_remove_swappers_test() {
+        let mut swap_allowlist = new_swap_allowlist();
+        let mut keys = vec![
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        ];
+        swap_allowlist.update_swappers(keys.clone()).unwrap();
+        // repeated keys are only stored once
+        swap_allowlist.update_swappers(vec![keys[0]]).unwrap();
+        keys.sort_by(|a, b| a.cmp(b));
+        assert_eq!(&keys[..], &swap_allowlist.swappers[..keys.len()]);
+        assert_eq!(swap_allowlist.swappers[keys.len()], Pubkey::default());
+
+        swap_allowlist.remove_swappers(vec![keys[1]]);
+        assert!(swap_allowlist.validate_swapper(keys[0]));
+        assert!(!swap_allowlist.validate_swapper(keys[1]));
+        assert!(swap_allowlist.validate_swapper(keys[2]));
+        assert!(!swap_allowlist.validate_swapper(Pubkey::default()));
+    }
+
+    #[test]
+    fn update_swappers_overflow_test() {
+        let mut swap_allowlist = new_swap_allowlist();
+        let keys: Vec<Pubkey> = (0..SWAP_ALLOWLIST_SIZE_USIZE)
+            .map(|_| Pubkey::new_unique())
+            .collect();
+        swap_allowlist.update_swappers(keys).unwrap();
+        assert!(swap_allowlist
+            .update_swappers(vec![Pubkey::new_unique()])
+            .is_err());
+    }
+}
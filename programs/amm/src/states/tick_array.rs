@@ -2,6 +2,7 @@ use super::pool::PoolState;
 use crate::error::ErrorCode;
 use crate::libraries::{liquidity_math, tick_math};
 use crate::pool::{RewardInfo, REWARD_NUM};
+use crate::pool_reward_extension::EXTENSION_REWARD_NUM;
 use crate::util::*;
 use crate::Result;
 use anchor_lang::{prelude::*, system_program};
@@ -308,12 +309,19 @@ pub struct TickState {
 
     // Reward growth per unit of liquidity like fee, array of Q64.64
     pub reward_growths_outside_x64: [u128; REWARD_NUM],
-    // Unused bytes for future upgrades.
-    pub padding: [u32; 13],
+    /// The seconds spent on the _other_ side of this tick (relative to the current tick)
+    /// only has relative meaning, not absolute — the value depends on when the tick is initialized
+    pub seconds_outside: u32,
+    // Reward growth per unit of liquidity of the rewards of the pool reward extension, array of Q64.64
+    pub reward_growths_outside_extension_x64: [u128; EXTENSION_REWARD_NUM],
+    /// Amount of net boosted liquidity of the locked positions added (subtracted) when tick is crossed
+    /// from left to right (right to left)
+    pub boosted_liquidity_net: i128,
 }
 
 impl TickState {
-    pub const LEN: usize = 4 + 16 + 16 + 16 + 16 + 16 * REWARD_NUM + 16 + 16 + 8 + 8 + 4;
+    pub const LEN: usize =
+        4 + 16 + 16 + 16 + 16 + 16 * REWARD_NUM + 4 + 16 * EXTENSION_REWARD_NUM + 16;
 
     pub fn initialize(&mut self, tick: i32, tick_spacing: u16) -> Result<()> {
         if TickState::check_is_out_of_boundary(tick) {
@@ -335,6 +343,8 @@ impl TickState {
         fee_growth_global_1_x64: u128,
         upper: bool,
         reward_infos: &[RewardInfo; REWARD_NUM],
+        extension_reward_infos: &[RewardInfo; EXTENSION_REWARD_NUM],
+        block_timestamp: u32,
     ) -> Result<bool> {
         let liquidity_gross_before = self.liquidity_gross;
         let liquidity_gross_after =
@@ -349,6 +359,9 @@ impl TickState {
                 self.fee_growth_outside_0_x64 = fee_growth_global_0_x64;
                 self.fee_growth_outside_1_x64 = fee_growth_global_1_x64;
                 self.reward_growths_outside_x64 = RewardInfo::get_reward_growths(reward_infos);
+                self.reward_growths_outside_extension_x64 =
+                    RewardInfo::get_reward_growths(extension_reward_infos);
+                self.seconds_outside = block_timestamp;
             }
         }
 
@@ -372,6 +385,8 @@ impl TickState {
         fee_growth_global_0_x64: u128,
         fee_growth_global_1_x64: u128,
         reward_infos: &[RewardInfo; REWARD_NUM],
+        extension_reward_infos: &[RewardInfo; EXTENSION_REWARD_NUM],
+        block_timestamp: u32,
     ) -> i128 {
         self.fee_growth_outside_0_x64 = fee_growth_global_0_x64
             .checked_sub(self.fee_growth_outside_0_x64)
@@ -380,16 +395,13 @@ impl TickState {
             .checked_sub(self.fee_growth_outside_1_x64)
             .unwrap();
 
-        for i in 0..REWARD_NUM {
-            if !reward_infos[i].initialized() {
-                continue;
-            }
-
-            self.reward_growths_outside_x64[i] = reward_infos[i]
-                .reward_growth_global_x64
-                .checked_sub(self.reward_growths_outside_x64[i])
-                .unwrap();
-        }
+        self.reward_growths_outside_x64 =
+            cross_reward_growths_outside(self.reward_growths_outside_x64, reward_infos);
+        self.reward_growths_outside_extension_x64 = cross_reward_growths_outside(
+            self.reward_growths_outside_extension_x64,
+            extension_reward_infos,
+        );
+        self.seconds_outside = block_timestamp.wrapping_sub(self.seconds_outside);
 
         self.liquidity_net
     }
@@ -400,6 +412,9 @@ impl TickState {
         self.fee_growth_outside_0_x64 = 0;
         self.fee_growth_outside_1_x64 = 0;
         self.reward_growths_outside_x64 = [0; REWARD_NUM];
+        self.reward_growths_outside_extension_x64 = [0; EXTENSION_REWARD_NUM];
+        self.seconds_outside = 0;
+        self.boosted_liquidity_net = 0;
     }
 
     pub fn is_initialized(self) -> bool {
@@ -473,28 +488,65 @@ pub fn get_reward_growths_inside(
     tick_current_index: i32,
     reward_infos: &[RewardInfo; REWARD_NUM],
 ) -> [u128; REWARD_NUM] {
-    let mut reward_growths_inside = [0; REWARD_NUM];
+    reward_growths_inside(
+        (tick_lower.tick, tick_lower.reward_growths_outside_x64),
+        (tick_upper.tick, tick_upper.reward_growths_outside_x64),
+        tick_current_index,
+        reward_infos,
+    )
+}
 
-    for i in 0..REWARD_NUM {
-        if !reward_infos[i].initialized() {
+/// Calculates the reward growths inside of tick_lower and tick_upper of the rewards of the pool reward extension
+pub fn get_extension_reward_growths_inside(
+    tick_lower: &TickState,
+    tick_upper: &TickState,
+    tick_current_index: i32,
+    extension_reward_infos: &[RewardInfo; EXTENSION_REWARD_NUM],
+) -> [u128; EXTENSION_REWARD_NUM] {
+    reward_growths_inside(
+        (
+            tick_lower.tick,
+            tick_lower.reward_growths_outside_extension_x64,
+        ),
+        (
+            tick_upper.tick,
+            tick_upper.reward_growths_outside_extension_x64,
+        ),
+        tick_current_index,
+        extension_reward_infos,
+    )
+}
+
+/// `reward_growth_inside = reward_growth_global - reward_growth_below(lower) - reward_growth_above(upper)`,
+/// the ticks are given with their reward growths outside
+fn reward_growths_inside<const N: usize>(
+    (tick_lower, reward_growths_outside_lower): (i32, [u128; N]),
+    (tick_upper, reward_growths_outside_upper): (i32, [u128; N]),
+    tick_current_index: i32,
+    reward_infos: &[RewardInfo; N],
+) -> [u128; N] {
+    let mut reward_growths_inside = [0; N];
+
+    for i in 0..N {
+        if !reward_infos[i].has_growth() {
             continue;
         }
 
-        let reward_growths_below = if tick_current_index >= tick_lower.tick {
-            tick_lower.reward_growths_outside_x64[i]
+        let reward_growths_below = if tick_current_index >= tick_lower {
+            reward_growths_outside_lower[i]
         } else {
             reward_infos[i]
                 .reward_growth_global_x64
-                .checked_sub(tick_lower.reward_growths_outside_x64[i])
+                .checked_sub(reward_growths_outside_lower[i])
                 .unwrap()
         };
 
-        let reward_growths_above = if tick_current_index < tick_upper.tick {
-            tick_upper.reward_growths_outside_x64[i]
+        let reward_growths_above = if tick_current_index < tick_upper {
+            reward_growths_outside_upper[i]
         } else {
             reward_infos[i]
                 .reward_growth_global_x64
-                .checked_sub(tick_upper.reward_growths_outside_x64[i])
+                .checked_sub(reward_growths_outside_upper[i])
                 .unwrap()
         };
         reward_growths_inside[i] = reward_infos[i]
@@ -515,6 +567,50 @@ pub fn get_reward_growths_inside(
     reward_growths_inside
 }
 
+/// Flips the reward growths outside of a crossed tick to the other side
+fn cross_reward_growths_outside<const N: usize>(
+    reward_growths_outside_x64: [u128; N],
+    reward_infos: &[RewardInfo; N],
+) -> [u128; N] {
+    let mut next_reward_growths_outside_x64 = reward_growths_outside_x64;
+    for i in 0..N {
+        if !reward_infos[i].has_growth() {
+            continue;
+        }
+
+        next_reward_growths_outside_x64[i] = reward_infos[i]
+            .reward_growth_global_x64
+            .checked_sub(reward_growths_outside_x64[i])
+            .unwrap();
+    }
+    next_reward_growths_outside_x64
+}
+
+/// Calculates the seconds spent inside of tick_lower and tick_upper, only has relative meaning,
+/// the difference of two snapshots is the time the range was active in between.
+/// `seconds_inside = block_timestamp - seconds_below(lower) - seconds_above(upper)`
+///
+pub fn get_seconds_inside(
+    tick_lower: &TickState,
+    tick_upper: &TickState,
+    tick_current: i32,
+    block_timestamp: u32,
+) -> u32 {
+    let seconds_below = if tick_current >= tick_lower.tick {
+        tick_lower.seconds_outside
+    } else {
+        block_timestamp.wrapping_sub(tick_lower.seconds_outside)
+    };
+    let seconds_above = if tick_current < tick_upper.tick {
+        tick_upper.seconds_outside
+    } else {
+        block_timestamp.wrapping_sub(tick_upper.seconds_outside)
+    };
+    block_timestamp
+        .wrapping_sub(seconds_below)
+        .wrapping_sub(seconds_above)
+}
+
 pub fn check_tick_array_start_index(
     tick_array_start_index: i32,
     tick_index: i32,
@@ -850,12 +946,16 @@ pub mod tick_array_test {
                     fee_growth_global_0_x64,
                     fee_growth_global_1_x64,
                     &[RewardInfo::default(); 3],
+                    &[RewardInfo::default(); EXTENSION_REWARD_NUM],
+                    0,
                 );
             } else {
                 tick_upper.cross(
                     fee_growth_global_0_x64,
                     fee_growth_global_1_x64,
                     &[RewardInfo::default(); 3],
+                    &[RewardInfo::default(); EXTENSION_REWARD_NUM],
+                    0,
                 );
             }
 
@@ -1186,9 +1286,21 @@ pub mod tick_array_test {
 
             reward_growth_global_x64 = reward_growth_global_x64 + reward_growth_global_delta;
             if cross_tick_lower {
-                tick_lower.cross(0, 0, &build_reward_infos(reward_growth_global_x64));
+                tick_lower.cross(
+                    0,
+                    0,
+                    &build_reward_infos(reward_growth_global_x64),
+                    &[RewardInfo::default(); EXTENSION_REWARD_NUM],
+                    0,
+                );
             } else {
-                tick_upper.cross(0, 0, &build_reward_infos(reward_growth_global_x64));
+                tick_upper.cross(
+                    0,
+                    0,
+                    &build_reward_infos(reward_growth_global_x64),
+                    &[RewardInfo::default(); EXTENSION_REWARD_NUM],
+                    0,
+                );
             }
 
             tick_current = target_tick_current;
@@ -1334,6 +1446,214 @@ pub mod tick_array_test {
             assert_eq!(reward_frowth_inside_delta, 500);
         }
     }
+    mod get_extension_reward_growths_inside_test {
+        use super::*;
+
+        #[test]
+        fn extension_reward_growths_apart_from_pool_rewards_test() {
+            let mut reward_infos = [RewardInfo::default(); 3];
+            reward_infos[0].token_mint = Pubkey::new_unique();
+            reward_infos[0].reward_growth_global_x64 = 1000;
+            let mut extension_reward_infos = [RewardInfo::default(); EXTENSION_REWARD_NUM];
+            extension_reward_infos[0].token_mint = Pubkey::new_unique();
+            extension_reward_infos[0].reward_growth_global_x64 = 300;
+
+            let mut tick_lower = TickState {
+                tick: -10,
+                ..Default::default()
+            };
+            let mut tick_upper = TickState {
+                tick: 10,
+                ..Default::default()
+            };
+            // the current tick is inside the range, the growths before are below tick_lower
+            tick_lower
+                .update(
+                    0,
+                    1000,
+                    0,
+                    0,
+                    false,
+                    &reward_infos,
+                    &extension_reward_infos,
+                    0,
+                )
+                .unwrap();
+            tick_upper
+                .update(
+                    0,
+                    1000,
+                    0,
+                    0,
+                    true,
+                    &reward_infos,
+                    &extension_reward_infos,
+                    0,
+                )
+                .unwrap();
+            let reward_growths_outside_x64 = tick_lower.reward_growths_outside_x64;
+            assert_eq!(reward_growths_outside_x64, [1000, 0, 0]);
+            let reward_growths_outside_extension_x64 =
+                tick_lower.reward_growths_outside_extension_x64;
+            assert_eq!(reward_growths_outside_extension_x64, [300, 0]);
+
+            extension_reward_infos[0].reward_growth_global_x64 = 800;
+            let extension_reward_growths_inside = get_extension_reward_growths_inside(
+                &tick_lower,
+                &tick_upper,
+                0,
+                &extension_reward_infos,
+            );
+            assert_eq!(extension_reward_growths_inside, [500, 0]);
+
+            // the price moves above the range, the growths stop accruing inside
+            tick_upper.cross(0, 0, &reward_infos, &extension_reward_infos, 0);
+            extension_reward_infos[0].reward_growth_global_x64 = 1200;
+            let extension_reward_growths_inside = get_extension_reward_growths_inside(
+                &tick_lower,
+                &tick_upper,
+                11,
+                &extension_reward_infos,
+            );
+            assert_eq!(extension_reward_growths_inside, [500, 0]);
+        }
+    }
+
+    mod retired_reward_growths_test {
+        use super::*;
+
+        #[test]
+        fn retired_reward_growths_stay_consistent_test() {
+            let mut reward_infos = [RewardInfo::default(); 3];
+            reward_infos[0].token_mint = Pubkey::new_unique();
+            reward_infos[0].reward_growth_global_x64 = 1000;
+            let extension_reward_infos = [RewardInfo::default(); EXTENSION_REWARD_NUM];
+
+            let mut tick_lower = TickState {
+                tick: -10,
+                ..Default::default()
+            };
+            let mut tick_upper = TickState {
+                tick: 10,
+                ..Default::default()
+            };
+            tick_lower
+                .update(
+                    0,
+                    1000,
+                    0,
+                    0,
+                    false,
+                    &reward_infos,
+                    &extension_reward_infos,
+                    0,
+                )
+                .unwrap();
+            tick_upper
+                .update(
+                    0,
+                    1000,
+                    0,
+                    0,
+                    true,
+                    &reward_infos,
+                    &extension_reward_infos,
+                    0,
+                )
+                .unwrap();
+            reward_infos[0].reward_growth_global_x64 = 1500;
+            assert_eq!(
+                get_reward_growths_inside(&tick_lower, &tick_upper, 0, &reward_infos),
+                [500, 0, 0]
+            );
+
+            // the price moves above the range while the reward is retired
+            reward_infos[0].retire();
+            tick_upper.cross(0, 0, &reward_infos, &extension_reward_infos, 0);
+            let reward_growths_outside_x64 = tick_upper.reward_growths_outside_x64;
+            assert_eq!(reward_growths_outside_x64, [1500, 0, 0]);
+            assert_eq!(
+                get_reward_growths_inside(&tick_lower, &tick_upper, 11, &reward_infos),
+                [500, 0, 0]
+            );
+
+            // the slot is reused, the growth continues from the retired one
+            reward_infos[0].token_mint = Pubkey::new_unique();
+            reward_infos[0].reward_growth_global_x64 = 2000;
+            assert_eq!(
+                get_reward_growths_inside(&tick_lower, &tick_upper, 11, &reward_infos),
+                [500, 0, 0]
+            );
+            tick_upper.cross(0, 0, &reward_infos, &extension_reward_infos, 0);
+            reward_infos[0].reward_growth_global_x64 = 2600;
+            assert_eq!(
+                get_reward_growths_inside(&tick_lower, &tick_upper, 0, &reward_infos),
+                [1100, 0, 0]
+            );
+        }
+    }
+
+    mod get_seconds_inside_test {
+        use super::*;
+
+        #[test]
+        fn seconds_inside_after_cross_test() {
+            let reward_infos = &[RewardInfo::default(); 3];
+            let extension_reward_infos = &[RewardInfo::default(); EXTENSION_REWARD_NUM];
+            let mut tick_lower = TickState {
+                tick: -10,
+                ..Default::default()
+            };
+            let mut tick_upper = TickState {
+                tick: 10,
+                ..Default::default()
+            };
+            // the position is opened at 100 while the current tick is inside the range
+            tick_lower
+                .update(
+                    0,
+                    1000,
+                    0,
+                    0,
+                    false,
+                    reward_infos,
+                    extension_reward_infos,
+                    100,
+                )
+                .unwrap();
+            tick_upper
+                .update(
+                    0,
+                    1000,
+                    0,
+                    0,
+                    true,
+                    reward_infos,
+                    extension_reward_infos,
+                    100,
+                )
+                .unwrap();
+            let seconds_lower_outside = tick_lower.seconds_outside;
+            let seconds_upper_outside = tick_upper.seconds_outside;
+            assert_eq!(seconds_lower_outside, 100);
+            assert_eq!(seconds_upper_outside, 0);
+
+            let seconds_inside_before = get_seconds_inside(&tick_lower, &tick_upper, 0, 150);
+            // the price moves out of the range at 160
+            tick_upper.cross(0, 0, reward_infos, extension_reward_infos, 160);
+            let seconds_inside_after = get_seconds_inside(&tick_lower, &tick_upper, 20, 200);
+            assert_eq!(seconds_inside_after.wrapping_sub(seconds_inside_before), 10);
+
+            // and back into the range at 300
+            tick_upper.cross(0, 0, reward_infos, extension_reward_infos, 300);
+            let seconds_inside_after = get_seconds_inside(&tick_lower, &tick_upper, 0, 400);
+            assert_eq!(
+                seconds_inside_after.wrapping_sub(seconds_inside_before),
+                110
+            );
+        }
+    }
+
     mod tick_array_layout_test {
         use super::*;
         use anchor_lang::Discriminator;
@@ -1360,15 +1680,12 @@ pub mod tick_array_test {
                 0x11223344556600778899aabbccddeeff,
                 0x11223344556677008899aabbccddeeff,
             ];
-            let mut tick_padding: [u32; 13] = [0u32; 13];
-            let mut tick_padding_data = [0u8; 4 * 13];
-            let mut offset = 0;
-            for i in 0..13 {
-                tick_padding[i] = u32::MAX - 3 * i as u32;
-                tick_padding_data[offset..offset + 4]
-                    .copy_from_slice(&tick_padding[i].to_le_bytes());
-                offset += 4;
-            }
+            let seconds_outside: u32 = 0x12345678;
+            let reward_growths_outside_extension_x64: [u128; EXTENSION_REWARD_NUM] = [
+                0x11223344556677889900aabbccddeeff,
+                0x11223344556677889988aa00ccddeeff,
+            ];
+            let boosted_liquidity_net: i128 = 0x11223344556677889900aabb00ddeeff;
 
             let mut tick_data = [0u8; TickState::LEN];
             let mut offset = 0;
@@ -1387,8 +1704,15 @@ pub mod tick_array_test {
                     .copy_from_slice(&reward_growths_outside_x64[i].to_le_bytes());
                 offset += 16;
             }
-            tick_data[offset..offset + 4 * 13].copy_from_slice(&tick_padding_data);
-            offset += 4 * 13;
+            tick_data[offset..offset + 4].copy_from_slice(&seconds_outside.to_le_bytes());
+            offset += 4;
+            for i in 0..EXTENSION_REWARD_NUM {
+                tick_data[offset..offset + 16]
+                    .copy_from_slice(&reward_growths_outside_extension_x64[i].to_le_bytes());
+                offset += 16;
+            }
+            tick_data[offset..offset + 16].copy_from_slice(&boosted_liquidity_net.to_le_bytes());
+            offset += 16;
             assert_eq!(offset, tick_data.len());
             assert_eq!(tick_data.len(), core::mem::size_of::<TickState>());
 
@@ -1446,8 +1770,16 @@ pub mod tick_array_test {
                     unpack_reward_growths_outside_x64,
                     reward_growths_outside_x64
                 );
-                let unpack_tick_padding = tick_item.padding;
-                assert_eq!(unpack_tick_padding, tick_padding);
+                let unpack_seconds_outside = tick_item.seconds_outside;
+                assert_eq!(unpack_seconds_outside, seconds_outside);
+                let unpack_reward_growths_outside_extension_x64 =
+                    tick_item.reward_growths_outside_extension_x64;
+                assert_eq!(
+                    unpack_reward_growths_outside_extension_x64,
+                    reward_growths_outside_extension_x64
+                );
+                let unpack_boosted_liquidity_net = tick_item.boosted_liquidity_net;
+                assert_eq!(unpack_boosted_liquidity_net, boosted_liquidity_net);
             }
             let unpack_initialized_tick_count = unpack_data.initialized_tick_count;
             assert_eq!(unpack_initialized_tick_count, initialized_tick_count);
@@ -77,3 +77,40 @@ pub fn get_recent_epoch() -> Result<u64> {
         .as_secs()
         / (2 * 24 * 3600))
 }
+
+/// Reject the transaction once the block timestamp is past the deadline, no deadline never expires
+pub fn check_deadline(deadline: Option<i64>, block_timestamp: i64) -> Result<()> {
+    if let Some(deadline) = deadline {
+        require_gte!(
+            deadline,
+            block_timestamp,
+            crate::error::ErrorCode::TransactionTooOld
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::error::ErrorCode;
+
+    #[test]
+    fn check_deadline_past_test() {
+        assert_eq!(
+            check_deadline(Some(99), 100).unwrap_err(),
+            ErrorCode::TransactionTooOld.into()
+        );
+    }
+
+    #[test]
+    fn check_deadline_equal_to_now_test() {
+        assert!(check_deadline(Some(100), 100).is_ok());
+        assert!(check_deadline(Some(101), 100).is_ok());
+    }
+
+    #[test]
+    fn check_deadline_none_test() {
+        assert!(check_deadline(None, i64::MAX).is_ok());
+    }
+}
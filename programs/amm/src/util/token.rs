@@ -19,16 +19,6 @@ use anchor_spl::token_2022::{
     Token2022,
 };
 use anchor_spl::token_interface::{initialize_mint2, InitializeMint2, Mint};
-use std::collections::HashSet;
-
-const MINT_WHITELIST: [&'static str; 6] = [
-    "HVbpJAQGNpkgBaYBZQBR1t7yFdvaYVp2vCQQfKKEN4tM",
-    "Crn4x1Y2HUKko7ox2EZMT6N2t2ZyH7eKtwkBGVnhEq1g",
-    "FrBfWJ4qE5sCzKm3k3JaAtqZcXUh4LvJygDeketsrsH4",
-    "2b1kV6DkPAnxd5ixfnxCpjxmKwqjjaYmCZfHsFu24GXo",
-    "DAUcJBg4jSpVoEzASxYzdqHMUN8vuTpQyG2TvDcCHfZg",
-    "AUSD1jCcCyPLybk1YnvPWsHQSrZ46dxwoMniN4N2UEB9",
-];
 
 pub fn invoke_memo_instruction<'info>(
     memo_msg: &[u8],
@@ -258,34 +248,152 @@ pub fn support_mint_associated_is_initialized(
     return Ok(mint_associated_is_initialized);
 }
 
+/// Find the mint registry in the remaining accounts, its address must be passed even before the registry is created
+pub fn load_mint_registry<'info>(
+    remaining_accounts: &'info [AccountInfo<'info>],
+) -> Result<Option<AccountLoader<'info, MintRegistryState>>> {
+    let mint_registry_key = MintRegistryState::key();
+    let account_info = remaining_accounts
+        .iter()
+        .find(|account_info| account_info.key() == mint_registry_key)
+        .ok_or(ErrorCode::MissingMintRegistryAccount)?;
+    // the registry is not created yet
+    if *account_info.owner != crate::id() {
+        return Ok(None);
+    }
+    Ok(Some(AccountLoader::<MintRegistryState>::try_from(
+        account_info,
+    )?))
+}
+
+/// Whether the mint is checked against the mint registry, which is only the case for
+/// token-2022 mints without a support mint associated account
+pub fn is_registry_checked_mint(
+    mint_account: &InterfaceAccount<Mint>,
+    mint_associated_is_initialized: bool,
+) -> bool {
+    *mint_account.to_account_info().owner != Token::id() && !mint_associated_is_initialized
+}
+
+/// Whether the operator has the role in the operation account, which is optional in the remaining accounts
+pub fn operator_has_role<'info>(
+    remaining_accounts: &'info [AccountInfo<'info>],
+    operator: Pubkey,
+    role: OperatorRoleBitIndex,
+) -> Result<bool> {
+    let operation_state_key = OperationState::key();
+    match remaining_accounts.iter().find(|account_info| {
+        *account_info.owner == crate::id() && account_info.key() == operation_state_key
+    }) {
+        Some(account_info) => {
+            let operation_state = AccountLoader::<OperationState>::try_from(account_info)?;
+            let operator_has_role = OperationState::load_with_roles(&operation_state)?
+                .validate_operator_role(operator, role);
+            Ok(operator_has_role)
+        }
+        None => Ok(false),
+    }
+}
+
+/// Load the reward extension of the pool from the remaining accounts, it must be passed once the pool has one
+pub fn load_reward_extension<'info>(
+    pool_state: &PoolState,
+    remaining_accounts: &'info [AccountInfo<'info>],
+) -> Result<Option<AccountLoader<'info, PoolRewardExtension>>> {
+    let reward_extension = find_reward_extension(pool_state, remaining_accounts)?;
+    if pool_state.reward_extension != 0 {
+        require!(
+            reward_extension.is_some(),
+            ErrorCode::MissingRewardExtensionAccount
+        );
+    }
+    Ok(reward_extension)
+}
+
+/// Find the reward extension of the pool in the remaining accounts, for the instructions which only need it
+/// when it is passed
+pub fn find_reward_extension<'info>(
+    pool_state: &PoolState,
+    remaining_accounts: &'info [AccountInfo<'info>],
+) -> Result<Option<AccountLoader<'info, PoolRewardExtension>>> {
+    if pool_state.reward_extension == 0 {
+        return Ok(None);
+    }
+    let reward_extension_key =
+        PoolRewardExtension::key(pool_state.key(), pool_state.reward_extension_bump)?;
+    remaining_accounts
+        .iter()
+        .find(|account_info| account_info.key() == reward_extension_key)
+        .map(AccountLoader::<PoolRewardExtension>::try_from)
+        .transpose()
+}
+
+/// Load the amm config of the pool from the remaining accounts, the instructions checking
+/// the status of the config must pass it
+pub fn load_amm_config<'info>(
+    pool_state: &PoolState,
+    remaining_accounts: &'info [AccountInfo<'info>],
+) -> Result<Account<'info, AmmConfig>> {
+    find_amm_config(pool_state, remaining_accounts)?
+        .ok_or(error!(ErrorCode::MissingAmmConfigAccount))
+}
+
+/// Find the amm config of the pool in the remaining accounts, the status of the config is
+/// inherited by the pool when it is passed
+pub fn find_amm_config<'info>(
+    pool_state: &PoolState,
+    remaining_accounts: &'info [AccountInfo<'info>],
+) -> Result<Option<Account<'info, AmmConfig>>> {
+    remaining_accounts
+        .iter()
+        .find(|account_info| account_info.key() == pool_state.amm_config)
+        .map(Account::<AmmConfig>::try_from)
+        .transpose()
+}
+
+/// Token-2022 mints are supported when they have a support mint associated account,
+/// or when the mint registry allows the mint itself or all of its extensions.
+/// Until the mint registry is created, the default mints and extensions are allowed as before the registry existed
 pub fn is_supported_mint(
     mint_account: &InterfaceAccount<Mint>,
     mint_associated_is_initialized: bool,
+    mint_registry: Option<&MintRegistryState>,
 ) -> Result<bool> {
     let mint_info = mint_account.to_account_info();
     if *mint_info.owner == Token::id() {
         return Ok(true);
     }
-    let mint_whitelist: HashSet<&str> = MINT_WHITELIST.into_iter().collect();
-    if mint_whitelist.contains(mint_account.key().to_string().as_str()) {
-        return Ok(true);
-    }
     if mint_associated_is_initialized {
         return Ok(true);
     }
     let mint_data = mint_info.try_borrow_data()?;
     let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
-    let extensions = mint.get_extension_types()?;
-    for e in extensions {
-        if e != ExtensionType::TransferFeeConfig
-            && e != ExtensionType::MetadataPointer
-            && e != ExtensionType::TokenMetadata
-            && e != ExtensionType::InterestBearingConfig
-        {
-            return Ok(false);
+    let mint_registry = match mint_registry {
+        Some(mint_registry) => mint_registry,
+        None => {
+            return Ok(MintRegistryState::is_default_supported(
+                mint_account.key(),
+                &mint.get_extension_types()?,
+            ))
+        }
+    };
+    if let Some(registered_mint) = mint_registry.get_mint(mint_account.key()) {
+        if registered_mint.is_restricted(RegisteredMintRestrictionBitIndex::MaxTransferFee) {
+            if let Ok(transfer_fee_config) = mint.get_extension::<TransferFeeConfig>() {
+                // the newer fee is scheduled to apply from a future epoch
+                let older_transfer_fee = &transfer_fee_config.older_transfer_fee;
+                let newer_transfer_fee = &transfer_fee_config.newer_transfer_fee;
+                let transfer_fee_bps = u16::from(older_transfer_fee.transfer_fee_basis_points)
+                    .max(u16::from(newer_transfer_fee.transfer_fee_basis_points));
+                return Ok(transfer_fee_bps <= { registered_mint.max_transfer_fee_bps });
+            }
         }
+        return Ok(true);
     }
-    Ok(true)
+    let extensions = mint.get_extension_types()?;
+    Ok(extensions
+        .into_iter()
+        .all(|extension| mint_registry.is_extension_allowed(extension)))
 }
 
 pub fn create_position_nft_mint_with_extensions<'info>(